@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+/// A short description of the command line arguments, shown when they can't be used.
+pub const USAGE: &str = "Usage: usda-alveograph-exporter --input <file>... --output <file> [--config <file>]\n\
+    usda-alveograph-exporter --input <file>... --dry-run [--config <file>]\n\
+    Run without any arguments to open the window instead.";
+
+/// The options given on the command line, for running without the gui.
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct CliArgs {
+    /// The input files to read data from.
+    pub inputs: Vec<PathBuf>,
+    /// The file to write output to.
+    pub output: PathBuf,
+    /// The config file to use instead of the usual one, if given.
+    pub config: Option<PathBuf>,
+    /// If true, the input files are only checked, and no output is written.
+    /// The output file isn't needed in this case.
+    pub dry_run: bool,
+}//end struct CliArgs
+
+/// Parses the command line arguments, not including the program name.
+/// Returns Ok(None) if there aren't any arguments, meaning the gui should be used.
+/// Returns an error message if the arguments can't be used.
+pub fn parse_args(args: &[String]) -> Result<Option<CliArgs>,String> {
+    if args.is_empty() {return Ok(None);}
+    let mut inputs = Vec::new();
+    let mut output = None;
+    let mut config = None;
+    let mut dry_run = false;
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let start_len = inputs.len();
+                while let Some(input) = args.next_if(|next| !next.starts_with("-")) {
+                    inputs.push(PathBuf::from(input));
+                }//end collecting each input file after the flag
+                if inputs.len() == start_len {return Err(format!("Expected at least one file after {}.", arg));}
+            },
+            "--output" | "-o" => {
+                if output.is_some() {return Err(format!("{} was given more than once.", arg));}
+                match args.next() {
+                    Some(path) => output = Some(PathBuf::from(path)),
+                    None => return Err(format!("Expected a file after {}.", arg)),
+                }//end matching whether there's an output path
+            },
+            "--config" | "-c" => {
+                if config.is_some() {return Err(format!("{} was given more than once.", arg));}
+                match args.next() {
+                    Some(path) => config = Some(PathBuf::from(path)),
+                    None => return Err(format!("Expected a file after {}.", arg)),
+                }//end matching whether there's a config path
+            },
+            "--dry-run" | "-n" => dry_run = true,
+            _ => return Err(format!("Unknown argument \"{}\".", arg)),
+        }//end matching each argument
+    }//end looping over each argument
+    if inputs.is_empty() {return Err("No input files were given. Use --input to list them.".to_string());}
+    match output {
+        None if dry_run => Ok(Some(CliArgs {inputs, output: PathBuf::new(), config, dry_run})),
+        None => Err("No output file was given. Use --output to choose one.".to_string()),
+        Some(output) => Ok(Some(CliArgs {inputs, output, config, dry_run})),
+    }//end matching whether we got an output file
+}//end parse_args()
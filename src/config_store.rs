@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::{env, fs::{self, File}, io::Write, path::PathBuf};
+use std::{collections::HashMap, env, fs::{self, File}, io::Write, ops::Range, path::{Path, PathBuf}};
+
+use crate::{data, logging::{self, LogLevel}};
 
 /// An enum to represent different ways of finding the header in a file.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
@@ -64,11 +66,482 @@ impl ReadRowMode {
     }//end from_str()
 }//end impl for ReadRowMode
 
+/// An enum to represent how the header and value are laid out in each
+/// line of the data block.
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Deserialize, Serialize)]
+pub enum InputLayout {
+    /// If this InputLayout is selected, then each line has a header followed
+    /// by its value, split by config read_row_split_char, such as "P\t45".
+    HeaderLineValueLine,
+    /// If this InputLayout is selected, then each line has one or more pairs of
+    /// header and value, all separated by single tabs, such as "P\t45\tL\t60".
+    TabDelimited,
+    /// If this InputLayout is selected, then the header and value are in fixed
+    /// columns of each line, counted in characters from 0, with the end left out.
+    /// Anything after the value is taken as its flag.
+    FixedWidth { header_cols: Range<usize>, value_cols: Range<usize> },
+}//end enum InputLayout
+
+impl InputLayout {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            InputLayout::HeaderLineValueLine => "HeaderLineValueLine".to_string(),
+            InputLayout::TabDelimited => "TabDelimited".to_string(),
+            InputLayout::FixedWidth { .. } => "FixedWidth".to_string(),
+        }//end matching self
+    }//end to_string()
+}//end impl for InputLayout
+
+/// An enum to represent different ways of writing values that aren't
+/// finite numbers, such as NaN or infinity, to the output.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum NonFiniteFormat {
+    /// If this NonFiniteFormat is selected, then non-finite values
+    /// will be left as empty cells in the output.
+    Blank,
+    /// If this NonFiniteFormat is selected, then non-finite values
+    /// will be written as text, such as "NaN" or "Infinity".
+    Text,
+    /// If this NonFiniteFormat is selected, then non-finite values
+    /// will be written as text in a highlighted cell.
+    Flagged,
+}//end enum NonFiniteFormat
+
+impl NonFiniteFormat {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            NonFiniteFormat::Blank => "Blank".to_string(),
+            NonFiniteFormat::Text => "Text".to_string(),
+            NonFiniteFormat::Flagged => "Flagged".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<NonFiniteFormat> {
+        match str {
+            "Blank" => Some(NonFiniteFormat::Blank),
+            "Text" => Some(NonFiniteFormat::Text),
+            "Flagged" => Some(NonFiniteFormat::Flagged),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+}//end impl for NonFiniteFormat
+
+/// An enum to represent the colors available for highlighting flagged cells.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum FlagColor {
+    Red,
+    Orange,
+    Yellow,
+    Blue,
+    Purple,
+    Gray,
+}//end enum FlagColor
+
+impl FlagColor {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            FlagColor::Red => "Red".to_string(),
+            FlagColor::Orange => "Orange".to_string(),
+            FlagColor::Yellow => "Yellow".to_string(),
+            FlagColor::Blue => "Blue".to_string(),
+            FlagColor::Purple => "Purple".to_string(),
+            FlagColor::Gray => "Gray".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<FlagColor> {
+        match str {
+            "Red" => Some(FlagColor::Red),
+            "Orange" => Some(FlagColor::Orange),
+            "Yellow" => Some(FlagColor::Yellow),
+            "Blue" => Some(FlagColor::Blue),
+            "Purple" => Some(FlagColor::Purple),
+            "Gray" => Some(FlagColor::Gray),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+
+    /// Returns a light shade of this color as 0xRRGGBB, suitable for a cell background.
+    pub fn fill_rgb(&self) -> u32 {
+        match self {
+            FlagColor::Red => 0xFFC7CE,
+            FlagColor::Orange => 0xFFDDB3,
+            FlagColor::Yellow => 0xFFEB9C,
+            FlagColor::Blue => 0xBDD7EE,
+            FlagColor::Purple => 0xE4D0F5,
+            FlagColor::Gray => 0xD9D9D9,
+        }//end matching self
+    }//end fill_rgb()
+
+    /// Returns a dark shade of this color as 0xRRGGBB, suitable for cell text.
+    pub fn font_rgb(&self) -> u32 {
+        match self {
+            FlagColor::Red => 0x9C0006,
+            FlagColor::Orange => 0xB45F06,
+            FlagColor::Yellow => 0x9C5700,
+            FlagColor::Blue => 0x1F4E79,
+            FlagColor::Purple => 0x5B2C83,
+            FlagColor::Gray => 0x404040,
+        }//end matching self
+    }//end font_rgb()
+}//end impl for FlagColor
+
+/// An enum to represent different ways of making flagged cells stand out.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum FlagStyle {
+    /// If this FlagStyle is selected, then flagged cells will
+    /// be filled with the flag color.
+    Fill,
+    /// If this FlagStyle is selected, then text in flagged cells
+    /// will be written in the flag color.
+    Font,
+    /// If this FlagStyle is selected, then text in flagged cells
+    /// will be bold, without any color.
+    Bold,
+    /// If this FlagStyle is selected, then text in flagged cells
+    /// will be italic, without any color.
+    Italic,
+}//end enum FlagStyle
+
+impl FlagStyle {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            FlagStyle::Fill => "Fill".to_string(),
+            FlagStyle::Font => "Font".to_string(),
+            FlagStyle::Bold => "Bold".to_string(),
+            FlagStyle::Italic => "Italic".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<FlagStyle> {
+        match str {
+            "Fill" => Some(FlagStyle::Fill),
+            "Font" => Some(FlagStyle::Font),
+            "Bold" => Some(FlagStyle::Bold),
+            "Italic" => Some(FlagStyle::Italic),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+}//end impl for FlagStyle
+
+/// An enum to represent the different kinds of files output can be written as.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum OutputFormat {
+    /// If this OutputFormat is selected, then output is written as an excel workbook.
+    Xlsx,
+    /// If this OutputFormat is selected, then output is written as a csv file.
+    Csv,
+    /// If this OutputFormat is selected, then the parsed data is written as a json file.
+    Json,
+}//end enum OutputFormat
+
+impl OutputFormat {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            OutputFormat::Xlsx => "Xlsx".to_string(),
+            OutputFormat::Csv => "Csv".to_string(),
+            OutputFormat::Json => "Json".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<OutputFormat> {
+        match str {
+            "Xlsx" => Some(OutputFormat::Xlsx),
+            "Csv" => Some(OutputFormat::Csv),
+            "Json" => Some(OutputFormat::Json),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+
+    /// Returns the file extension used for this format, without a dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Xlsx => "xlsx",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+        }//end matching self
+    }//end extension()
+
+    /// Attempts to match a file extension, without a dot, to a variant.
+    /// Case is ignored.
+    pub fn from_extension(ext: &str) -> Option<OutputFormat> {
+        match ext.to_ascii_lowercase().as_str() {
+            "xlsx" => Some(OutputFormat::Xlsx),
+            "csv" => Some(OutputFormat::Csv),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }//end matching ext
+    }//end from_extension()
+
+    /// Returns every variant, in the order they're offered in the gui.
+    pub fn all() -> Vec<OutputFormat> {
+        vec![OutputFormat::Xlsx, OutputFormat::Csv, OutputFormat::Json]
+    }//end all()
+
+    /// Returns the extensions of every variant, with dots, as a comma separated list
+    /// suitable for showing to the user, like ".xlsx, .csv, .json".
+    pub fn supported_extensions() -> String {
+        OutputFormat::all().iter()
+            .map(|format| format!(".{}", format.extension()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }//end supported_extensions()
+}//end impl for OutputFormat
+
+/// An enum to represent the different ways of laying out data in the output.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum OutputLayout {
+    /// If this OutputLayout is selected, then each input file is a row
+    /// of output, with one column for each measurement.
+    Wide,
+    /// If this OutputLayout is selected, then each measurement is a row
+    /// of output, with a group of columns for each input file.
+    Pivot,
+    /// If this OutputLayout is selected, then every input file is a row of a
+    /// single sheet, led by its source file and test name, with a column for
+    /// each measurement found in any of the files.
+    Merged,
+}//end enum OutputLayout
+
+impl OutputLayout {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            OutputLayout::Wide => "Wide".to_string(),
+            OutputLayout::Pivot => "Pivot".to_string(),
+            OutputLayout::Merged => "Merged".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<OutputLayout> {
+        match str {
+            "Wide" => Some(OutputLayout::Wide),
+            "Pivot" => Some(OutputLayout::Pivot),
+            "Merged" => Some(OutputLayout::Merged),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+}//end impl for OutputLayout
+
+/// An enum to represent the statistics that can be written for each header
+/// in the summary sheet.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum SummaryStat {
+    /// The number of files with a value for the header.
+    Count,
+    /// The average of the header's values.
+    Mean,
+    /// The smallest of the header's values.
+    Min,
+    /// The largest of the header's values.
+    Max,
+    /// The sample standard deviation of the header's values.
+    StdDev,
+    /// The standard error of the mean, which is StdDev divided by the square root of Count.
+    StdErr,
+    /// The coefficient of variation, which is StdDev divided by Mean.
+    Cv,
+}//end enum SummaryStat
+
+impl SummaryStat {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            SummaryStat::Count => "Count".to_string(),
+            SummaryStat::Mean => "Mean".to_string(),
+            SummaryStat::Min => "Min".to_string(),
+            SummaryStat::Max => "Max".to_string(),
+            SummaryStat::StdDev => "Std Dev".to_string(),
+            SummaryStat::StdErr => "Std Err".to_string(),
+            SummaryStat::Cv => "CV".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<SummaryStat> {
+        match str {
+            "Count" => Some(SummaryStat::Count),
+            "Mean" => Some(SummaryStat::Mean),
+            "Min" => Some(SummaryStat::Min),
+            "Max" => Some(SummaryStat::Max),
+            "Std Dev" => Some(SummaryStat::StdDev),
+            "Std Err" => Some(SummaryStat::StdErr),
+            "CV" => Some(SummaryStat::Cv),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+}//end impl for SummaryStat
+
+/// An enum to represent what happens when processing is started without
+/// any input files selected.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum EmptyInputAction {
+    /// If this EmptyInputAction is selected, then the user is told to
+    /// select input files before processing.
+    Error,
+    /// If this EmptyInputAction is selected, then the input files from the
+    /// last successful run are used again.
+    LastFiles,
+    /// If this EmptyInputAction is selected, then the input files listed
+    /// in the manifest file at this path are used.
+    Manifest(PathBuf),
+}//end enum EmptyInputAction
+
+impl EmptyInputAction {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            EmptyInputAction::Error => "Error".to_string(),
+            EmptyInputAction::LastFiles => "LastFiles".to_string(),
+            EmptyInputAction::Manifest(_) => "Manifest".to_string(),
+        }//end matching self
+    }//end to_string()
+}//end impl for EmptyInputAction
+
+/// The acceptable values for a measurement, such as W or P.
+/// Either bound can be left out to only check the other.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ValueRange {
+    /// The lowest acceptable value, if there is one.
+    pub min: Option<f64>,
+    /// The highest acceptable value, if there is one.
+    pub max: Option<f64>,
+}//end struct ValueRange
+
+impl ValueRange {
+    /// Returns true if value is below min or above max.  
+    /// Values that aren't finite numbers are never out of range,
+    /// since non_finite_format already decides how they're shown.
+    pub fn is_out_of_range(&self, value: f64) -> bool {
+        if !value.is_finite() {return false;}
+        self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max)
+    }//end is_out_of_range()
+
+    /// Returns a description of the range, such as "150 to 400" or "at least 150".
+    pub fn to_string(&self) -> String {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{} to {}", min, max),
+            (Some(min), None) => format!("at least {}", min),
+            (None, Some(max)) => format!("at most {}", max),
+            (None, None) => "any value".to_string(),
+        }//end matching which bounds there are
+    }//end to_string()
+}//end impl ValueRange
+
+/// A linear conversion applied to every value of a measurement as it's read,
+/// such as to change units. Values become value * factor + offset.
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UnitConversion {
+    /// The amount each value is multiplied by.
+    pub factor: f64,
+    /// The amount added to each value after multiplying by factor.
+    pub offset: f64,
+}//end struct UnitConversion
+
+impl Default for UnitConversion {
+    fn default() -> Self {
+        UnitConversion { factor: 1.0, offset: 0.0 }
+    }//end default()
+}//end impl Default for UnitConversion
+
+impl UnitConversion {
+    /// Gets value converted with factor and offset.
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }//end apply()
+}//end impl UnitConversion
+
+/// An enum to represent the arithmetic a DerivedMetric does with its two operands.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum DerivedOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}//end enum DerivedOperator
+
+impl DerivedOperator {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            DerivedOperator::Add => "+".to_string(),
+            DerivedOperator::Subtract => "-".to_string(),
+            DerivedOperator::Multiply => "*".to_string(),
+            DerivedOperator::Divide => "/".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<DerivedOperator> {
+        match str {
+            "+" => Some(DerivedOperator::Add),
+            "-" => Some(DerivedOperator::Subtract),
+            "*" => Some(DerivedOperator::Multiply),
+            "/" => Some(DerivedOperator::Divide),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+
+    /// Gets the result of this operator on left and right.  
+    /// Returns None when dividing by 0, since there's no result to use.
+    pub fn apply(&self, left: f64, right: f64) -> Option<f64> {
+        match self {
+            DerivedOperator::Add => Some(left + right),
+            DerivedOperator::Subtract => Some(left - right),
+            DerivedOperator::Multiply => Some(left * right),
+            DerivedOperator::Divide => if right == 0.0 {None} else {Some(left / right)},
+        }//end matching self
+    }//end apply()
+}//end impl for DerivedOperator
+
+/// A measurement worked out from two others read from each file, such as P/L,
+/// and added after them.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DerivedMetric {
+    /// The header the result is written under, such as "P/L".
+    pub name: String,
+    /// Two headers with one of +, -, *, or / between them, such as "P/L" or "W*L".
+    /// Spaces around the headers are ignored.
+    pub expression: String,
+}//end struct DerivedMetric
+
+impl DerivedMetric {
+    /// Splits expression into its left header, operator, and right header.  
+    /// The expression is split at the first operator with a header on both sides,
+    /// so headers can't contain an operator. Returns None if there isn't one.
+    pub fn parse_expression(&self) -> Option<(String, DerivedOperator, String)> {
+        for (idx, c) in self.expression.char_indices() {
+            let Some(operator) = DerivedOperator::from_str(&c.to_string()) else {continue;};
+            let (left, right) = (self.expression[..idx].trim(), self.expression[idx + c.len_utf8()..].trim());
+            if !left.is_empty() && !right.is_empty() {return Some((left.to_string(), operator, right.to_string()));}
+        }//end looking for an operator with a header on each side
+        None
+    }//end parse_expression()
+}//end impl DerivedMetric
+
 /// This struct is meant to store configuration information
 /// in a way that is not reliant on a specific ui implementation,
-/// such that it can be passed around easily.
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+/// such that it can be passed around easily.  
+/// Any fields missing from a config file are filled in from the default config.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ConfigStore {
+    /// The version of the config file layout this config was written with, which
+    /// migrate() uses to upgrade older files. Always CONFIG_SCHEMA_VERSION once read.
+    pub schema_version: u32,
     /// The exact string header above where the data rows start.
     pub read_start_header: String,
     /// An optional alternative to read_start_header. If the correct ReadMode is
@@ -94,9 +567,189 @@ pub struct ConfigStore {
     /// The character (or string) to split on when separating the header
     /// from the data in a row.
     pub read_row_split_char: String,
+    /// How the header and value are laid out in each line of the data block.
+    pub input_layout: InputLayout,
     /// The string directly in front of the test-name, which is used to label
     /// which file data came from.
     pub read_test_name_prefix: String,
+    /// Other prefixes which can also label the test name, for instruments which
+    /// label it differently, such as "Sample:" or "Test ID =". Empty ones are ignored.
+    pub read_test_name_alt_prefixes: Vec<String>,
+    /// The number of lines directly after the start header to skip before
+    /// reading any data rows. Useful for summary lines above the measurements.
+    pub skip_rows_after_start: usize,
+    /// The number of lines at the end of the data block to skip. The data block
+    /// runs from the start header to the end header, or the end of the file if
+    /// there isn't one, so this can skip a fixed footer, like a signature block.
+    pub skip_rows_before_end: usize,
+    /// How values which aren't finite numbers, such as NaN or infinity, are written to output.
+    pub non_finite_format: NonFiniteFormat,
+    /// If true, grouping (thousands) separators such as the commas in "1,234.56"
+    /// will be removed from values before they're parsed.
+    pub strip_thousands_separators: bool,
+    /// If true, values are read with a comma as the decimal mark, such as "283,4",
+    /// as written by instruments set to many European locales.
+    pub decimal_comma: bool,
+    /// If true, then alongside the xlsx output, each sheet will also be written
+    /// as a separate csv file, in a folder next to the xlsx file.
+    pub export_sheets_as_csv: bool,
+    /// The color used to highlight flagged cells in the output, including
+    /// values outside their range in value_ranges.
+    pub flag_color: FlagColor,
+    /// The way flagged cells, including values out of range, are made to stand out in the output.
+    pub flag_style: FlagStyle,
+    /// The name of the sheet to write output to. The tokens {date}, {time},
+    /// and {index} are replaced with the current date, current time, and
+    /// the 1-based number of the sheet within the workbook.
+    pub sheet_name_template: String,
+    /// How often, in seconds, the config is automatically saved while the
+    /// program is open. If 0, the config is only saved when closing.
+    pub auto_save_interval_secs: u32,
+    /// If true, a successful export in the gui finishes with a dialog offering to open
+    /// the output file or its folder. If false, the summary is shown without those choices.
+    pub prompt_open_output: bool,
+    /// If true, all input files are averaged together into a single row
+    /// of output, instead of writing one row per file.
+    pub average_all_inputs: bool,
+    /// The test name given to the averaged row when average_all_inputs is used.
+    pub average_row_name: String,
+    /// The row headers to include in the output. Rows with any other header
+    /// are left out. If empty, every row is included.
+    pub include_headers: Vec<String>,
+    /// If not empty, only files whose test name matches one of these patterns are exported.
+    /// A pattern with * or ? is matched as a wildcard against the whole name, otherwise
+    /// it only needs to be found somewhere in the name. Case is ignored.
+    pub test_name_include: Vec<String>,
+    /// Files whose test name matches one of these patterns are left out of the export,
+    /// even if they also match test_name_include. Patterns work as in test_name_include.
+    pub test_name_exclude: Vec<String>,
+    /// If true, the line found by the read start mode is itself the first
+    /// data row. If false, data rows start on the line after it.
+    pub start_header_is_data: bool,
+    /// If true, an empty read_start_header with the Header read start mode means
+    /// the file has no marker, so data rows are read from the first line of the file.
+    pub empty_start_header_reads_from_start: bool,
+    /// The kind of file the output is written as.
+    pub output_format: OutputFormat,
+    /// If true, any text after the value in a data row is read as a quality flag
+    /// for that measurement, and written to the output in a column next to the value.
+    pub parse_row_flags: bool,
+    /// How data is laid out in the output.
+    pub output_layout: OutputLayout,
+    /// The exact string line below where the data rows end. If empty,
+    /// data rows are read until the end of the file.
+    pub read_end_header: String,
+    /// The folder that typed output names are saved into. If empty, the
+    /// folder of the first input file is used instead.
+    pub default_output_dir: String,
+    /// The output name filled in whenever the output is cleared, with the same
+    /// tokens as sheet_name_template. If empty, nothing is filled in.
+    pub output_name_template: String,
+    /// What to do when processing is started without any input files selected.
+    pub on_empty_input: EmptyInputAction,
+    /// Columns with a fixed value, written after the data for every row, as pairs
+    /// of header and value. Values can use the same tokens as sheet_name_template.
+    pub constant_columns: Vec<(String, String)>,
+    /// If true, read_start_header and read_end_header are matched against lines
+    /// ignoring trailing punctuation and differences in whitespace, instead of exactly.
+    pub normalize_header_match: bool,
+    /// If true, read_start_header and read_end_header are matched against lines
+    /// after trimming whitespace from both ends, ignoring upper and lower case.
+    pub header_match_case_insensitive: bool,
+    /// If true, the header of each data row read is trimmed, with runs of whitespace
+    /// and non-breaking spaces collapsed into single spaces, by data::normalize_header().
+    pub normalize_headers: bool,
+    /// The character placed between fields in csv output.
+    pub csv_delimiter: char,
+    /// The character used to surround csv fields which contain the delimiter,
+    /// a quote, or a line break.
+    pub csv_quote: char,
+    /// If true, xlsx output gets an extra sheet with statistics for each header
+    /// across all the input files.
+    pub include_summary_sheet: bool,
+    /// The statistics written in the summary sheet, in the order they're written.
+    pub summary_stats: Vec<SummaryStat>,
+    /// If true, the wide layout gets a column with the name of the file each
+    /// row of data was read from, after the data.
+    pub include_source_file_column: bool,
+    /// If true, each input file is written to its own sheet, named after its test name,
+    /// instead of writing every file to one sheet. Takes priority over max_rows_per_sheet.
+    pub sheet_per_file: bool,
+    /// If set, the wide layout is split across several sheets, each holding at most
+    /// this many rows of data, with the header row repeated on each sheet.
+    pub max_rows_per_sheet: Option<usize>,
+    /// If true, choosing an input folder also finds the txt files in its subfolders,
+    /// rather than just those directly inside it.
+    pub input_folder_recursive: bool,
+    /// The folder the input file dialog was last used in, so it can start there next time.
+    pub last_input_dir: Option<String>,
+    /// The folder the output file dialog was last used in, so it can start there next time.
+    pub last_output_dir: Option<String>,
+    /// The acceptable range for each measurement, by header. Values outside their
+    /// range are highlighted in xlsx output. Headers not listed aren't checked.
+    pub value_ranges: HashMap<String, ValueRange>,
+    /// The conversion applied to each measurement as it's read, by header, such as
+    /// to change units. Headers not listed are left as they are. value_ranges and
+    /// everything else after reading see the converted values.
+    pub unit_conversions: HashMap<String, UnitConversion>,
+    /// The units of each measurement, by header, such as "mm" for "L". If any are
+    /// given, a row of units is written beneath the header row in the wide layout
+    /// of xlsx output, left blank for headers not listed.
+    pub header_units: HashMap<String, String>,
+    /// If true, the header row of the wide layout in xlsx output stays in view
+    /// while scrolling, and gets filter buttons for sorting and filtering.
+    pub excel_freeze_and_filter: bool,
+    /// If true, statistics in the summary sheet are written as excel formulas over
+    /// the values in the wide layout, so they update when those values are changed.
+    pub summary_use_formulas: bool,
+    /// The sets of input files most recently processed, most recent first,
+    /// so they can be picked again. Holds at most RECENT_INPUTS_MAX sets.
+    pub recent_inputs: Vec<Vec<String>>,
+    /// The order and names of columns in the wide layout, as pairs of row header and
+    /// the name to show it as. Listed headers come first, in this order, followed by any
+    /// others in the order they were read. An empty name keeps the header as it is.
+    pub column_display: Vec<(String, String)>,
+    /// If true, values within zero_epsilon of 0 are treated as placeholders for
+    /// measurements that weren't taken. They're left blank in the output and
+    /// left out of the summary statistics.
+    pub skip_zero_rows: bool,
+    /// How close to 0 a value must be to be skipped by skip_zero_rows.
+    /// If 0, only values of exactly 0 are skipped.
+    pub zero_epsilon: f64,
+    /// The least serious level of message written to the log file. Debug adds
+    /// details such as each file read and its encoding.
+    pub log_level: LogLevel,
+    /// The width of the window when the program was last closed, if known.
+    pub window_width: Option<i32>,
+    /// The height of the window when the program was last closed, if known.
+    pub window_height: Option<i32>,
+    /// The horizontal screen position of the window when the program was last closed, if known.
+    pub window_x: Option<i32>,
+    /// The vertical screen position of the window when the program was last closed, if known.
+    pub window_y: Option<i32>,
+    /// If true, xlsx output gets an extra sheet recording when and how it was made,
+    /// such as the program version, input files, and key settings.
+    pub include_metadata_sheet: bool,
+    /// If true, the wide layout gets a column for each "key: value" line found above
+    /// the start header in the input files, such as the operator, right after the test name.
+    pub include_file_metadata_columns: bool,
+    /// Measurements worked out from the others in each file, such as P/L, added
+    /// after them in this order, before include_headers and row_order_preference
+    /// are applied. A metric can use the ones listed before it.
+    pub derived_metrics: Vec<DerivedMetric>,
+    /// If set, each sheet in the wide layout of xlsx output gets a bar chart below
+    /// the data, of the value of this header for each test, such as "W".
+    pub chart_metric: Option<String>,
+    /// The number of decimal places values are shown with in output, from 0 to
+    /// MAX_DECIMAL_PLACES. Values are still written in full to xlsx cells, but are
+    /// rounded to this many places in csv output and copied text.
+    pub decimal_places: u8,
+    /// The number of decimal places to show for particular headers, by header,
+    /// instead of decimal_places.
+    pub header_decimal_places: HashMap<String, u8>,
+    /// If true, values in xlsx output are grouped by thousands, such as 1,234.00.
+    /// Like decimal_places, this only changes how values are shown.
+    pub use_thousands_separator: bool,
 }//end struct ConfigStore
 
 impl Default for ConfigStore {
@@ -111,6 +764,7 @@ impl Default for ConfigStore {
             ].iter().map(|str| str.to_string()).collect();
 
         Self {
+            schema_version: CONFIG_SCHEMA_VERSION,
             read_start_header: "Standard\t : \tAverage".to_string(),
             read_start_idx: 105,
             read_row_headers,
@@ -119,72 +773,469 @@ impl Default for ConfigStore {
             read_row_mode: ReadRowMode::Header,
             row_order_preference,
             read_row_split_char: "\t".to_string(),
+            input_layout: InputLayout::HeaderLineValueLine,
             read_test_name_prefix: "Test name\t:\t".to_string(),
+            read_test_name_alt_prefixes: Vec::new(),
+            skip_rows_after_start: 0,
+            skip_rows_before_end: 0,
+            non_finite_format: NonFiniteFormat::Flagged,
+            strip_thousands_separators: false,
+            decimal_comma: false,
+            export_sheets_as_csv: false,
+            flag_color: FlagColor::Red,
+            flag_style: FlagStyle::Fill,
+            sheet_name_template: "alveograph-exporter-output".to_string(),
+            auto_save_interval_secs: 0,
+            prompt_open_output: true,
+            average_all_inputs: false,
+            average_row_name: "Average".to_string(),
+            include_headers: Vec::new(),
+            test_name_include: Vec::new(),
+            test_name_exclude: Vec::new(),
+            start_header_is_data: false,
+            empty_start_header_reads_from_start: false,
+            output_format: OutputFormat::Xlsx,
+            parse_row_flags: false,
+            output_layout: OutputLayout::Wide,
+            read_end_header: "".to_string(),
+            default_output_dir: "".to_string(),
+            output_name_template: "".to_string(),
+            on_empty_input: EmptyInputAction::Error,
+            constant_columns: Vec::new(),
+            normalize_header_match: false,
+            header_match_case_insensitive: false,
+            normalize_headers: false,
+            csv_delimiter: ',',
+            csv_quote: '"',
+            include_summary_sheet: false,
+            summary_stats: vec![SummaryStat::Count, SummaryStat::Mean, SummaryStat::Min, SummaryStat::Max, SummaryStat::StdDev],
+            include_source_file_column: false,
+            sheet_per_file: false,
+            max_rows_per_sheet: None,
+            input_folder_recursive: false,
+            last_input_dir: None,
+            last_output_dir: None,
+            value_ranges: HashMap::new(),
+            unit_conversions: HashMap::new(),
+            header_units: HashMap::new(),
+            excel_freeze_and_filter: true,
+            summary_use_formulas: false,
+            recent_inputs: Vec::new(),
+            column_display: Vec::new(),
+            skip_zero_rows: false,
+            zero_epsilon: 0.0,
+            log_level: LogLevel::Info,
+            window_width: None,
+            window_height: None,
+            window_x: None,
+            window_y: None,
+            include_metadata_sheet: false,
+            include_file_metadata_columns: false,
+            derived_metrics: Vec::new(),
+            chart_metric: None,
+            decimal_places: 2,
+            header_decimal_places: HashMap::new(),
+            use_thousands_separator: false,
         }//end struct construction
     }//end default()
 }//end impl Default for ConfigStore
 
-/// Attempts to determine the path to the config file.  
-/// Assumes that config file has filename of config_name and extension of .config.  
-/// If create_if_missing is true, and the file at path does not exist, then it will be created with default values.  
-/// If create_if_missing is false, then this function does not check whether or not the filepath exists.
-pub fn try_read_config_path(config_name: &str, create_if_missing: bool) -> Result<PathBuf, String> {
-    // directory which contains exe this program runs from
-    let exe_path = {
-        match env::current_exe() {
-            Ok(exe_path) => exe_path,
-            Err(error) => return Err(error.to_string()),
-        }//end matching whether we could get the current exe path
-    };
+impl ConfigStore {
+    /// Gets every prefix which can label the test name, with read_test_name_prefix
+    /// first, followed by any read_test_name_alt_prefixes that aren't empty.
+    pub fn get_test_name_prefixes(&self) -> Vec<&str> {
+        let mut prefixes = vec![self.read_test_name_prefix.as_str()];
+        prefixes.extend(self.read_test_name_alt_prefixes.iter().map(|prefix| prefix.as_str()).filter(|prefix| !prefix.is_empty()));
+        prefixes
+    }//end get_test_name_prefixes()
+
+    /// Gets the number of decimal places to show values of header with, from
+    /// header_decimal_places or decimal_places, clamped to MAX_DECIMAL_PLACES.
+    pub fn get_decimal_places(&self, header: &str) -> u8 {
+        self.header_decimal_places.get(header).copied().unwrap_or(self.decimal_places).min(MAX_DECIMAL_PLACES)
+    }//end get_decimal_places()
+
+    /// Returns true if value should be left out of the output because of skip_zero_rows.
+    pub fn is_skipped_zero(&self, value: f64) -> bool {
+        self.skip_zero_rows && data::is_zero(value, self.zero_epsilon)
+    }//end is_skipped_zero()
+
+    /// Adds inputs to the front of recent_inputs, removing any earlier copy of the
+    /// same set of files, and dropping the oldest sets past RECENT_INPUTS_MAX.
+    pub fn add_recent_inputs(&mut self, inputs: &[PathBuf]) {
+        if inputs.is_empty() {return;}
+        let inputs: Vec<String> = inputs.iter().map(|path| path.to_string_lossy().into_owned()).collect();
+        self.recent_inputs.retain(|recent| *recent != inputs);
+        self.recent_inputs.insert(0, inputs);
+        self.recent_inputs.truncate(RECENT_INPUTS_MAX);
+    }//end add_recent_inputs()
 
-    // set config path to be same parent as exe_path, but config_name
-    let config_path = {
-        let mut config_path = exe_path.clone();
-        config_path.set_file_name(config_name);
-        config_path.set_extension("json");
-        config_path
+    /// Checks the config for settings that can't work together, such as an
+    /// empty read_start_header when searching for the start header.  
+    /// If there are any problems, a message for each is returned.
+    pub fn validate(&self) -> Result<(),Vec<String>> {
+        let mut problems = Vec::new();
+        if self.read_start_mode == ReadStartMode::Header {
+            if self.read_start_header.trim().is_empty() {
+                // with empty_start_header_reads_from_start, there's no marker and data starts at the top of the file
+                if !self.empty_start_header_reads_from_start {
+                    problems.push("The Read Start Header is empty, so the start of the data can't be found. Please enter the header to look for, or switch the Read Start Mode to Index.".to_string());
+                }//end if an empty start header isn't allowed
+            } else if self.normalize_header_match && data::normalize_header_line(&self.read_start_header).is_empty() {
+                problems.push(format!("The Read Start Header \"{}\" is only punctuation, so with normalize_header_match it would match blank lines. Please enter the header to look for, or turn off normalize_header_match.", self.read_start_header));
+            } else if data::header_line_matches(&self.read_end_header, &self.read_start_header, self) {
+                problems.push(format!("The Read Start Header and read_end_header are both \"{}\", so there would be no data between them. Please change one of them.", self.read_start_header));
+            }//end else if start and end would match the same line
+        }//end if we search for the start header
+        if self.csv_delimiter == self.csv_quote {
+            problems.push(format!("csv_delimiter and csv_quote are both '{}', so csv output couldn't be read back correctly. Please change one of them.", self.csv_delimiter));
+        } else if ['\n', '\r'].contains(&self.csv_delimiter) || ['\n', '\r'].contains(&self.csv_quote) {
+            problems.push("csv_delimiter and csv_quote can't be line breaks. Please change them to another character.".to_string());
+        }//end else if the csv characters would break up lines
+        if self.read_row_mode == ReadRowMode::Max && self.read_max_rows == 0 {
+            problems.push("Read Rows Max is 0, so no rows of data would be read. Please enter the number of rows to read, or switch the Read Row Mode to Header.".to_string());
+        }//end if no rows would be read
+        if self.read_row_mode == ReadRowMode::Header && self.read_row_headers.iter().all(|header| header.trim().is_empty()) {
+            problems.push("The Read Row Headers are empty, so no rows of data would be read. Please enter the headers to look for, or switch the Read Row Mode to Max.".to_string());
+        }//end if there are no row headers to read
+        if self.test_name_exclude.iter().any(|pattern| pattern.trim().is_empty()) {
+            problems.push("test_name_exclude has a blank pattern, which would leave out every file. Please remove it.".to_string());
+        }//end if every file would be excluded
+        if self.max_rows_per_sheet == Some(0) {
+            problems.push("max_rows_per_sheet is 0, so no data could be written to any sheet. Please set it to at least 1, or to null to keep all the data on one sheet.".to_string());
+        }//end if sheets couldn't hold any rows
+        if self.include_summary_sheet && self.summary_stats.is_empty() {
+            problems.push("include_summary_sheet is on, but summary_stats is empty, so the summary sheet would have nothing in it. Please list the statistics to include, or turn off include_summary_sheet.".to_string());
+        }//end if the summary sheet would be empty
+        let mut range_headers: Vec<&String> = self.value_ranges.keys().collect();
+        range_headers.sort();
+        for header in range_headers {
+            let range = self.value_ranges[header];
+            if let (Some(min), Some(max)) = (range.min, range.max) {
+                if min > max {problems.push(format!("The value range for \"{}\" has a min of {}, which is above its max of {}. Please swap them.", header, min, max));}
+            }//end if both bounds are given
+            if range.min.is_some_and(|min| !min.is_finite()) || range.max.is_some_and(|max| !max.is_finite()) {
+                problems.push(format!("The value range for \"{}\" has a bound that isn't a number. Please use a number, or leave the bound out.", header));
+            }//end if a bound isn't usable
+        }//end checking each value range
+        let mut conversion_headers: Vec<&String> = self.unit_conversions.keys().collect();
+        conversion_headers.sort();
+        for header in conversion_headers {
+            let conversion = self.unit_conversions[header];
+            if !conversion.factor.is_finite() || !conversion.offset.is_finite() {
+                problems.push(format!("The unit conversion for \"{}\" has a factor or offset that isn't a number. Please use numbers for both.", header));
+            } else if conversion.factor == 0.0 {
+                problems.push(format!("The unit conversion for \"{}\" has a factor of 0, which would make every value the same. Please use a different factor.", header));
+            }//end else if the factor would throw away the values
+        }//end checking each unit conversion
+        let mut display_names: Vec<&str> = Vec::new();
+        for (idx, (header, name)) in self.column_display.iter().enumerate() {
+            if self.column_display[..idx].iter().any(|(earlier,_)| earlier == header) {
+                problems.push(format!("The header \"{}\" is listed more than once in the column names. Please remove one of them.", header));
+                continue;
+            }//end if this header was already listed
+            let display_name = if name.is_empty() {header.as_str()} else {name.as_str()};
+            if display_names.contains(&display_name) {
+                problems.push(format!("More than one column would be named \"{}\", so they couldn't be told apart. Please give them different names.", display_name));
+            } else {display_names.push(display_name);}
+        }//end checking each column name
+        if let InputLayout::FixedWidth { header_cols, value_cols } = &self.input_layout {
+            if header_cols.is_empty() || value_cols.is_empty() {
+                problems.push("The fixed width input layout needs both header_cols and value_cols to have an end after their start.".to_string());
+            } else if header_cols.start < value_cols.end && value_cols.start < header_cols.end {
+                problems.push(format!("The fixed width columns for headers ({:?}) and values ({:?}) overlap. Please make them separate.", header_cols, value_cols));
+            }//end else if the columns overlap
+        }//end if the fixed width columns need checking
+        for (idx, metric) in self.derived_metrics.iter().enumerate() {
+            if metric.name.trim().is_empty() {
+                problems.push(format!("The derived metric with expression \"{}\" doesn't have a name. Please give it one.", metric.expression));
+            } else if self.derived_metrics[..idx].iter().any(|earlier| earlier.name == metric.name) {
+                problems.push(format!("There's more than one derived metric named \"{}\". Please remove or rename one of them.", metric.name));
+            }//end else if this name was already used
+            if metric.parse_expression().is_none() {
+                problems.push(format!("The derived metric \"{}\" has the expression \"{}\", which isn't two headers with one of +, -, *, or / between them, like \"P/L\".", metric.name, metric.expression));
+            }//end if the expression can't be used
+        }//end checking each derived metric
+        if !self.zero_epsilon.is_finite() || self.zero_epsilon < 0.0 {
+            problems.push(format!("zero_epsilon is {}, but it needs to be a number of at least 0. Please change it.", self.zero_epsilon));
+        }//end if the zero epsilon can't be used
+        if problems.is_empty() {Ok(())} else {Err(problems)}
+    }//end validate()
+}//end impl ConfigStore
+
+/// The version of the config file layout written by this version of the program.  
+/// Configs without a schema_version are version 0, from before it was added.
+pub const CONFIG_SCHEMA_VERSION: u32 = 1;
+
+/// Reads a ConfigStore from the raw json of a config, upgrading it from older
+/// versions of the config file layout first, as given by its schema_version.  
+/// Each setting is read on its own, so a setting that can't be read, such as one
+/// with the wrong type, falls back to its default, with a warning logged, instead
+/// of losing every other setting. Settings missing from raw_value use their defaults,
+/// and ones this version doesn't know about are ignored.
+pub fn migrate(raw_value: serde_json::Value) -> ConfigStore {
+    let serde_json::Value::Object(mut raw_fields) = raw_value else {
+        logging::log_warn("The config isn't a json object, so the default config was used.");
+        return ConfigStore::default();
     };
+    let mut version = raw_fields.get("schema_version").and_then(|version| version.as_u64()).unwrap_or(0) as u32;
+    while version < CONFIG_SCHEMA_VERSION {
+        upgrade_config_fields(&mut raw_fields, version);
+        version += 1;
+    }//end upgrading one version at a time
+    raw_fields.remove("schema_version");
+
+    let serde_json::Value::Object(mut fields) = serde_json::to_value(ConfigStore::default()).expect("The default config should always serialize.")
+        else {unreachable!("ConfigStore serializes to a json object.")};
+    for (name, value) in raw_fields {
+        if !fields.contains_key(&name) {continue;}
+        let mut candidate = fields.clone();
+        candidate.insert(name.clone(), value);
+        match serde_json::from_value::<ConfigStore>(serde_json::Value::Object(candidate.clone())) {
+            Ok(_) => fields = candidate,
+            Err(err) => logging::log_warn(&format!("Couldn't read the config setting \"{}\", so its default was used: {}", name, err)),
+        }//end matching whether this setting could be read
+    }//end reading each setting on its own
+    serde_json::from_value(serde_json::Value::Object(fields)).expect("Every setting was already checked.")
+}//end migrate()
+
+/// Upgrades the raw settings of a config from the given version of the config
+/// file layout to the next one, such as by renaming settings.
+fn upgrade_config_fields(fields: &mut serde_json::Map<String, serde_json::Value>, from_version: u32) {
+    // version 0 configs only ever gained settings, which get their defaults, so
+    // there's nothing to change yet. Changes to the layout go here, by from_version.
+    fields.insert("schema_version".to_string(), serde_json::Value::from(from_version + 1));
+}//end upgrade_config_fields()
+
+/// The most decimal places values can be shown with, from config decimal_places.
+pub const MAX_DECIMAL_PLACES: u8 = 8;
+
+/// The name of the profile used for configs that weren't given one.
+pub const DEFAULT_PROFILE_NAME: &str = "Default";
+
+/// The most sets of input files kept in config recent_inputs.
+pub const RECENT_INPUTS_MAX: usize = 10;
+
+/// A set of named configurations, such as one for each instrument,
+/// along with which of them is currently in use.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ConfigProfiles {
+    /// Each configuration, by profile name.
+    profiles: HashMap<String, ConfigStore>,
+    /// The name of the profile currently in use. This is always a key in profiles.
+    active: String,
+}//end struct ConfigProfiles
+
+impl Default for ConfigProfiles {
+    fn default() -> Self {ConfigProfiles::new(ConfigStore::default())}
+}//end impl Default for ConfigProfiles
+
+impl ConfigProfiles {
+    /// Creates a new ConfigProfiles with config as its only profile, named DEFAULT_PROFILE_NAME.
+    pub fn new(config: ConfigStore) -> ConfigProfiles {
+        let mut profiles = HashMap::new();
+        profiles.insert(DEFAULT_PROFILE_NAME.to_string(), config);
+        ConfigProfiles { profiles, active: DEFAULT_PROFILE_NAME.to_string() }
+    }//end new()
+
+    /// Makes sure the active profile exists, switching to the first
+    /// profile by name, or adding a default one, if it doesn't.
+    fn ensure_active(&mut self) {
+        if self.profiles.contains_key(&self.active) {return;}
+        match self.get_profile_names().into_iter().next() {
+            Some(name) => self.active = name,
+            None => *self = ConfigProfiles::default(),
+        }//end matching whether there's any profile to use
+    }//end ensure_active()
 
-    // depending on parameter, ensure config file exists
-    if !config_path.exists() && create_if_missing {
-        match File::create(config_path.clone()) {
-            Ok(mut file) => {
-                let default_config = ConfigStore::default();
-                match serde_json::to_string_pretty(&default_config) {
-                    Ok(serialized_config) => {
-                        match file.write_all(serialized_config.as_bytes()) {
-                            Ok(_) => (),
-                            Err(error) => return Err(error.to_string()),
-                        }//end matching whether file write was successful
-                    },
-                    Err(error) => return Err(error.to_string()),
-                }//end matching whether or not serde serialization worked
-            },
-            Err(error) => return Err(error.to_string()),
-        }//end matching if file was created
-    }//end if config_path does not exist
-
-    Ok(config_path)
+    /// Gets the config for the profile currently in use.
+    pub fn get_active(&self) -> &ConfigStore {
+        self.profiles.get(&self.active).expect("The active profile should always exist.")
+    }//end get_active()
+
+    /// Gets the name of the profile currently in use.
+    pub fn get_active_name(&self) -> &str {&self.active}
+
+    /// Switches to the profile with the given name.  
+    /// Returns an error if there isn't a profile with that name.
+    pub fn set_active(&mut self, name: &str) -> Result<(),String> {
+        if !self.profiles.contains_key(name) {return Err(format!("There isn't a config profile named \"{}\".", name));}
+        self.active = name.to_string();
+        Ok(())
+    }//end set_active()
+
+    /// Replaces the config for the profile currently in use.
+    pub fn update_active(&mut self, config: ConfigStore) {
+        self.profiles.insert(self.active.clone(), config);
+    }//end update_active()
+
+    /// Checks the config for every profile, as with ConfigStore::validate().  
+    /// If there's more than one profile, each problem starts with the name of its profile.
+    pub fn validate(&self) -> Result<(),Vec<String>> {
+        let mut problems = Vec::new();
+        for name in self.get_profile_names() {
+            if let Err(profile_problems) = self.profiles[&name].validate() {
+                if self.profiles.len() == 1 {problems.extend(profile_problems);}
+                else {problems.extend(profile_problems.iter().map(|problem| format!("In profile \"{}\": {}", name, problem)));}
+            }//end if this profile has problems
+        }//end checking each profile
+        if problems.is_empty() {Ok(())} else {Err(problems)}
+    }//end validate()
+
+    /// Gets the name of every profile, in alphabetical order.
+    pub fn get_profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        names.sort();
+        names
+    }//end get_profile_names()
+}//end impl ConfigProfiles
+
+/// Attempts to determine the path to the config file.  
+/// Assumes that config file has filename of config_name and extension of .json.  
+/// The places from get_config_path_candidates() are checked in order, and the first
+/// one with a config file already in it is used.  
+/// If create_if_missing is true, and there isn't a config file in any of them, then one is
+/// created with default values in the first place that can be written to.  
+/// If create_if_missing is false, then the usual place is given back even if the file doesn't exist.
+pub fn try_read_config_path(config_name: &str, create_if_missing: bool) -> Result<PathBuf, String> {
+    let candidates = get_config_path_candidates(config_name)?;
+    if let Some(existing_path) = candidates.iter().find(|path| path.exists()) {return Ok(existing_path.clone());}
+    if !create_if_missing {
+        return Ok(candidates.into_iter().next().expect("get_config_path_candidates() returns at least one path"));
+    }//end if we don't need the file to exist
+
+    // try each place until one of them lets us create the file
+    let mut problems = Vec::new();
+    for config_path in candidates {
+        match try_write_config(&config_path, &ConfigProfiles::default()) {
+            Ok(_) => return Ok(config_path),
+            Err(msg) => problems.push(format!("Couldn't create \"{}\": {}", config_path.to_string_lossy(), msg)),
+        }//end matching whether we could create the config file here
+    }//end trying each place the config file could go
+    Err(problems.join("\n"))
 }//end try_read_config_path()
 
-/// Attempts to read contents of file at path and deserialize into ConfigStore object.
-pub fn try_read_config(config_path: &PathBuf) -> Result<ConfigStore,String> {
-    match fs::read_to_string(config_path) {
-        Ok(file_contents) => {
-            match serde_json::from_str(&file_contents) {
-                Ok(config_store) => Ok(config_store),
-                Err(error) => Err(error.to_string()),
-            }//end matching whether we can deserialize config
+/// Gets the places the config file called config_name can be kept, in the order they're tried.  
+/// The usual place is next to the exe this program runs from. The current working directory is
+/// a fallback, for when the exe's folder can't be determined or written to, such as on
+/// locked-down machines. Returns an error only if neither place can be determined.
+pub fn get_config_path_candidates(config_name: &str) -> Result<Vec<PathBuf>, String> {
+    let mut candidates = Vec::new();
+    let mut problems = Vec::new();
+    match get_exe_sibling_path(config_name, "json") {
+        Ok(exe_path) => candidates.push(exe_path),
+        Err(msg) => problems.push(format!("Couldn't find the folder of the program: {}", msg)),
+    }//end matching whether we know where the exe is
+    match env::current_dir() {
+        Ok(current_dir) => {
+            let cwd_path = current_dir.join(config_name).with_extension("json");
+            if !candidates.contains(&cwd_path) {candidates.push(cwd_path);}
         },
-        Err(error) => Err(error.to_string())
-    }//end matching whether we could read string from file
+        Err(error) => problems.push(format!("Couldn't find the current folder: {}", error)),
+    }//end matching whether we know the current directory
+    if candidates.is_empty() {Err(problems.join("\n"))} else {Ok(candidates)}
+}//end get_config_path_candidates()
+
+/// Returns true if config_path isn't the usual place for the config file called
+/// config_name, which is next to the exe, meaning a fallback from get_config_path_candidates() is in use.
+pub fn is_fallback_config_path(config_path: &Path, config_name: &str) -> bool {
+    get_exe_sibling_path(config_name, "json").map_or(true, |exe_path| exe_path != config_path)
+}//end is_fallback_config_path()
+
+/// Attempts to determine the path to the log file, which goes in the same folder
+/// as config_path, with extension of .log. If config_path is None, it goes next
+/// to the exe this program runs from instead.  
+/// This function does not check whether or not the filepath exists.
+pub fn try_get_log_path(log_name: &str, config_path: Option<&Path>) -> Result<PathBuf, String> {
+    match config_path {
+        Some(config_path) => Ok(config_path.with_file_name(log_name).with_extension("log")),
+        None => get_exe_sibling_path(log_name, "log"),
+    }//end matching whether we know where the config file is
+}//end try_get_log_path()
+
+/// Gets the path to a file called name with the given extension, in the
+/// directory which contains the exe this program runs from.
+fn get_exe_sibling_path(name: &str, extension: &str) -> Result<PathBuf, String> {
+    let mut path = env::current_exe().map_err(|error| error.to_string())?;
+    path.set_file_name(name);
+    path.set_extension(extension);
+    Ok(path)
+}//end get_exe_sibling_path()
+
+/// The ways reading a config file can go wrong.
+#[derive(Clone,PartialEq,Debug)]
+pub enum ConfigReadError {
+    /// The file couldn't be read, or isn't a config file. Holds a message describing why.
+    Unreadable(String),
+    /// The file was read, but has settings that can't be used.
+    Invalid {
+        /// Everything read from the file, so it isn't lost.
+        config_profiles: ConfigProfiles,
+        /// A message for each setting that can't be used, from ConfigProfiles::validate().
+        problems: Vec<String>,
+    },
+}//end enum ConfigReadError
+
+impl std::fmt::Display for ConfigReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigReadError::Unreadable(msg) => write!(f, "{}", msg),
+            ConfigReadError::Invalid{problems, ..} => write!(f, "The config file has settings that can't be used:\n{}", problems.join("\n")),
+        }//end matching self
+    }//end fmt()
+}//end impl Display for ConfigReadError
+
+impl std::error::Error for ConfigReadError {}
+
+/// Attempts to read contents of file at path and deserialize into ConfigProfiles object.  
+/// See parse_config() for the formats understood. Every profile is checked with
+/// ConfigProfiles::validate(), and any problems are returned along with what was read.
+pub fn try_read_config(config_path: &PathBuf) -> Result<ConfigProfiles,ConfigReadError> {
+    let file_contents = fs::read_to_string(config_path).map_err(|e| ConfigReadError::Unreadable(e.to_string()))?;
+    let config_profiles = parse_config(&file_contents).map_err(ConfigReadError::Unreadable)?;
+    match config_profiles.validate() {
+        Ok(()) => Ok(config_profiles),
+        Err(problems) => Err(ConfigReadError::Invalid { config_profiles, problems }),
+    }//end matching whether the settings can be used
 }//end try_read_config()
 
-/// Attempts to write given config_store to the given path.
-pub fn try_write_config(config_path: &PathBuf, config_store: &ConfigStore) -> Result<(),String> {
+/// Deserializes the contents of a config file, reading each config with migrate().  
+/// Files holding a single ConfigStore, as written before profiles were added,
+/// are read as one profile named DEFAULT_PROFILE_NAME.  
+/// Returns an error only if the contents aren't json, or don't have a name for the active profile.
+pub fn parse_config(file_contents: &str) -> Result<ConfigProfiles,String> {
+    let mut value: serde_json::Value = serde_json::from_str(file_contents).map_err(|e| e.to_string())?;
+    let is_profiles = value.get("profiles").is_some() && value.get("active").is_some();
+    if is_profiles {
+        let active: String = serde_json::from_value(value["active"].take()).map_err(|e| e.to_string())?;
+        let profiles = match value["profiles"].take() {
+            serde_json::Value::Object(profiles) => profiles.into_iter().map(|(name, config)| (name, migrate(config))).collect(),
+            _ => HashMap::new(),
+        };//end matching whether there are profiles to read
+        let mut config_profiles = ConfigProfiles { profiles, active };
+        config_profiles.ensure_active();
+        Ok(config_profiles)
+    } else {
+        Ok(ConfigProfiles::new(migrate(value)))
+    }//end else this is a file from before profiles
+}//end parse_config()
+
+/// Attempts to write given config_profiles to the given path.  
+/// If the only profile is the default one, it's written on its own, in the
+/// same format as before profiles were added.
+pub fn try_write_config(config_path: &PathBuf, config_profiles: &ConfigProfiles) -> Result<(),String> {
+    // a hand-edited config file could have more recent inputs than we keep
+    let mut config_profiles = config_profiles.clone();
+    for config in config_profiles.profiles.values_mut() {
+        config.recent_inputs.truncate(RECENT_INPUTS_MAX);
+        config.schema_version = CONFIG_SCHEMA_VERSION;
+    }//end tidying each config before writing it
+    let serialized = if config_profiles.profiles.len() == 1 && config_profiles.active == DEFAULT_PROFILE_NAME {
+        serde_json::to_string_pretty(config_profiles.get_active())
+    } else {serde_json::to_string_pretty(&config_profiles)};
     match File::create(config_path) {
         Ok(mut file) => {
-            match serde_json::to_string_pretty(config_store) {
+            match serialized {
                 Ok(config_serial) => {
                     match file.write_all(config_serial.as_bytes()) {
                         Ok(_) => Ok(()),
@@ -197,3 +1248,27 @@ pub fn try_write_config(config_path: &PathBuf, config_store: &ConfigStore) -> Re
         Err(error) => Err(error.to_string()),
     }//end matching whether we can see the file
 }//end try_write_config()
+
+/// Writes config to the given path as json, such as for sharing it with someone else.  
+/// Only the one config is written, whatever format the config file itself uses.
+pub fn export_config_json(config: &ConfigStore, path: &PathBuf) -> Result<(),String> {
+    let config = ConfigStore { schema_version: CONFIG_SCHEMA_VERSION, ..config.clone() };
+    let config_serial = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    fs::write(path, config_serial).map_err(|e| format!("Couldn't write to \"{}\":\n{}", path.to_string_lossy(), e))
+}//end export_config_json()
+
+/// Reads a config written by export_config_json() from the given path.  
+/// The config is read with migrate(), so settings missing from the file or that can't be read
+/// use their defaults. Returns an error if the file can't be read or isn't json, or if it has
+/// settings that ConfigStore::validate() rejects.
+pub fn import_config_json(path: &PathBuf) -> Result<ConfigStore,String> {
+    let file_contents = fs::read_to_string(path).map_err(|e| format!("Couldn't read \"{}\":\n{}", path.to_string_lossy(), e))?;
+    let raw_value: serde_json::Value = serde_json::from_str(&file_contents).map_err(|e| format!("\"{}\" isn't a config file that can be imported:\n{}", path.to_string_lossy(), e))?;
+    if !raw_value.is_object() {return Err(format!("\"{}\" isn't a config file that can be imported, since it doesn't hold any settings.", path.to_string_lossy()));}
+    let config = migrate(raw_value);
+    match config.validate() {
+        Ok(()) => Ok(config),
+        Err(problems) => Err(format!("The config in \"{}\" has settings that can't be used:\n{}", path.to_string_lossy(), problems.join("\n"))),
+    }//end matching whether the imported settings can be used
+}//end import_config_json()
+
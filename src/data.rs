@@ -1,119 +1,856 @@
-use crate::config_store::ConfigStore;
+use std::ops::Range;
 
-/// Represents a single row with a single value and header.
-#[derive(Clone,PartialEq,PartialOrd,Debug,Default)]
+use serde::{Deserialize, Serialize};
+
+use crate::{config_store::{ConfigStore, InputLayout, SummaryStat}, logging};
+
+/// Represents a single row with a single value and header.  
+/// PartialEq compares values exactly. Use approx_eq() to allow for rounding.
+#[derive(Clone,PartialEq,PartialOrd,Debug,Default,Deserialize,Serialize)]
 pub struct Row {
     pub header: String,
     pub value: f64,
+    /// A quality flag given by the instrument for this measurement, such as "OK" or "LOW".
+    pub flag: Option<String>,
+    /// The value as it was read from the file, if value was changed from it by config unit_conversions.
+    pub raw_value: Option<f64>,
 }//end struct Row
 
 impl Row {
-    /// Creates a new Row with given header and value
-    pub fn new(header: String, value: f64) -> Row {Row{header,value}}
+    /// Creates a new Row with given header and value, and no flag
+    pub fn new(header: String, value: f64) -> Row {Row{header,value,flag:None,raw_value:None}}
+    /// Creates a new Row with given header, value, and flag
+    pub fn new1(header: String, value: f64, flag: Option<String>) -> Row {Row{header,value,flag,raw_value:None}}
+
+    /// Gets this row with its value converted by the entry for its header in
+    /// config unit_conversions, keeping the value read in raw_value.  
+    /// If there's no entry for its header, the row is given back unchanged.
+    pub fn convert_units(self, config: &ConfigStore) -> Row {
+        match config.unit_conversions.get(&self.header) {
+            Some(conversion) => Row {value: conversion.apply(self.value), raw_value: Some(self.value), ..self},
+            None => self,
+        }//end matching whether there's a conversion for this header
+    }//end convert_units()
+
+    /// Returns true if other has the same header and flag as this row, and
+    /// its value and raw_value are each within epsilon of this row's.  
+    /// Values that aren't finite only match if they're the same, with NaN matching NaN.
+    pub fn approx_eq(&self, other: &Row, epsilon: f64) -> bool {
+        self.header == other.header
+            && self.flag == other.flag
+            && values_approx_eq(self.value, other.value, epsilon)
+            && match (self.raw_value, other.raw_value) {
+                (Some(raw), Some(other_raw)) => values_approx_eq(raw, other_raw, epsilon),
+                (None, None) => true,
+                _ => false,
+            }//end matching whether both rows have raw values
+    }//end approx_eq()
 }//end impl for Row
 
-/// Represents all the data from a file.
-#[derive(Clone,PartialEq,PartialOrd,Debug,Default)]
+/// Represents all the data from a file.  
+/// PartialEq compares values exactly. Use approx_eq() to allow for rounding.
+#[derive(Clone,PartialEq,PartialOrd,Debug,Default,Deserialize,Serialize)]
 pub struct Data {
     pub test_name: String,
     pub row_data: Vec<Row>,
+    /// The name of the file this data was read from, if it came from a single file.
+    pub source_file: Option<String>,
+    /// The "key: value" lines found above the start header in the file, such as the
+    /// operator or hydration, in the order they were found, with whitespace trimmed.
+    pub metadata: Vec<(String, String)>,
+    /// True if test_name wasn't found in the file, so it was made from the file name instead.
+    #[serde(default)]
+    pub test_name_derived: bool,
 }//end struct Data
 
 impl Data {
-    /// Creates a new Data struct with given test_name, empty row_data.
-    pub fn new(test_name: String) -> Data {Data{test_name,row_data:Vec::new()}}
-    /// Creates a new Data struct with given test_name and row_data.
-    pub fn new1(test_name: String, row_data: Vec<Row>) -> Data {Data{test_name,row_data}}
+    /// Creates a new Data struct with given test_name, empty row_data, and no source_file.
+    pub fn new(test_name: String) -> Data {Data{test_name,row_data:Vec::new(),source_file:None,metadata:Vec::new(),test_name_derived:false}}
+    /// Creates a new Data struct with given test_name and row_data, and no source_file.
+    pub fn new1(test_name: String, row_data: Vec<Row>) -> Data {Data{test_name,row_data,source_file:None,metadata:Vec::new(),test_name_derived:false}}
+    /// Creates a new Data struct with given test_name, row_data, and source_file, and no metadata.
+    pub fn new2(test_name: String, row_data: Vec<Row>, source_file: Option<String>) -> Data {Data{test_name,row_data,source_file,metadata:Vec::new(),test_name_derived:false}}
+
+    /// Gets every row whose value is outside the range given for its header in config value_ranges.  
+    /// Values skipped by config skip_zero_rows are never out of range.
+    pub fn out_of_range(&self, config: &ConfigStore) -> Vec<&Row> {
+        self.row_data.iter()
+            .filter(|row| !config.is_skipped_zero(row.value))
+            .filter(|row| config.value_ranges.get(&row.header).is_some_and(|range| range.is_out_of_range(row.value)))
+            .collect()
+    }//end out_of_range()
+
+    /// Gets every row whose value isn't within epsilon of 0, such as to leave out
+    /// placeholder rows for measurements that weren't taken. Values that aren't
+    /// finite numbers are kept.
+    pub fn nonzero_rows(&self, epsilon: f64) -> Vec<&Row> {
+        self.row_data.iter()
+            .filter(|row| !is_zero(row.value, epsilon))
+            .collect()
+    }//end nonzero_rows()
+
+    /// Gets the value of the metadata with the given key, if there is any.
+    pub fn get_metadata_value(&self, key: &str) -> Option<&str> {
+        self.metadata.iter().find(|(metadata_key,_)| metadata_key == key).map(|(_,value)| value.as_str())
+    }//end get_metadata_value()
+
+    /// Gets the date the sample was tested, from the first metadata key with
+    /// "date" in it, ignoring upper and lower case. Returns None if there isn't one.
+    pub fn get_sample_date(&self) -> Option<&str> {
+        self.metadata.iter().find(|(key,_)| key.to_lowercase().contains("date")).map(|(_,value)| value.as_str())
+    }//end get_sample_date()
+
+    /// Returns true if other has the same test_name, source_file, and metadata as this data,
+    /// with test_name found or derived the same way,
+    /// and the same rows in the same order, with values compared by Row::approx_eq().
+    pub fn approx_eq(&self, other: &Data, epsilon: f64) -> bool {
+        self.test_name == other.test_name
+            && self.test_name_derived == other.test_name_derived
+            && self.source_file == other.source_file
+            && self.metadata == other.metadata
+            && self.row_data.len() == other.row_data.len()
+            && self.row_data.iter().zip(other.row_data.iter()).all(|(row, other_row)| row.approx_eq(other_row, epsilon))
+    }//end approx_eq()
 }//end impl Data
 
-/// Gets the test name, such as "24-PCF4001", from the lines of a file.
+/// Returns true if a and b are within epsilon of each other, or are the same
+/// non-finite value, counting NaN as the same as NaN.
+fn values_approx_eq(a: f64, b: f64, epsilon: f64) -> bool {
+    if a.is_nan() || b.is_nan() {return a.is_nan() && b.is_nan();}
+    if !a.is_finite() || !b.is_finite() {return a == b;}
+    (a - b).abs() <= epsilon
+}//end values_approx_eq()
+
+/// Returns true if value is within epsilon of 0.
+pub fn is_zero(value: f64, epsilon: f64) -> bool {
+    value.abs() <= epsilon
+}//end is_zero()
+
+/// The ways reading data from a file can go wrong.  
+/// Some of these stop the file from being read at all, while others are
+/// only reported alongside the data that could be read.
+#[derive(Clone,PartialEq,Debug)]
+pub enum DataParseError {
+    /// The file has no content, or only whitespace.
+    EmptyFile,
+    /// The start header couldn't be found in the file, so the data couldn't be found.
+    StartHeaderNotFound {header: String},
+    /// The end header couldn't be found, so data was read until the end of the file.
+    EndHeaderNotFound {header: String},
+    /// The line starting with the test name prefix couldn't be found, so a name was made from the file name.
+    NoTestName,
+    /// A data row's value couldn't be read as a number.  
+    /// line is 1-based, to match line numbers shown by text editors.
+    InvalidNumber {line: usize, raw: String},
+    /// A data row couldn't be split into a header and a value.  
+    /// line is 1-based, to match line numbers shown by text editors.
+    MissingValue {line: usize, text: String},
+    /// A data row didn't start with the next header in config read_row_headers,
+    /// so no more rows were read.  
+    /// line is 1-based, to match line numbers shown by text editors.
+    UnexpectedRow {line: usize, expected: String, text: String},
+}//end enum DataParseError
+
+impl std::fmt::Display for DataParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataParseError::EmptyFile => write!(f, "The file is empty."),
+            DataParseError::StartHeaderNotFound{header} => write!(f, "Could not find the header str \"{}\".", header),
+            DataParseError::EndHeaderNotFound{header} => write!(f, "Could not find the end header str \"{}\", so reading until the end of the file.", header),
+            DataParseError::NoTestName => write!(f, "Could not find the test name, so the file name was used instead."),
+            DataParseError::InvalidNumber{line, raw} => write!(f, "Failed to parse \"{}\" on line {} as a number.", raw, line),
+            DataParseError::MissingValue{line, text} => write!(f, "Couldn't split line {} \"{}\" into a header and a value.", line, text),
+            DataParseError::UnexpectedRow{line, expected, text} => write!(f, "Broken row pattern. We were looking for row header \"{}\", but we found line {} \"{}\".", expected, line, text),
+        }//end matching self
+    }//end fmt()
+}//end impl Display for DataParseError
+
+impl std::error::Error for DataParseError {}
+
+impl DataParseError {
+    /// Gets the 1-based line number this error is about, or None if it isn't about a single line.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            DataParseError::InvalidNumber{line, ..} => Some(*line),
+            DataParseError::MissingValue{line, ..} => Some(*line),
+            DataParseError::UnexpectedRow{line, ..} => Some(*line),
+            _ => None,
+        }//end matching self
+    }//end line()
+}//end impl DataParseError
+
+/// The number of columns a tab takes up in get_error_context().
+const CONTEXT_TAB_WIDTH: usize = 4;
+
+/// Replaces the tabs in text with spaces, out to the next multiple of CONTEXT_TAB_WIDTH,
+/// so the text lines up the same way in any monospace font.
+fn expand_tabs(text: &str) -> String {
+    let mut expanded = String::new();
+    for c in text.chars() {
+        if c == '\t' {
+            let spaces = CONTEXT_TAB_WIDTH - expanded.chars().count() % CONTEXT_TAB_WIDTH;
+            expanded.push_str(&" ".repeat(spaces));
+        } else {expanded.push(c);}
+    }//end expanding each character
+    expanded
+}//end expand_tabs()
+
+/// Gets the lines around the one an error is about, from the contents of the file
+/// it was found in, as a small block of text to show alongside the error, like:
+/// ```text
+///    9 | G   3
+/// > 10 | L   2.x
+///      |     ^^^
+///   11 | P   1
+/// ```
+/// The caret points at the value that couldn't be read, or the whole line if it
+/// isn't more specific than that. Tabs are replaced with spaces so the caret lines up
+/// in a monospace font. Returns None if the error isn't about a line in contents.
+pub fn get_error_context(contents: &str, err: &DataParseError) -> Option<String> {
+    let line_num = err.line()?;
+    // line numbers are counted the same way as read_data_from_file()
+    let contents = contents.strip_prefix('\u{FEFF}').unwrap_or(contents);
+    let lines: Vec<&str> = contents.split('\n').map(|line| line.trim_matches('\r')).collect();
+    let line = *lines.get(line_num.checked_sub(1)?)?;
+
+    let (caret_start, caret_len) = match err {
+        DataParseError::InvalidNumber{raw, ..} if !raw.is_empty() => match line.find(raw.as_str()) {
+            Some(byte_idx) => {
+                let start = expand_tabs(&line[..byte_idx]).chars().count();
+                (start, expand_tabs(&line[..byte_idx + raw.len()]).chars().count() - start)
+            },
+            None => (0, expand_tabs(line).chars().count()),
+        },
+        _ => (0, expand_tabs(line).chars().count()),
+    };//end matching which part of the line to point at
+
+    let first_num = line_num.saturating_sub(1).max(1);
+    let last_num = (line_num + 1).min(lines.len());
+    let num_width = last_num.to_string().len();
+    let mut context = Vec::new();
+    for num in first_num..=last_num {
+        let marker = if num == line_num {">"} else {" "};
+        context.push(format!("{} {:>width$} | {}", marker, num, expand_tabs(lines[num - 1]), width = num_width));
+        if num == line_num {
+            context.push(format!("  {} | {}{}", " ".repeat(num_width), " ".repeat(caret_start), "^".repeat(caret_len.max(1))));
+        }//end if we should point out the problem in this line
+    }//end adding each line of context
+    Some(context.join("\n"))
+}//end get_error_context()
+
+/// Gets the test name, such as "24-PCF4001", from the lines of a file, as the trimmed text after
+/// any of the prefixes from ConfigStore::get_test_name_prefixes().  
+/// The first line starting with one of them is used, even if a later line has another.
 pub fn get_test_name_from_lines(lines: &Vec<String>, config: &ConfigStore) -> Option<String> {
+    let prefixes = config.get_test_name_prefixes();
     for line in lines.iter() {
-        if line.starts_with(&config.read_test_name_prefix) {
-            let test_name = line.replace(&config.read_test_name_prefix,"");
-            return Some(test_name);
-        }//end if we found the test_name_prefix
+        if let Some(test_name) = prefixes.iter().find_map(|prefix| line.strip_prefix(prefix)) {
+            return Some(test_name.trim().to_string());
+        }//end if we found a test name prefix
     }//end looking for test_name in each line
     return None;
 }//end get_test_name_from_lines()
 
+/// Gets a test name to use for a file that doesn't have one, which is
+/// filename without any directories or extension.  
+/// If that leaves nothing, filename is used as is.
+pub fn get_test_name_from_filename(filename: &str) -> String {
+    match std::path::Path::new(filename).file_stem().and_then(|stem| stem.to_str()) {
+        Some(stem) if !stem.trim().is_empty() => stem.to_string(),
+        _ => filename.to_string(),
+    }//end matching whether there's a usable file stem
+}//end get_test_name_from_filename()
+
+/// Gets the metadata from the lines above the start header, such as "Operator\t:\tJane".
+/// Each line is split at its first colon into a key and value, both trimmed.  
+/// Lines without a colon or with nothing before it are ignored, as is the line with
+/// the test name, since that's kept separately. If a key is found more than once,
+/// the last value found is kept, in the place the key was first found.
+pub fn get_metadata_from_lines(lines: &[String], config: &ConfigStore) -> Vec<(String, String)> {
+    let mut metadata: Vec<(String, String)> = Vec::new();
+    for line in lines.iter() {
+        if config.get_test_name_prefixes().iter().any(|prefix| !prefix.is_empty() && line.starts_with(prefix)) {continue;}
+        let Some((key, value)) = line.split_once(':') else {continue;};
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty() {continue;}
+        match metadata.iter_mut().find(|(found_key,_)| found_key == key) {
+            Some((_, found_value)) => *found_value = value.to_string(),
+            None => metadata.push((key.to_string(), value.to_string())),
+        }//end matching whether we've seen this key before
+    }//end looking for metadata in each line
+    metadata
+}//end get_metadata_from_lines()
+
 /// Gets the index of the header before the rows we want to read, such as "Standard\t : \tAverage".
-pub fn get_header_idx_from_lines(filename: &str, lines: &Vec<String>, config: &ConfigStore) -> Result<usize,String> {
+pub fn get_header_idx_from_lines(lines: &Vec<String>, config: &ConfigStore) -> Result<usize,DataParseError> {
     match config.read_start_mode {
         crate::config_store::ReadStartMode::Index => Ok(config.read_start_idx as usize),
         crate::config_store::ReadStartMode::Header => {
+            if reads_from_file_start(config) {return Ok(0);}
             for (i,line) in lines.iter().enumerate() {
-                if line.eq(&config.read_start_header) {
+                if header_line_matches(line, &config.read_start_header, config) {
                     return Ok(i);
                 }//end if we found the start_header
             }//end looking for start header in each line
-            return Err(format!("Could not find the header str \"{}\" in file \"{}\"", config.read_start_header, filename));
+            return Err(DataParseError::StartHeaderNotFound{header: config.read_start_header.clone()});
         },
     }//end matching the read_start_mode
 }//end get_header_idx_from_lines()
 
+/// Checks whether line matches the given start or end header.  
+/// If config.normalize_header_match is set, both are compared after normalize_header_line().
+/// If config.header_match_case_insensitive is set, both are trimmed and compared ignoring case.
+/// Otherwise, they must match exactly.
+pub fn header_line_matches(line: &str, header: &str, config: &ConfigStore) -> bool {
+    let (line, header) = if config.normalize_header_match {(normalize_header_line(line), normalize_header_line(header))}
+        else {(line.to_string(), header.to_string())};
+    if config.header_match_case_insensitive {line.trim().to_lowercase() == header.trim().to_lowercase()}
+    else {line == header}
+}//end header_line_matches()
+
+/// Normalizes a header line for loose matching, collapsing runs of whitespace
+/// into single spaces and removing trailing punctuation, such as a colon.
+pub fn normalize_header_line(line: &str) -> String {
+    line.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+        .to_string()
+}//end normalize_header_line()
+
+/// Normalizes the header of a data row, so that the same measurement is always
+/// given the same header. Whitespace is trimmed from both ends, and runs of whitespace,
+/// including non-breaking spaces, are collapsed into single spaces.  
+/// Unlike normalize_header_line(), punctuation is kept, since headers like "P/L" need it.
+pub fn normalize_header(header: &str) -> String {
+    header.replace('\u{FEFF}', "")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}//end normalize_header()
+
+/// Gets header as it should be used for a row, which is with normalize_header()
+/// applied if config.normalize_headers is set, or just as it is otherwise.
+fn clean_header(header: &str, config: &ConfigStore) -> String {
+    if config.normalize_headers {normalize_header(header)} else {header.to_string()}
+}//end clean_header()
+
+/// Checks whether the file has no start header to look for, because read_start_header
+/// is empty and config.empty_start_header_reads_from_start is set.  
+/// In that case, data rows are read from the first line of the file.
+pub fn reads_from_file_start(config: &ConfigStore) -> bool {
+    config.read_start_mode == crate::config_store::ReadStartMode::Header
+        && config.empty_start_header_reads_from_start
+        && config.read_start_header.trim().is_empty()
+}//end reads_from_file_start()
+
+/// Gets the index of the line which ends the data rows, searching for config.read_end_header
+/// in the lines after header_idx.  
+/// If read_end_header is empty or can't be found, the end of the file is used instead.
+pub fn get_end_idx_from_lines(header_idx: usize, lines: &Vec<String>, config: &ConfigStore) -> Option<usize> {
+    if config.read_end_header.is_empty() {return Some(lines.len());}
+    lines.iter()
+        .enumerate()
+        .skip(header_idx + 1)
+        .find(|(_,line)| header_line_matches(line, &config.read_end_header, config))
+        .map(|(i,_)| i)
+}//end get_end_idx_from_lines()
+
+/// Gets the range of line indices which hold data rows, given the index of the start header.  
+/// The data block starts on the line after the header (or on the header itself, if
+/// config.start_header_is_data is set, or there's no start header to skip) and runs up
+/// to line_count, which is normally the end of the file or the end header,
+/// with config.skip_rows_after_start and config.skip_rows_before_end lines trimmed
+/// off of the start and end respectively. If the trimming leaves nothing, the range is empty.
+pub fn get_data_block_range(header_idx: usize, line_count: usize, config: &ConfigStore) -> std::ops::Range<usize> {
+    let first_data_idx = if config.start_header_is_data || reads_from_file_start(config) {header_idx} else {header_idx + 1};
+    let block_start = (first_data_idx + config.skip_rows_after_start).min(line_count);
+    let block_end = line_count.saturating_sub(config.skip_rows_before_end).max(block_start);
+    block_start..block_end
+}//end get_data_block_range()
+
+/// The characters windows-1252 uses for bytes 0x80 through 0x9F, which latin-1 leaves
+/// as control characters. Bytes that windows-1252 doesn't use keep their latin-1 meaning.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{8D}', '\u{017D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{9D}', '\u{017E}', '\u{0178}',
+];
+
+/// Decodes the bytes of an input file into text, working out the encoding as it goes.  
+/// A byte order mark picks UTF-8, UTF-16LE, or UTF-16BE, and is left out of the text.
+/// Without one, the bytes are read as UTF-8 if they're valid, or otherwise as Windows-1252,
+/// which every byte is valid in. Gives back the text along with a label for the encoding used.
+pub fn read_file_bytes_to_string(bytes: &[u8]) -> (String, &'static str) {
+    let decode_utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks(2).map(|pair| from_bytes([pair[0], *pair.get(1).unwrap_or(&0)])).collect();
+        String::from_utf16_lossy(&units)
+    };
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        (String::from_utf8_lossy(rest).into_owned(), "UTF-8")
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        (decode_utf16(rest, u16::from_le_bytes), "UTF-16LE")
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        (decode_utf16(rest, u16::from_be_bytes), "UTF-16BE")
+    } else {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => (text.to_string(), "UTF-8"),
+            Err(_) => {
+                let text = bytes.iter().map(|byte| match byte {
+                    0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+                    _ => *byte as char,
+                }).collect();
+                (text, "Windows-1252")
+            },
+        }//end matching whether the bytes are valid utf-8
+    }//end else there's no byte order mark
+}//end read_file_bytes_to_string()
+
 /// Reads data from a given file.  
-/// If the process fails, a string will be returned, holding a message about the failure.  
-/// If any issues occur that don't prevent completion, they will be returned as well, as strings.
-pub fn read_data_from_file(filename: &str, file_contents: &str, config: &ConfigStore) -> Result<(Data,Vec<String>),String> {
+/// If the process fails, a DataParseError describing the failure will be returned.  
+/// If any issues occur that don't prevent completion, they will be returned as well, as DataParseErrors.
+pub fn read_data_from_file(filename: &str, file_contents: &str, config: &ConfigStore) -> Result<(Data,Vec<DataParseError>),DataParseError> {
     // init error message list
     let mut errs = Vec::new();
+    // a leading byte order mark would otherwise end up in the first line
+    let file_contents = file_contents.strip_prefix('\u{FEFF}').unwrap_or(file_contents);
+    if file_contents.trim().is_empty() {return Err(DataParseError::EmptyFile);}
     // split up contents into lines
     let lines: Vec<&str> = file_contents.split(['\n']).collect();
     // clean out any carriage returns and convert to string
-    let lines: Vec<String> = lines.iter().map(|s| s.trim_matches('\r').to_string()).collect();
-    // find the test_name
-    let test_name = get_test_name_from_lines(&lines, config).unwrap_or_else(|| format!("Unknown test name from {:?}", filename));
-    // find the line with the header
-    let header_idx = match get_header_idx_from_lines(filename, &lines, config) {
-        Ok(h) => h,
-        Err(err) => return Err(err),
+    let mut lines: Vec<String> = lines.iter().map(|s| s.trim_matches('\r').to_string()).collect();
+    // a trailing newline shouldn't count as an extra line
+    if lines.len() > 1 && lines.last().is_some_and(|s| s.is_empty()) {lines.pop();}
+    // find the test_name, falling back to the file name without its extension
+    let (test_name, test_name_derived) = match get_test_name_from_lines(&lines, config).filter(|name| !name.trim().is_empty()) {
+        Some(test_name) => (test_name, false),
+        None => {
+            errs.push(DataParseError::NoTestName);
+            (get_test_name_from_filename(filename), true)
+        },
+    };
+    // find the line with the header, and any metadata above it
+    let header_idx = get_header_idx_from_lines(&lines, config)?;
+    let metadata = get_metadata_from_lines(&lines[..header_idx.min(lines.len())], config);
+    // find where the data block stops, either at the end header or end of file
+    let end_idx = match get_end_idx_from_lines(header_idx, &lines, config) {
+        Some(end_idx) => end_idx,
+        None => {
+            errs.push(DataParseError::EndHeaderNotFound{header: config.read_end_header.clone()});
+            lines.len()
+        },
     };
-    // start reading rows after the header idx
+    // start reading rows within the data block after the header idx
+    let data_range = get_data_block_range(header_idx, end_idx, config);
+    // 1-based line numbers for each line in the data block, for error messages
+    let numbered_block = lines[data_range.clone()].iter().enumerate().map(|(i,line)| (data_range.start + i + 1, line));
     let mut row_data = Vec::new();
     match config.read_row_mode {
         crate::config_store::ReadRowMode::Max => {
-            for line in &lines[(header_idx+1)..=(header_idx+(config.read_max_rows as usize))] {
-                let split_row: Vec<&str> = line.split(&config.read_row_split_char).collect();
-                if split_row.len() < 2 {errs.push(format!("Couldn't find a proper split for \"{:?}\", len < 2, in file {filename}", split_row));}
-                else {
-                    let row_header = split_row[0].to_string();
-                    let row_value = split_row[1].trim().parse::<f64>();
-                    match row_value {
-                        Ok(row_value) => row_data.push(Row::new(row_header, row_value)),
-                        Err(msg) => errs.push(format!("Failed to parse \"{}\" in line \"{}\" as f64 in file {filename}:\n{}",split_row[1],line,msg)),
-                    }//end matching whether we can parse the raw value
-                }//end else we can get split stuff find
+            for (line_num, line) in numbered_block.take(config.read_max_rows as usize) {
+                for parsed in parse_line(line_num, line, config) {
+                    match parsed {
+                        Ok(row) => row_data.push(row),
+                        Err(err) => errs.push(err),
+                    }//end matching whether we could parse the row
+                }//end looking at each row in the line
             }//end looping over each line specified
         },
         crate::config_store::ReadRowMode::Header => {
             let mut header_offset = 0;
-            for line in &lines[(header_idx+1)..] {
+            for (line_num, line) in numbered_block {
                 if config.read_row_headers.len() <= header_offset {break;}
                 let this_row_header = config.read_row_headers.get(header_offset).expect("Already checked.");
-                if line.starts_with(this_row_header) {
-                    let split_row: Vec<&str> = line.split(&config.read_row_split_char).collect();
-                    if split_row.len() < 2 {errs.push(format!("Couldn't find a proper split for \"{:?}\" in file {filename}, len < 2", split_row));}
-                    else {
-                        let row_header = split_row[0].to_string();
-                        let row_value = split_row[1].trim().parse::<f64>();
-                        match row_value {
-                            Ok(row_value) => row_data.push(Row::new(row_header, row_value)),
-                            Err(msg) => errs.push(format!("Failed to parse \"{}\" in line \"{}\" as f64 for file {filename}:\n{}",split_row[1],line,msg)),
-                        }//end matching whether we can parse the row value
-                    }//end else we can get split stuff find
-                } else {errs.push(format!("Broken row pattern in filename {filename}. We were looking for row header \"{this_row_header}\", but we found line \"{line}\".")); break;}
+                let expected_header = clean_header(this_row_header, config);
+                if clean_header(line, config).starts_with(&expected_header) || get_line_header(line, config).is_some_and(|header| clean_header(&header, config) == expected_header) {
+                    for parsed in parse_line(line_num, line, config) {
+                        match parsed {
+                            Ok(row) => row_data.push(row),
+                            Err(err) => errs.push(err),
+                        }//end matching whether we could parse the row
+                    }//end looking at each row in the line
+                } else {errs.push(DataParseError::UnexpectedRow{line: line_num, expected: this_row_header.clone(), text: line.clone()}); break;}
                 header_offset += 1;
             }//end looping over each line specified
         },
     }//end matching the row read method
 
-    // sort the row_data based off config
-    row_data = sort_row_data(row_data, config);
-
-    Ok((Data::new1(test_name, row_data),errs))
+    let mut data = Data {metadata, test_name_derived, ..Data::new2(test_name, row_data, Some(filename.to_string()))};
+    // derived metrics need every row, even ones that get left out below
+    apply_derived_metrics(&mut data, config);
+    // remove any columns we don't want, then sort the row_data based off config
+    data.row_data = filter_row_data(data.row_data, config);
+    data.row_data = sort_row_data(data.row_data, config);
+    Ok((data,errs))
 }//end read_data_from_file()
 
+/// Adds a row to data for each of config derived_metrics, in order, worked out
+/// from the rows already in data, such as P/L from P and L.  
+/// A metric is skipped, with a warning logged, if either header it uses is missing,
+/// if it would divide by 0, or if data already has a row with its name.
+pub fn apply_derived_metrics(data: &mut Data, config: &ConfigStore) {
+    for metric in config.derived_metrics.iter() {
+        let skip = |reason: String| logging::log_warn(&format!("Skipped derived metric {} for {}: {}", metric.name, data.test_name, reason));
+        let Some((left, operator, right)) = metric.parse_expression() else {
+            skip(format!("\"{}\" isn't an expression that can be used.", metric.expression));
+            continue;
+        };
+        if data.row_data.iter().any(|row| row.header == metric.name) {
+            skip("there's already a row with that name.".to_string());
+            continue;
+        }//end if this metric would repeat a header
+        let value_of = |header: &str| data.row_data.iter().find(|row| row.header == header).map(|row| row.value);
+        let (Some(left_value), Some(right_value)) = (value_of(&left), value_of(&right)) else {
+            skip(format!("it needs both {} and {}.", left, right));
+            continue;
+        };
+        match operator.apply(left_value, right_value) {
+            Some(value) => data.row_data.push(Row::new(metric.name.clone(), value)),
+            None => skip(format!("{} is 0, so it can't be divided by.", right)),
+        }//end matching whether there's a result
+    }//end adding each derived metric
+}//end apply_derived_metrics()
+
+/// Reads data from several files at once, given the filename and contents of each.  
+/// Data from each file that could be read is collected into the first list, in the same order.
+/// Every issue is collected into the second list along with the filename it came from,
+/// so one bad file doesn't stop the rest from being read. This includes issues that
+/// stopped a file from being read, as well as ones that didn't.
+pub fn read_data_from_files(paths_and_contents: &[(String, String)], config: &ConfigStore) -> (Vec<Data>, Vec<(String, DataParseError)>) {
+    let mut data_files = Vec::new();
+    let mut errs = Vec::new();
+    for (filename, file_contents) in paths_and_contents {
+        match read_data_from_file(filename, file_contents, config) {
+            Ok((data, file_errs)) => {
+                data_files.push(data);
+                errs.extend(file_errs.into_iter().map(|err| (filename.clone(), err)));
+            },
+            Err(err) => errs.push((filename.clone(), err)),
+        }//end matching whether the file could be read
+    }//end reading each file
+    (data_files, errs)
+}//end read_data_from_files()
+
+/// Parses each Row in a single line of the data block, as laid out by config input_layout.  
+/// Most layouts have one row per line, but InputLayout::TabDelimited can have several.
+/// line_num is the 1-based line number of the line, used in any error.
+pub fn parse_line(line_num: usize, line: &str, config: &ConfigStore) -> Vec<Result<Row,DataParseError>> {
+    match &config.input_layout {
+        InputLayout::HeaderLineValueLine => vec![parse_row(line_num, line, config)],
+        InputLayout::TabDelimited => parse_tab_delimited_line(line_num, line, config),
+        InputLayout::FixedWidth { header_cols, value_cols } => vec![parse_fixed_width_row(line_num, line, header_cols, value_cols, config)],
+    }//end matching the input layout
+}//end parse_line()
+
+/// Gets the header of the first row in a line of the data block, as laid out by
+/// config input_layout, with whitespace trimmed. Returns None if there isn't one.
+pub fn get_line_header(line: &str, config: &ConfigStore) -> Option<String> {
+    let header = match &config.input_layout {
+        InputLayout::HeaderLineValueLine => line.split(&config.read_row_split_char).next().unwrap_or(""),
+        InputLayout::TabDelimited => line.split('\t').next().unwrap_or(""),
+        InputLayout::FixedWidth { header_cols, .. } => char_slice(line, header_cols),
+    }.trim();
+    if header.is_empty() {None} else {Some(header.to_string())}
+}//end get_line_header()
+
+/// Gets the part of line within the given character columns, counted from 0.  
+/// Columns past the end of the line are left out, so a short line gives a short or empty slice.
+fn char_slice<'a>(line: &'a str, cols: &Range<usize>) -> &'a str {
+    let byte_idx = |col: usize| line.char_indices().nth(col).map(|(idx,_)| idx).unwrap_or(line.len());
+    let (start, end) = (byte_idx(cols.start), byte_idx(cols.end));
+    if start >= end {""} else {&line[start..end]}
+}//end char_slice()
+
+/// Parses a line of pairs of header and value separated by tabs, such as "P\t45\tL\t60",
+/// for InputLayout::TabDelimited. Empty fields at the end of the line are ignored.
+fn parse_tab_delimited_line(line_num: usize, line: &str, config: &ConfigStore) -> Vec<Result<Row,DataParseError>> {
+    let mut fields: Vec<&str> = line.split('\t').map(|field| field.trim()).collect();
+    while fields.last().is_some_and(|field| field.is_empty()) {fields.pop();}
+    fields.chunks(2).map(|pair| match pair {
+        [header, value] => parse_value(value, config)
+            .map(|value| Row::new(clean_header(header, config), value).convert_units(config))
+            .map_err(|_| DataParseError::InvalidNumber{line: line_num, raw: value.to_string()}),
+        _ => Err(DataParseError::MissingValue{line: line_num, text: line.to_string()}),
+    }).collect()
+}//end parse_tab_delimited_line()
+
+/// Parses a line with the header and value in fixed columns, for InputLayout::FixedWidth.  
+/// If config.parse_row_flags is set, anything after value_cols is taken as the flag.
+fn parse_fixed_width_row(line_num: usize, line: &str, header_cols: &Range<usize>, value_cols: &Range<usize>, config: &ConfigStore) -> Result<Row,DataParseError> {
+    let header = char_slice(line, header_cols).trim();
+    let value = char_slice(line, value_cols).trim();
+    if value.is_empty() {return Err(DataParseError::MissingValue{line: line_num, text: line.to_string()});}
+    let rest = char_slice(line, &(value_cols.end..usize::MAX)).trim();
+    let flag = if config.parse_row_flags && !rest.is_empty() {Some(rest.to_string())} else {None};
+    match parse_value(value, config) {
+        Ok(row_value) => Ok(Row::new1(clean_header(header, config), row_value, flag).convert_units(config)),
+        Err(_) => Err(DataParseError::InvalidNumber{line: line_num, raw: value.to_string()}),
+    }//end matching whether we can parse the raw value
+}//end parse_fixed_width_row()
+
+/// Splits a single data row into a Row, using config read_row_split_char, for
+/// InputLayout::HeaderLineValueLine.  
+/// line_num is the 1-based line number of the row, used in any error.
+fn parse_row(line_num: usize, line: &str, config: &ConfigStore) -> Result<Row,DataParseError> {
+    let split_row: Vec<&str> = line.split(&config.read_row_split_char).collect();
+    if split_row.len() < 2 {return Err(DataParseError::MissingValue{line: line_num, text: line.to_string()});}
+    match parse_value(split_row[1], config) {
+        Ok(row_value) => Ok(Row::new1(clean_header(split_row[0], config), row_value, get_row_flag(&split_row, config)).convert_units(config)),
+        Err(_) => Err(DataParseError::InvalidNumber{line: line_num, raw: split_row[1].to_string()}),
+    }//end matching whether we can parse the raw value
+}//end parse_row()
+
+/// Parses the text of a single value into a number, applying each of the
+/// configured transforms in order:
+/// 1. Surrounding whitespace is trimmed.
+/// 2. If config.strip_thousands_separators is set, grouping separators are removed,
+///    with ',' as the decimal mark if config.decimal_comma is set.
+///    Otherwise, if config.decimal_comma is set, the decimal comma is converted
+///    with convert_decimal_comma().
+/// 3. The remaining text is parsed as a float.
+///
+/// Every value read from a file goes through this function.
+pub fn parse_value(text: &str, config: &ConfigStore) -> Result<f64,String> {
+    let text = text.trim();
+    let decimal_sep = if config.decimal_comma {','} else {'.'};
+    let text = match (config.strip_thousands_separators, config.decimal_comma) {
+        (true, _) => strip_grouping_separators(text, decimal_sep),
+        (false, true) => convert_decimal_comma(text),
+        (false, false) => text.to_string(),
+    };
+    text.parse::<f64>().map_err(|e| e.to_string())
+}//end parse_value()
+
+/// Converts a number written with a decimal comma, such as "283,4", to use a period instead.  
+/// A single comma with no period is the decimal mark. If there are both commas and periods,
+/// such as "1.234,56" or "1,234.56", whichever comes last is the decimal mark, and the
+/// other is removed as a grouping separator. Anything else is left as it is, so values
+/// already written with a period, like "283.4", still parse the same.
+pub fn convert_decimal_comma(text: &str) -> String {
+    match (text.contains('.'), text.matches(',').count()) {
+        (false, 1) => text.replace(',', "."),
+        (true, commas) if commas > 0 => strip_grouping_separators(text, ','),
+        _ => text.to_string(),
+    }//end matching which separators are in the text
+}//end convert_decimal_comma()
+
+/// Gets the quality flag from a data line that's already been split, such as
+/// "LOW" from "P\t45\tLOW". The flag is whatever comes after the value.  
+/// Returns None if config.parse_row_flags isn't set or if there's no flag.
+pub fn get_row_flag(split_row: &[&str], config: &ConfigStore) -> Option<String> {
+    if !config.parse_row_flags {return None;}
+    split_row.get(2)
+        .map(|flag| flag.trim().to_string())
+        .filter(|flag| !flag.is_empty())
+}//end get_row_flag()
+
+/// Removes grouping (thousands) separators from a number, so that text like
+/// "1,234.56", "1.234,56", or "1 234.56" can be parsed as a float.  
+/// Spaces are always treated as grouping separators. If both '.' and ',' are found,
+/// whichever comes last is the decimal mark. If only one of them is found, it's the
+/// decimal mark if it matches decimal_sep and only appears once, otherwise it's grouping.  
+/// The returned string always uses '.' as the decimal mark.
+pub fn strip_grouping_separators(text: &str, decimal_sep: char) -> String {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let last_period = text.rfind('.');
+    let last_comma = text.rfind(',');
+    let decimal_mark = match (last_period, last_comma) {
+        (Some(p), Some(c)) => if p > c {Some('.')} else {Some(',')},
+        (Some(_), None) => if decimal_sep == '.' && text.matches('.').count() == 1 {Some('.')} else {None},
+        (None, Some(_)) => if decimal_sep == ',' && text.matches(',').count() == 1 {Some(',')} else {None},
+        (None, None) => None,
+    };
+    text.chars()
+        .filter(|c| !(*c == '.' || *c == ',') || Some(*c) == decimal_mark)
+        .map(|c| if Some(c) == decimal_mark {'.'} else {c})
+        .collect()
+}//end strip_grouping_separators()
+
+/// Finds each test name used by more than one of data, along with how many times
+/// it's used, in the order each name is first seen.
+pub fn find_duplicate_test_names(data: &[Data]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for data_file in data.iter() {
+        match counts.iter_mut().find(|(name,_)| *name == data_file.test_name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((data_file.test_name.clone(), 1)),
+        }//end matching whether we've seen this name before
+    }//end counting each test name
+    counts.retain(|(_, count)| *count > 1);
+    counts
+}//end find_duplicate_test_names()
+
+/// Adds a suffix to repeated test names in data so that each is unique, like
+/// "name", "name (2)", "name (3)". The first use of a name is left as it is,
+/// as are names that aren't repeated. A suffix is skipped if another file already has that name.
+pub fn disambiguate_test_names(data: &mut [Data]) {
+    let mut taken: Vec<String> = Vec::new();
+    for idx in 0..data.len() {
+        let name = data[idx].test_name.clone();
+        if !taken.contains(&name) {taken.push(name); continue;}
+        let mut suffix = 2;
+        let new_name = loop {
+            let candidate = format!("{} ({})", name, suffix);
+            // a later file might already be using this name, and would clash once renamed
+            if !taken.contains(&candidate) && !data.iter().any(|d| d.test_name == candidate) {break candidate;}
+            suffix += 1;
+        };//end finding a suffix that isn't used
+        taken.push(new_name.clone());
+        data[idx].test_name = new_name;
+    }//end checking each test name
+}//end disambiguate_test_names()
+
+/// Combines all the given data into a single Data with the given test_name,
+/// where each header's value is the average of that header across all the data.  
+/// Headers present in only some of the data are averaged over just the data
+/// that has them. Headers are kept in the order they're first seen.
+pub fn average_data(data: &[Data], test_name: &str) -> Data {
+    // running sum and count for each header, in order of first appearance
+    let mut totals: Vec<(String, f64, usize)> = Vec::new();
+    for data_file in data.iter() {
+        for row in data_file.row_data.iter() {
+            match totals.iter_mut().find(|(header,_,_)| header.eq(&row.header)) {
+                Some((_, sum, count)) => {*sum += row.value; *count += 1;},
+                None => totals.push((row.header.clone(), row.value, 1)),
+            }//end matching whether we've seen this header before
+        }//end adding each row to the running totals
+    }//end going through each file's data
+    let row_data = totals.into_iter()
+        .map(|(header, sum, count)| Row::new(header, sum / count as f64))
+        .collect();
+    Data::new1(test_name.to_string(), row_data)
+}//end average_data()
+
+/// Statistics for a single header across the data from several files.
+#[derive(Clone,PartialEq,PartialOrd,Debug,Default)]
+pub struct HeaderSummary {
+    pub header: String,
+    /// The number of finite values found for this header.
+    pub count: usize,
+    /// The average of the values, or None if there weren't any.
+    pub mean: Option<f64>,
+    /// The smallest of the values, or None if there weren't any.
+    pub min: Option<f64>,
+    /// The largest of the values, or None if there weren't any.
+    pub max: Option<f64>,
+    /// The sample standard deviation of the values, or None if there were fewer than two.
+    pub std_dev: Option<f64>,
+}//end struct HeaderSummary
+
+impl HeaderSummary {
+    /// Gets the value of the given statistic, or None if it can't be calculated,
+    /// such as when there are too few values, or the mean is 0 for the coefficient of variation.
+    pub fn get_stat(&self, stat: SummaryStat) -> Option<f64> {
+        match stat {
+            SummaryStat::Count => Some(self.count as f64),
+            SummaryStat::Mean => self.mean,
+            SummaryStat::Min => self.min,
+            SummaryStat::Max => self.max,
+            SummaryStat::StdDev => self.std_dev,
+            SummaryStat::StdErr => self.std_dev.map(|std_dev| std_dev / (self.count as f64).sqrt()),
+            SummaryStat::Cv => match (self.std_dev, self.mean) {
+                (Some(std_dev), Some(mean)) if mean != 0. => Some(std_dev / mean),
+                _ => None,
+            },
+        }//end matching the stat to calculate
+    }//end get_stat()
+}//end impl HeaderSummary
+
+/// Calculates summary statistics for each header across all the given data.  
+/// Values that aren't finite are left out. Headers are kept in the order they're first seen.
+pub fn summarize_data(data: &[Data]) -> Vec<HeaderSummary> {
+    // finite values for each header, in order of first appearance
+    let mut values: Vec<(String, Vec<f64>)> = Vec::new();
+    for data_file in data.iter() {
+        for row in data_file.row_data.iter() {
+            let idx = match values.iter().position(|(header,_)| header.eq(&row.header)) {
+                Some(idx) => idx,
+                None => {values.push((row.header.clone(), Vec::new())); values.len() - 1},
+            };//end matching whether we've seen this header before
+            if row.value.is_finite() {values[idx].1.push(row.value);}
+        }//end adding each row's value
+    }//end going through each file's data
+    values.into_iter()
+        .map(|(header, header_values)| {
+            let count = header_values.len();
+            let mean = if count > 0 {Some(header_values.iter().sum::<f64>() / count as f64)} else {None};
+            let std_dev = match mean {
+                Some(mean) if count >= 2 => {
+                    let sum_squares: f64 = header_values.iter().map(|value| (value - mean).powi(2)).sum();
+                    Some((sum_squares / (count - 1) as f64).sqrt())
+                },
+                _ => None,
+            };//end matching whether there are enough values for a standard deviation
+            let min = header_values.iter().copied().reduce(f64::min);
+            let max = header_values.iter().copied().reduce(f64::max);
+            HeaderSummary {header, count, mean, min, max, std_dev}
+        })
+        .collect()
+}//end summarize_data()
+
+/// Removes any rows whose header isn't in config include_headers.  
+/// If include_headers is empty, all rows are kept.
+pub fn filter_row_data(row_data: Vec<Row>, config: &ConfigStore) -> Vec<Row> {
+    if config.include_headers.is_empty() {return row_data;}
+    row_data.into_iter()
+        .filter(|row| config.include_headers.contains(&row.header))
+        .collect()
+}//end filter_row_data()
+
+/// Returns true if test_name matches pattern, ignoring case.  
+/// If pattern has a * (any number of characters) or ? (any one character), it's
+/// matched against the whole test name. Otherwise, it only needs to be found somewhere
+/// in the test name, so "cal" matches "Calibration-01".
+pub fn test_name_matches(test_name: &str, pattern: &str) -> bool {
+    let test_name = test_name.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    if !pattern.contains(['*', '?']) {return test_name.contains(&pattern);}
+    let name_chars: Vec<char> = test_name.chars().collect();
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+    let (mut name_idx, mut pattern_idx) = (0, 0);
+    // where to go back to if what follows the last * doesn't match
+    let mut last_star: Option<(usize,usize)> = None;
+    while name_idx < name_chars.len() {
+        match pattern_chars.get(pattern_idx) {
+            Some('*') => {
+                last_star = Some((pattern_idx, name_idx));
+                pattern_idx += 1;
+            },
+            Some(c) if *c == '?' || *c == name_chars[name_idx] => {
+                pattern_idx += 1;
+                name_idx += 1;
+            },
+            _ => match last_star {
+                // let the * take one more character, then try again
+                Some((star_pattern_idx, star_name_idx)) => {
+                    last_star = Some((star_pattern_idx, star_name_idx + 1));
+                    pattern_idx = star_pattern_idx + 1;
+                    name_idx = star_name_idx + 1;
+                },
+                None => return false,
+            },
+        }//end matching the next character of the pattern
+    }//end going through each character of the test name
+    pattern_chars[pattern_idx..].iter().all(|c| *c == '*')
+}//end test_name_matches()
+
+/// Returns true if a file with test_name should be exported, given the include and
+/// exclude patterns, as matched by test_name_matches().  
+/// Excludes take precedence over includes, and an empty include list lets every name through.
+pub fn test_name_passes_filter(test_name: &str, include: &[String], exclude: &[String]) -> bool {
+    if exclude.iter().any(|pattern| test_name_matches(test_name, pattern)) {return false;}
+    include.is_empty() || include.iter().any(|pattern| test_name_matches(test_name, pattern))
+}//end test_name_passes_filter()
+
+/// Removes any Data whose test name is left out by config test_name_include
+/// or test_name_exclude. If both are empty, all the data is kept.
+pub fn filter_by_test_name(data: Vec<Data>, config: &ConfigStore) -> Vec<Data> {
+    data.into_iter()
+        .filter(|data_file| test_name_passes_filter(&data_file.test_name, &config.test_name_include, &config.test_name_exclude))
+        .collect()
+}//end filter_by_test_name()
+
 /// Sorts the Vec of Rows based off of config row order pref.  
 /// No rows will be removed or added, simply rearranged, with specified rows
 /// in front of unspecified rows.  
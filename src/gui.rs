@@ -1,13 +1,20 @@
-use std::{cell::RefCell, path::PathBuf, rc::Rc};
+use std::{cell::RefCell, path::{Path, PathBuf}, rc::Rc};
 
-use alveograph_exporter::config_store::{ConfigStore, ReadRowMode, ReadStartMode};
-use fltk::{app::{self, App, Receiver, Sender}, button::Button, dialog::{self, BeepType, FileDialogOptions, FileDialogType, NativeFileChooser}, enums::{Align, Color, Event, FrameType}, frame::Frame, group::{Flex, FlexType, Group, Tile}, image::PngImage, input::IntInput, menu::Choice, misc::HelpView, prelude::{ButtonExt, DisplayExt, GroupExt, InputExt, MenuExt, WidgetBase, WidgetExt, WindowExt}, text::{TextBuffer, TextDisplay, TextEditor, WrapMode}, window::{self, Window}};
+use alveograph_exporter::{data::{self, Data, DataParseError}, config_store::{self, ConfigStore, FlagColor, FlagStyle, NonFiniteFormat, OutputFormat, OutputLayout, ReadRowMode, ReadStartMode}, logging, process};
+use fltk::{app::{self, App, Receiver, Sender}, button::{Button, CheckButton}, browser::CheckBrowser, dialog::{self, BeepType, FileChooser, FileChooserType, FileDialogOptions, FileDialogType, NativeFileChooser}, enums::{Align, Color, Event, Font, FrameType, Key, Shortcut}, menu::{MenuButton, MenuFlag}, frame::Frame, group::{Flex, FlexType, Group, Tabs, Tile}, image::PngImage, input::{Input, IntInput}, menu::Choice, misc::{HelpView, Progress}, prelude::{ButtonExt, DisplayExt, GroupExt, InputExt, MenuExt, WidgetBase, WidgetExt, WindowExt}, text::{TextBuffer, TextDisplay, TextEditor, WrapMode}, window::{self, Window}};
 
 /// Width in pixels of the main window
 const WINDOW_WIDTH: i32 = 750;
 /// Height in pixels of the main window
 const WINDOW_HEIGHT: i32 = 460;
 
+/// Height in pixels of the status bar at the bottom of the main window
+const STATUS_BAR_HEIGHT: i32 = 20;
+/// The color to use for the background of the status bar
+const STATUS_BAR_COLOR: Color = Color::from_rgb(240,240,240);
+/// The text size to use for the status bar
+const STATUS_BAR_TEXT_SIZE: i32 = 12;
+
 /// FrameType to use for all major groups of widgets
 const GROUP_FRAME: FrameType = FrameType::GtkThinUpBox;
 /// Background color (set_color()) for the major group of headers information
@@ -55,11 +62,21 @@ const IO_BOX_HEIGHT: i32 = 30;
 const IO_BOX_PADDING: i32 = 10;
 /// The FrameType to use for each TextBox in the fileIO section.
 const IO_BOX_FRAME: FrameType = FrameType::GtkDownFrame;
+/// The width in pixels of the menu of recent input files, next to the input box.
+const IO_RECENT_BTN_WIDTH: i32 = 60;
+/// The width in pixels of the sheet name box, next to the output box.
+const IO_SHEET_BOX_WIDTH: i32 = 140;
+/// The width in pixels of the label to the left of the sheet name box.
+const IO_SHEET_LABEL_WIDTH: i32 = 45;
 /// The amount of padding in pixels to use around the process button in the fileIO section.
 const IO_PRC_BTN_PADDING: i32 = 10;
 /// The width in pixels of the process button in the fileIO section. 
 /// The height is calculated based on the space available and the padding.
-const IO_PRC_BTN_WIDTH: i32 = 250;
+const IO_PRC_BTN_WIDTH: i32 = 150;
+/// The height in pixels of the progress bar shown below the process button while processing.
+const IO_PROGRESS_HEIGHT: i32 = 8;
+/// The Color used to fill the progress bar as processing goes along.
+const IO_PROGRESS_COLOR: Color = Color::from_rgb(60,179,113);
 /// The Color to use for the textbox for input files in the fileIO section. 
 /// A gray color is recommended in order to indicate that it cannot be edited by the user.
 const IO_INPUT_BOX_COLOR: Color = Color::from_rgb(240,240,240);
@@ -123,13 +140,21 @@ const CONF_CHOICE_SELECTION_FRAME: FrameType = FrameType::GtkThinUpBox;
 const CONF_CHOICE_LABEL_SIZE: i32 = 14;
 /// The color of the label for each choice in the config section.
 const CONF_CHOICE_LABEL_COLOR: Color = Color::Black;
+/// The width in pixels of the menu for choosing a config profile.
+const CONF_PROFILE_CHOICE_WIDTH: i32 = 110;
 
 /// The frame for input widgets in the config section.
 const CONF_INPUT_FRAME: FrameType = FrameType::GleamRoundUpBox;
 /// The size in pixels of the scrollbar for input widgets in the config section.
 const CONF_INPUT_SCROLLBAR_SIZE: i32 = 5;
 /// THe height in pixels of the multiline input flex in the config section.
-const CONF_MULTI_INPUT_HEIGHT: i32 = 185;
+const CONF_MULTI_INPUT_HEIGHT: i32 = 150;
+/// The height in pixels of the tab labels in the config section.
+const CONF_TAB_HEIGHT: i32 = 25;
+/// The amount of vertical padding in pixels around the tabs in the config section.
+const CONF_TABS_PADDING: i32 = 5;
+/// The color of the selected tab in the config section.
+const CONF_TAB_SELECTION_COLOR: Color = Color::from_rgb(235,247,235);
 /// The height in pixels of the button flex in the config section.
 const CONF_BUTTON_HEIGHT: i32 = 30;
 /// The text size in pixels to use for the line number in multiline inputs in the config group.
@@ -142,6 +167,56 @@ const CONF_MULIT_INPUT_SCROLLBAR_ALIGN: Align = Align::Right;
 const CONF_BTN_FRAME: FrameType = FrameType::GleamRoundUpBox;
 /// The down_frame to use for buttons in the config section.
 const CONF_BTN_DOWN_FRAME: FrameType = FrameType::GleamRoundDownBox;
+/// The size of the label for each button at the bottom of the config section.
+const CONF_BTN_LABEL_SIZE: i32 = 12;
+/// The width in pixels of the buttons with short labels at the bottom of the config section.
+const CONF_SMALL_BTN_WIDTH: i32 = 46;
+
+/// The maximum number of previous input lists kept for undoing changes.
+const INPUT_HISTORY_MAX: usize = 50;
+
+/// Keeps track of previous states of the list of input files,
+/// so that changes to that list can be undone and redone.  
+/// This is only kept for the current session.
+#[derive(Clone,PartialEq,Debug,Default)]
+struct InputHistory {
+    /// States of the input list before each change, most recent last.
+    undo_stack: Vec<Vec<PathBuf>>,
+    /// States of the input list before each undo, most recent last.
+    redo_stack: Vec<Vec<PathBuf>>,
+}//end struct InputHistory
+
+impl InputHistory {
+    /// Records the state of the input list from before a change.  
+    /// Making a new change means that undone changes can't be redone.
+    fn record(&mut self, previous: Vec<PathBuf>) {
+        if self.undo_stack.len() >= INPUT_HISTORY_MAX {self.undo_stack.remove(0);}
+        self.undo_stack.push(previous);
+        self.redo_stack.clear();
+    }//end record()
+
+    /// Gives the input list from before the last change, if there is one,
+    /// remembering current so that the undo can be redone.
+    fn undo(&mut self, current: Vec<PathBuf>) -> Option<Vec<PathBuf>> {
+        let previous = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        Some(previous)
+    }//end undo()
+
+    /// Gives the input list from before the last undo, if there is one,
+    /// remembering current so that the redo can be undone.
+    fn redo(&mut self, current: Vec<PathBuf>) -> Option<Vec<PathBuf>> {
+        let next = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        Some(next)
+    }//end redo()
+
+    /// Forgets all changes, so nothing can be undone or redone.
+    fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }//end clear()
+}//end impl InputHistory
 
 /// This enum is specifically intended for message passing from
 /// the GUI to the main function. This is done with Sender and 
@@ -153,9 +228,56 @@ pub enum InterfaceMessage {
     /// Indicates that the user wants to close the program
     AppClosing,
     /// Indicates that the user wants to reset the config to the default value
-    ConfigReset
+    ConfigReset,
+    /// Indicates that the user wants to see the config file in their file manager
+    OpenConfigLocation,
+    /// Indicates that it's time to automatically save the config
+    AutoSaveConfig,
+    /// Indicates that the user wants to copy the results for the selected input files to the clipboard
+    CopyToClipboard,
+    /// Indicates that the user wants to process the same input and output files as the last run again
+    ReprocessLast,
+    /// Indicates that the user wants to put the input files from the last run back, and process them again
+    RepeatLastExport,
+    /// Indicates that files were dropped onto the window, but none of them were .txt files
+    NoValidDrop,
+    /// Indicates that the user wants to see the data parsed from the input files, without writing output
+    Preview,
+    /// Indicates that the user wants to stop processing before all the input files are read
+    CancelProcess,
+    /// Indicates that the user wants to check that the input files can all be processed, without writing output
+    Validate,
+    /// Indicates that the user chose a different config profile
+    ProfileChanged,
+    /// Indicates that the user wants to save the current config settings to a json file
+    ExportConfig,
+    /// Indicates that the user wants to load config settings from a json file
+    ImportConfig,
+    /// Indicates that the user wants to use every txt file in a folder as input
+    SelectInputFolder,
 }//end enum InterfaceMessage
 
+/// The answer to a dialog from GUI::start_dialog_message_choice(), which
+/// is filled in once the user clicks one of its buttons.  
+/// Clones share the same answer, so one can be kept by each button's callback.
+#[derive(Clone,Debug,Default)]
+pub struct PendingChoice {
+    choice: Rc<RefCell<Option<usize>>>,
+}//end struct PendingChoice
+
+impl PendingChoice {
+    /// Gets the index of the option the user chose, or None if they haven't chosen yet.
+    pub fn get(&self) -> Option<usize> {
+        // if a button callback is writing the choice right now, it isn't ready yet
+        self.choice.try_borrow().ok().and_then(|choice| *choice)
+    }//end get()
+
+    /// Records that the user chose the option at idx.
+    fn set(&self, idx: usize) {
+        if let Ok(mut choice) = self.choice.try_borrow_mut() {*choice = Some(idx);}
+    }//end set()
+}//end impl PendingChoice
+
 /// This struct holds together all the objects and functions for manipulating and using the GUI.
 #[allow(dead_code)]
 pub struct GUI {
@@ -171,10 +293,22 @@ pub struct GUI {
     ux_input_box: Rc<RefCell<TextDisplay>>,
     /// A reference to a vec containing the paths of any input files chosen by the user.
     last_input_paths: Rc<RefCell<Vec<PathBuf>>>,
+    /// A reference to the history of changes to last_input_paths, for undo and redo.
+    input_history: Rc<RefCell<InputHistory>>,
+    /// The menu of recently processed sets of input files.
+    ux_recent_inputs_menu: MenuButton,
+    /// The sets of input files shown in the recent inputs menu, for config recent_inputs.
+    recent_inputs: Vec<Vec<String>>,
     /// A reference to the TextBox that shows the output file chosen by the user.
     ux_output_box: Rc<RefCell<TextEditor>>,
+    /// The box for the name of the sheet to write data to, which overrides config sheet_name_template.
+    ux_sheet_name_box: Input,
     /// A reference to the path of a potential output path chosen by the user.
     last_output_path: Rc<RefCell<Option<PathBuf>>>,
+    /// A reference to the folder the input dialog was last used in, for config last_input_dir.
+    last_input_dir: Rc<RefCell<Option<String>>>,
+    /// A reference to the folder the output dialog was last used in, for config last_output_dir.
+    last_output_dir: Rc<RefCell<Option<String>>>,
     /// The group holding all the configuration controls.
     /// This is stored here in order to disable during dialog.
     ux_config_group: Group,
@@ -207,6 +341,68 @@ pub struct GUI {
     ux_cf_split_char_box: TextEditor,
     /// THe text editor that displays setting for read_test_name_prefix
     ux_cf_test_name_prefix_box: TextEditor,
+    /// The choice which displays options for the NonFiniteFormat.
+    ux_cf_non_finite_format_choice: Choice,
+    /// The choice which displays options for the FlagColor.
+    ux_cf_flag_color_choice: Choice,
+    /// The choice which displays options for the FlagStyle.
+    ux_cf_flag_style_choice: Choice,
+    /// The choice which displays options for the OutputFormat.
+    ux_cf_output_format_choice: Choice,
+    /// The choice which displays options for the OutputLayout.
+    ux_cf_output_layout_choice: Choice,
+    /// The text editor that displays setting for sheet_name_template
+    ux_cf_sheet_name_template_box: TextEditor,
+    /// The check button that displays setting for average_all_inputs
+    ux_cf_average_all_inputs_check: CheckButton,
+    /// The text editor that displays setting for average_row_name
+    ux_cf_average_row_name_box: TextEditor,
+    /// The text editor that displays setting for default_output_dir
+    ux_cf_default_output_dir_box: TextEditor,
+    /// The text editor that displays setting for output_name_template
+    ux_cf_output_name_template_box: TextEditor,
+    /// The check browser that displays setting for include_headers
+    ux_cf_include_headers_browser: CheckBrowser,
+    /// The editor for the order and names of output columns, one "header = name" per line.
+    ux_cf_column_display_box: TextEditor,
+    /// The text editor that displays setting for read_end_header
+    ux_cf_read_end_header_box: TextEditor,
+    /// The check button that displays setting for header_match_case_insensitive
+    ux_cf_header_match_case_insensitive_check: CheckButton,
+    /// The check button that displays setting for normalize_header_match
+    ux_cf_normalize_header_match_check: CheckButton,
+    /// The check button that displays setting for normalize_headers
+    ux_cf_normalize_headers_check: CheckButton,
+    /// The check button that displays setting for parse_row_flags
+    ux_cf_parse_row_flags_check: CheckButton,
+    /// The check button that displays setting for decimal_comma
+    ux_cf_decimal_comma_check: CheckButton,
+    /// The check button that displays setting for excel_freeze_and_filter
+    ux_cf_excel_freeze_and_filter_check: CheckButton,
+    /// The button which shows the location of the config file.  
+    /// This is stored here in order to disable it if there's no config file.
+    ux_cf_config_location_btn: Button,
+    /// The button for reprocessing the last run, which is only active after a run.
+    ux_reprocess_btn: Button,
+    /// The button for repeating the last export, which is only active after a run.
+    ux_repeat_btn: Button,
+    /// The bar showing how far along processing is, hidden while idle.
+    ux_progress_bar: Progress,
+    /// A reference to whether the user clicked Cancel since start_cancelable_wait().
+    cancel_requested: Rc<RefCell<bool>>,
+    /// The menu for choosing which config profile is shown.
+    ux_profile_choice: Choice,
+    /// The name of each profile in ux_profile_choice, in the same order.
+    profile_names: Vec<String>,
+    /// The config most recently passed to set_config_store().  
+    /// Settings without a widget of their own are carried over from this.
+    last_config_store: ConfigStore,
+    /// The bar at the bottom of the window showing the profile, input count, and last action.
+    ux_status_bar: Frame,
+    /// The last action message shown in the status bar, from set_status().
+    status_message: String,
+    /// The display on the Log tab, holding the warnings from the last run.
+    ux_log_display: TextDisplay,
 }//end struct GUI
 
 impl GUI {
@@ -217,9 +413,11 @@ impl GUI {
     }//end get_receiver(self)
 
     /// Gets a config store that represents the configuratino chosen
-    /// by the user.
+    /// by the user.  
+    /// Returns an error instead of panicking if a widget has an unexpected value
+    /// or is currently in use elsewhere.
     pub fn get_config_store(&self) -> Result<ConfigStore,String> {
-        let mut config = ConfigStore::default();
+        let mut config = self.last_config_store.clone();
         
         match self.ux_cf_read_start_mode_choice.value() {
             0 => config.read_start_mode = ReadStartMode::Header,
@@ -231,10 +429,44 @@ impl GUI {
             1 => config.read_row_mode = ReadRowMode::Max,
             _ => return Err(format!("Invalid read_row_mode menu choice {} !!!", self.ux_cf_read_row_mode_choice.value()))
         }// end matching from value to variant for read_row_mode
+        match self.ux_cf_non_finite_format_choice.value() {
+            0 => config.non_finite_format = NonFiniteFormat::Blank,
+            1 => config.non_finite_format = NonFiniteFormat::Text,
+            2 => config.non_finite_format = NonFiniteFormat::Flagged,
+            _ => return Err(format!("Invalid non_finite_format menu choice {} !!!", self.ux_cf_non_finite_format_choice.value()))
+        }//end matching from value to variant for non_finite_format
+        match self.ux_cf_flag_color_choice.value() {
+            0 => config.flag_color = FlagColor::Red,
+            1 => config.flag_color = FlagColor::Orange,
+            2 => config.flag_color = FlagColor::Yellow,
+            3 => config.flag_color = FlagColor::Blue,
+            4 => config.flag_color = FlagColor::Purple,
+            5 => config.flag_color = FlagColor::Gray,
+            _ => return Err(format!("Invalid flag_color menu choice {} !!!", self.ux_cf_flag_color_choice.value()))
+        }//end matching from value to variant for flag_color
+        match self.ux_cf_flag_style_choice.value() {
+            0 => config.flag_style = FlagStyle::Fill,
+            1 => config.flag_style = FlagStyle::Font,
+            2 => config.flag_style = FlagStyle::Bold,
+            3 => config.flag_style = FlagStyle::Italic,
+            _ => return Err(format!("Invalid flag_style menu choice {} !!!", self.ux_cf_flag_style_choice.value()))
+        }//end matching from value to variant for flag_style
+        match self.ux_cf_output_format_choice.value() {
+            0 => config.output_format = OutputFormat::Xlsx,
+            1 => config.output_format = OutputFormat::Csv,
+            2 => config.output_format = OutputFormat::Json,
+            _ => return Err(format!("Invalid output_format menu choice {} !!!", self.ux_cf_output_format_choice.value()))
+        }//end matching from value to variant for output_format
+        match self.ux_cf_output_layout_choice.value() {
+            0 => config.output_layout = OutputLayout::Wide,
+            1 => config.output_layout = OutputLayout::Pivot,
+            2 => config.output_layout = OutputLayout::Merged,
+            _ => return Err(format!("Invalid output_layout menu choice {} !!!", self.ux_cf_output_layout_choice.value()))
+        }//end matching from value to variant for output_layout
 
         {
             let read_start_idx_input_ref = (&self.ux_cf_read_start_idx_input).clone();
-            let read_start_idx_input = read_start_idx_input_ref.as_ref().borrow();
+            let read_start_idx_input = read_start_idx_input_ref.try_borrow().map_err(|e| format!("Couldn't read the read_start_idx box because it's in use: {}", e))?;
             match read_start_idx_input.value().parse::<u16>() {
                 Err(msg) => return Err(format!("Couldn't parse read_start_idx due to {:?}", msg)),
                 Ok(read_start_idx) => config.read_start_idx = read_start_idx,
@@ -242,7 +474,7 @@ impl GUI {
         }
         {
             let read_rows_max_input_ref = (&self.ux_cf_read_rows_max_input).clone();
-            let read_rows_max_input = read_rows_max_input_ref.as_ref().borrow();
+            let read_rows_max_input = read_rows_max_input_ref.try_borrow().map_err(|e| format!("Couldn't read the read_rows_max box because it's in use: {}", e))?;
             match read_rows_max_input.value().parse::<u16>() {
                 Err(msg) => return Err(format!("Couldn't parse read_rows_max due to {:?}", msg)),
                 Ok(read_rows_max) => config.read_max_rows = read_rows_max,
@@ -251,7 +483,7 @@ impl GUI {
 
         {
             let read_start_header_box_ref = (&self.ux_cf_read_start_header_box).clone();
-            let read_start_header_box = read_start_header_box_ref.as_ref().borrow();
+            let read_start_header_box = read_start_header_box_ref.try_borrow().map_err(|e| format!("Couldn't read the read_start_header box because it's in use: {}", e))?;
             match read_start_header_box.buffer() {
                 None => {},
                 Some(buf) => config.read_start_header = buf.text(),
@@ -259,7 +491,7 @@ impl GUI {
         }
         {
             let read_row_headers_box_ref = (&self.ux_cf_read_row_headers_box).clone();
-            let read_row_headers_box = read_row_headers_box_ref.as_ref().borrow();
+            let read_row_headers_box = read_row_headers_box_ref.try_borrow().map_err(|e| format!("Couldn't read the read_row_headers box because it's in use: {}", e))?;
             match read_row_headers_box.buffer() {
                 None => {},
                 Some(buf) => config.read_row_headers = buf.text().split("\n").map(|s| s.to_string()).collect(),
@@ -271,6 +503,17 @@ impl GUI {
             Some(buf) => config.row_order_preference = buf.text().split("\n").map(|s| s.to_string()).collect(),
         }//end matching whether or not we can access buffer for row_order_preference
 
+        match self.ux_cf_column_display_box.buffer() {
+            None => {},
+            Some(buf) => config.column_display = buf.text().lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| match line.split_once('=') {
+                    Some((header, name)) => (header.trim().to_string(), name.trim().to_string()),
+                    None => (line.trim().to_string(), "".to_string()),
+                })
+                .collect(),
+        }//end matching whether or not we can access buffer for column_display
+
         match self.ux_cf_split_char_box.buffer() {
             None => {},
             Some(buf) => config.read_row_split_char = buf.text(),
@@ -281,11 +524,87 @@ impl GUI {
             Some(buf) => config.read_test_name_prefix = buf.text(),
         }//end matching whether or not we can access buffer for read_test_name_prefix
 
+        match self.ux_cf_sheet_name_template_box.buffer() {
+            None => {},
+            Some(buf) => config.sheet_name_template = buf.text(),
+        }//end matching whether or not we can access buffer for sheet_name_template
+
+        config.average_all_inputs = self.ux_cf_average_all_inputs_check.is_checked();
+        match self.ux_cf_read_end_header_box.buffer() {
+            None => {},
+            Some(buf) => config.read_end_header = buf.text(),
+        }//end matching whether or not we can access buffer for read_end_header
+        config.header_match_case_insensitive = self.ux_cf_header_match_case_insensitive_check.is_checked();
+        config.normalize_header_match = self.ux_cf_normalize_header_match_check.is_checked();
+        config.normalize_headers = self.ux_cf_normalize_headers_check.is_checked();
+        config.parse_row_flags = self.ux_cf_parse_row_flags_check.is_checked();
+        config.decimal_comma = self.ux_cf_decimal_comma_check.is_checked();
+        config.excel_freeze_and_filter = self.ux_cf_excel_freeze_and_filter_check.is_checked();
+        match self.ux_cf_average_row_name_box.buffer() {
+            None => {},
+            Some(buf) => config.average_row_name = buf.text(),
+        }//end matching whether or not we can access buffer for average_row_name
+        match self.ux_cf_default_output_dir_box.buffer() {
+            None => {},
+            Some(buf) => config.default_output_dir = buf.text(),
+        }//end matching whether or not we can access buffer for default_output_dir
+        match self.ux_cf_output_name_template_box.buffer() {
+            None => {},
+            Some(buf) => config.output_name_template = buf.text(),
+        }//end matching whether or not we can access buffer for output_name_template
+
+        config.include_headers = Vec::new();
+        let browser = &self.ux_cf_include_headers_browser;
+        let all_checked = (1..=browser.nitems() as i32).all(|i| browser.is_checked(i));
+        if !all_checked {
+            for i in 1..=browser.nitems() as i32 {
+                if let (true, Some(header)) = (browser.is_checked(i), browser.text(i)) {
+                    config.include_headers.push(header);
+                }//end if this header is checked
+            }//end adding each checked header
+        }//end if only some headers are checked
+
+        config.last_input_dir = self.last_input_dir.try_borrow().map_err(|e| format!("Couldn't read the last input folder because it's in use: {}", e))?.clone();
+        config.last_output_dir = self.last_output_dir.try_borrow().map_err(|e| format!("Couldn't read the last output folder because it's in use: {}", e))?.clone();
+        config.recent_inputs = self.recent_inputs.clone();
+        config.window_width = Some(self.ux_main_window.w());
+        config.window_height = Some(self.ux_main_window.h());
+        config.window_x = Some(self.ux_main_window.x());
+        config.window_y = Some(self.ux_main_window.y());
+
         Ok(config)
     }//end get_config_store()
 
-    /// Updates the gui to show the given configuration settings
+    /// Sizes and places the window as it was in the given config, using the
+    /// default size for anything that wasn't saved. The window is kept on a
+    /// screen that's connected now, even if it was last on one that isn't.
+    pub fn restore_window_geometry(&mut self, config: &ConfigStore) {
+        let width = config.window_width.filter(|w| *w > 0).unwrap_or(WINDOW_WIDTH);
+        let height = config.window_height.filter(|h| *h > 0).unwrap_or(WINDOW_HEIGHT);
+        let x = config.window_x.unwrap_or(self.ux_main_window.x());
+        let y = config.window_y.unwrap_or(self.ux_main_window.y());
+        let screens: Vec<(i32,i32,i32,i32)> = (0..app::screen_count()).map(app::screen_xywh).collect();
+        let (x, y, width, height) = GUI::clamp_to_screens((x, y, width, height), &screens);
+        self.ux_main_window.resize(x, y, width, height);
+    }//end restore_window_geometry()
+
+    /// Moves and shrinks the rect, given as x, y, width, and height, so that it fits on
+    /// the screen its top left corner is on, or the first screen if it isn't on any.
+    /// If there aren't any screens, the rect is given back unchanged.
+    fn clamp_to_screens(rect: (i32,i32,i32,i32), screens: &[(i32,i32,i32,i32)]) -> (i32,i32,i32,i32) {
+        let (x, y, width, height) = rect;
+        let contains = |(sx, sy, sw, sh): &(i32,i32,i32,i32)| x >= *sx && x < sx + sw && y >= *sy && y < sy + sh;
+        let Some(&(sx, sy, sw, sh)) = screens.iter().find(|screen| contains(screen)).or(screens.first()) else {return rect;};
+        let width = width.min(sw);
+        let height = height.min(sh);
+        (x.clamp(sx, sx + sw - width), y.clamp(sy, sy + sh - height), width, height)
+    }//end clamp_to_screens()
+
+    /// Updates the gui to show the given configuration settings.  
+    /// Returns an error if a widget is currently in use elsewhere, in which case
+    /// some settings might not be shown yet.
     pub fn set_config_store(&mut self, config: &ConfigStore) -> Result<(),String> {
+        self.last_config_store = config.clone();
         match config.read_start_mode {
             ReadStartMode::Header => {let _ = self.ux_cf_read_start_mode_choice.set_value(0);},
             ReadStartMode::Index => {let _ = self.ux_cf_read_start_mode_choice.set_value(1);},
@@ -294,6 +613,35 @@ impl GUI {
             ReadRowMode::Header => {let _ = self.ux_cf_read_row_mode_choice.set_value(0);},
             ReadRowMode::Max => {let _ = self.ux_cf_read_row_mode_choice.set_value(1);},
         }
+        match config.non_finite_format {
+            NonFiniteFormat::Blank => {let _ = self.ux_cf_non_finite_format_choice.set_value(0);},
+            NonFiniteFormat::Text => {let _ = self.ux_cf_non_finite_format_choice.set_value(1);},
+            NonFiniteFormat::Flagged => {let _ = self.ux_cf_non_finite_format_choice.set_value(2);},
+        }
+        match config.flag_color {
+            FlagColor::Red => {let _ = self.ux_cf_flag_color_choice.set_value(0);},
+            FlagColor::Orange => {let _ = self.ux_cf_flag_color_choice.set_value(1);},
+            FlagColor::Yellow => {let _ = self.ux_cf_flag_color_choice.set_value(2);},
+            FlagColor::Blue => {let _ = self.ux_cf_flag_color_choice.set_value(3);},
+            FlagColor::Purple => {let _ = self.ux_cf_flag_color_choice.set_value(4);},
+            FlagColor::Gray => {let _ = self.ux_cf_flag_color_choice.set_value(5);},
+        }
+        match config.flag_style {
+            FlagStyle::Fill => {let _ = self.ux_cf_flag_style_choice.set_value(0);},
+            FlagStyle::Font => {let _ = self.ux_cf_flag_style_choice.set_value(1);},
+            FlagStyle::Bold => {let _ = self.ux_cf_flag_style_choice.set_value(2);},
+            FlagStyle::Italic => {let _ = self.ux_cf_flag_style_choice.set_value(3);},
+        }
+        match config.output_format {
+            OutputFormat::Xlsx => {let _ = self.ux_cf_output_format_choice.set_value(0);},
+            OutputFormat::Csv => {let _ = self.ux_cf_output_format_choice.set_value(1);},
+            OutputFormat::Json => {let _ = self.ux_cf_output_format_choice.set_value(2);},
+        }
+        match config.output_layout {
+            OutputLayout::Wide => {let _ = self.ux_cf_output_layout_choice.set_value(0);},
+            OutputLayout::Pivot => {let _ = self.ux_cf_output_layout_choice.set_value(1);},
+            OutputLayout::Merged => {let _ = self.ux_cf_output_layout_choice.set_value(2);},
+        }
         // hide and reshow choices to trigger event handling of change
         self.ux_cf_read_start_mode_choice.hide();
         self.ux_cf_read_row_mode_choice.hide();
@@ -302,18 +650,18 @@ impl GUI {
 
         {
             let read_start_idx_input_ref = (&self.ux_cf_read_start_idx_input).clone();
-            let mut read_start_idx_input = read_start_idx_input_ref.as_ref().borrow_mut();
+            let mut read_start_idx_input = read_start_idx_input_ref.try_borrow_mut().map_err(|e| format!("Couldn't update the read_start_idx box because it's in use: {}", e))?;
             read_start_idx_input.set_value(&config.read_start_idx.to_string());
         }
         {
             let read_rows_max_input_ref = (&self.ux_cf_read_rows_max_input).clone();
-            let mut read_rows_max_input = read_rows_max_input_ref.as_ref().borrow_mut();
+            let mut read_rows_max_input = read_rows_max_input_ref.try_borrow_mut().map_err(|e| format!("Couldn't update the read_rows_max box because it's in use: {}", e))?;
             read_rows_max_input.set_value(&config.read_max_rows.to_string());
         }
 
         {
             let read_start_header_box_ref = (&self.ux_cf_read_start_header_box).clone();
-            let mut read_start_header_box = read_start_header_box_ref.as_ref().borrow_mut();
+            let mut read_start_header_box = read_start_header_box_ref.try_borrow_mut().map_err(|e| format!("Couldn't update the read_start_header box because it's in use: {}", e))?;
             let mut buf = read_start_header_box.buffer().unwrap_or_else(|| TextBuffer::default());
             buf.set_text(&config.read_start_header);
             read_start_header_box.set_buffer(buf);
@@ -321,7 +669,7 @@ impl GUI {
 
         {
             let read_row_headers_box_ref = (&self.ux_cf_read_row_headers_box).clone();
-            let mut read_row_headers_box = read_row_headers_box_ref.as_ref().borrow_mut();
+            let mut read_row_headers_box = read_row_headers_box_ref.try_borrow_mut().map_err(|e| format!("Couldn't update the read_row_headers box because it's in use: {}", e))?;
             let mut buf = read_row_headers_box.buffer().unwrap_or_else(|| TextBuffer::default());
             buf.set_text(&config.read_row_headers.join("\n"));
             read_row_headers_box.set_buffer(buf);
@@ -331,6 +679,13 @@ impl GUI {
         buf3.set_text(&config.row_order_preference.join("\n"));
         self.ux_cf_row_order_pref_box.set_buffer(buf3);
 
+        let mut column_display_buf = self.ux_cf_column_display_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        column_display_buf.set_text(&config.column_display.iter()
+            .map(|(header, name)| if name.is_empty() {header.clone()} else {format!("{} = {}", header, name)})
+            .collect::<Vec<String>>()
+            .join("\n"));
+        self.ux_cf_column_display_box.set_buffer(column_display_buf);
+
         let mut buf4 = self.ux_cf_split_char_box.buffer().unwrap_or_else(|| TextBuffer::default());
         buf4.set_text(&config.read_row_split_char);
         self.ux_cf_split_char_box.set_buffer(buf4);
@@ -338,10 +693,145 @@ impl GUI {
         let mut buf5 = self.ux_cf_test_name_prefix_box.buffer().unwrap_or_else(|| TextBuffer::default());
         buf5.set_text(&config.read_test_name_prefix);
         self.ux_cf_test_name_prefix_box.set_buffer(buf5);
+        let mut buf6 = self.ux_cf_sheet_name_template_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        buf6.set_text(&config.sheet_name_template);
+        self.ux_cf_sheet_name_template_box.set_buffer(buf6);
+        self.ux_cf_average_all_inputs_check.set_checked(config.average_all_inputs);
+        let mut end_header_buf = self.ux_cf_read_end_header_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        end_header_buf.set_text(&config.read_end_header);
+        self.ux_cf_read_end_header_box.set_buffer(end_header_buf);
+        self.ux_cf_header_match_case_insensitive_check.set_checked(config.header_match_case_insensitive);
+        self.ux_cf_normalize_header_match_check.set_checked(config.normalize_header_match);
+        self.ux_cf_normalize_headers_check.set_checked(config.normalize_headers);
+        self.ux_cf_parse_row_flags_check.set_checked(config.parse_row_flags);
+        self.ux_cf_decimal_comma_check.set_checked(config.decimal_comma);
+        self.ux_cf_excel_freeze_and_filter_check.set_checked(config.excel_freeze_and_filter);
+        let mut buf7 = self.ux_cf_average_row_name_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        buf7.set_text(&config.average_row_name);
+        self.ux_cf_average_row_name_box.set_buffer(buf7);
+        let mut buf8 = self.ux_cf_default_output_dir_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        buf8.set_text(&config.default_output_dir);
+        self.ux_cf_default_output_dir_box.set_buffer(buf8);
+        let mut buf9 = self.ux_cf_output_name_template_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        buf9.set_text(&config.output_name_template);
+        self.ux_cf_output_name_template_box.set_buffer(buf9);
+        self.ux_cf_include_headers_browser.clear();
+        let mut listed_headers = config.read_row_headers.clone();
+        listed_headers.extend(config.include_headers.iter().cloned());
+        GUI::update_include_headers_browser(&mut self.ux_cf_include_headers_browser, &listed_headers, &config.include_headers);
+        *self.last_input_dir.try_borrow_mut().map_err(|e| format!("Couldn't update the last input folder because it's in use: {}", e))? = config.last_input_dir.clone();
+        *self.last_output_dir.try_borrow_mut().map_err(|e| format!("Couldn't update the last output folder because it's in use: {}", e))? = config.last_output_dir.clone();
+        self.set_recent_inputs(&config.recent_inputs);
 
         Ok(())
     }//end set_config_store()
 
+    /// Enables or disables the button for opening the config file location,
+    /// depending on whether we know where the config file is.
+    pub fn set_config_location_available(&mut self, available: bool) {
+        if available {self.ux_cf_config_location_btn.activate();}
+        else {self.ux_cf_config_location_btn.deactivate();}
+    }//end set_config_location_available()
+
+    /// Enables or disables the buttons for reprocessing the last run and repeating
+    /// the last export, depending on whether there's a previous run to repeat.
+    pub fn set_reprocess_available(&mut self, available: bool) {
+        if available {
+            self.ux_reprocess_btn.activate();
+            self.ux_repeat_btn.activate();
+        } else {
+            self.ux_reprocess_btn.deactivate();
+            self.ux_repeat_btn.deactivate();
+        }//end else the buttons shouldn't be used
+    }//end set_reprocess_available()
+
+    /// Sets the last action message shown in the status bar, such as "Exported 10 file(s)",
+    /// and refreshes the rest of the status bar.
+    pub fn set_status(&mut self, text: &str) {
+        self.status_message = text.to_string();
+        self.update_status_bar();
+    }//end set_status()
+
+    /// Refreshes the status bar with the selected config profile, the number of
+    /// selected input files, and the last action message from set_status().  
+    /// The input files can change without a message, so this should be called often.
+    pub fn update_status_bar(&mut self) {
+        let profile = self.get_selected_profile().unwrap_or_else(|| config_store::DEFAULT_PROFILE_NAME.to_string());
+        let input_count = self.last_input_paths.as_ref().borrow().len();
+        let status = format!("Profile: {}  |  {} input file(s) selected  |  {}", profile, input_count, self.status_message);
+        // labels treat @ as the start of a symbol
+        let status = status.replace('@', "@@");
+        if self.ux_status_bar.label() != status {
+            self.ux_status_bar.set_label(&status);
+            self.ux_status_bar.redraw();
+        }//end if the status has changed
+    }//end update_status_bar()
+
+    /// Adds a line to the end of the Log tab, scrolling down to show it.  
+    /// Line breaks in line are replaced with spaces, so each entry takes one line.
+    pub fn append_log(&mut self, line: &str) {
+        let mut log_buf = self.ux_log_display.buffer().unwrap_or_else(|| TextBuffer::default());
+        log_buf.append(&format!("{}\n", line.replace('\n', " ")));
+        let line_count = self.ux_log_display.count_lines(0, log_buf.length(), true);
+        self.ux_log_display.set_buffer(log_buf);
+        self.ux_log_display.scroll(line_count, 0);
+    }//end append_log()
+
+    /// Clears everything from the Log tab.
+    pub fn clear_log(&mut self) {
+        let mut log_buf = self.ux_log_display.buffer().unwrap_or_else(|| TextBuffer::default());
+        log_buf.set_text("");
+        self.ux_log_display.set_buffer(log_buf);
+    }//end clear_log()
+
+    /// Sends the given message to the main function, as if it came from a widget.
+    pub fn send_message(&self, msg: InterfaceMessage) {
+        self.msg_sender.send(msg);
+    }//end send_message()
+
+    /// Schedules an AutoSaveConfig message to be sent after the given
+    /// number of seconds. If interval_secs is 0, nothing is scheduled.
+    pub fn schedule_config_auto_save(&self, interval_secs: u32) {
+        if interval_secs == 0 {return;}
+        let sender = self.msg_sender.clone();
+        app::add_timeout3(interval_secs as f64, move |_| {
+            sender.send(InterfaceMessage::AutoSaveConfig);
+        });
+    }//end schedule_config_auto_save()
+
+    /// Copies the given text to the clipboard.
+    pub fn copy_to_clipboard(&self, text: &str) {
+        app::copy(text);
+    }//end copy_to_clipboard()
+
+    /// Adds any of the given headers which aren't already listed to the
+    /// include headers browser. Headers are checked if include_headers is
+    /// empty or contains them. Blank and duplicate headers are skipped.
+    fn update_include_headers_browser(browser: &mut CheckBrowser, headers: &[String], include_headers: &[String]) {
+        for header in headers.iter() {
+            let header = header.trim_end_matches('\r');
+            if header.is_empty() {continue;}
+            let already_listed = (1..=browser.nitems() as i32).any(|i| browser.text(i).is_some_and(|t| t.eq(header)));
+            if already_listed {continue;}
+            let checked = include_headers.is_empty() || include_headers.iter().any(|h| h.eq(header));
+            browser.add(header, checked);
+        }//end adding each header that isn't listed yet
+    }//end update_include_headers_browser()
+
+    /// Applies the shared appearance settings for choices in the config section.
+    fn format_config_choice(choice: &mut Choice) {
+        choice.set_color(CONF_CHOICE_COLOR);
+        choice.set_selection_color(CONF_CHOICE_SELECTION_COLOR);
+        choice.set_text_color(CONF_CHOICE_TEXT_COLOR);
+        choice.set_frame(CONF_CHOICE_MENU_FRAME);
+        choice.set_down_frame(CONF_CHOICE_SELECTION_FRAME);
+        choice.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        choice.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        choice.set_text_size(CONF_CHOICE_TEXT_SIZE);
+        choice.clear_visible_focus();
+        choice.set_value(0);
+    }//end format_config_choice()
+
     /// Creates formatted strings holding the version number and date this
     /// application was compiled.
     /// 
@@ -381,18 +871,172 @@ impl GUI {
         return output_buf.text();
     }//end get_output_path_text()
 
-    /// Clears all memory or display of currently stored input paths.
+    /// Gets the sheet name typed by the user, which may be empty.
+    pub fn get_sheet_name(&self) -> String {
+        self.ux_sheet_name_box.value()
+    }//end get_sheet_name()
+
+    /// Sets the text in the output box, forgetting any output path chosen
+    /// through the file dialog, so the text is treated as a typed name.
+    pub fn set_output_path_text(&mut self, text: &str) {
+        self.clear_last_output_path();
+        let output_box_ref = (&self.ux_output_box).clone();
+        let mut output_box = output_box_ref.as_ref().borrow_mut();
+        let mut output_buf = output_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        output_buf.set_text(text);
+        output_box.set_buffer(output_buf);
+    }//end set_output_path_text()
+
+    /// Clears all memory or display of currently stored input paths.  
+    /// This can be undone, until clear_input_history() is called.
     pub fn clear_last_input_paths(&mut self) {
         let last_input_paths_ref = (&self.last_input_paths).clone();
         let mut last_input_paths = last_input_paths_ref.as_ref().borrow_mut();
         let input_box_ref = (&self.ux_input_box).clone();
         let mut input_box = input_box_ref.as_ref().borrow_mut();
-        let mut input_buf = input_box.buffer().unwrap_or_else(|| TextBuffer::default());
-        input_buf.set_text("");
-        input_box.set_buffer(input_buf);
+        if last_input_paths.len() > 0 {
+            self.input_history.as_ref().borrow_mut().record(last_input_paths.clone());
+        }//end if there's something to be able to undo
         last_input_paths.clear();
+        GUI::update_input_box(&mut input_box, &last_input_paths);
     }//end clear_last_input_paths()
 
+    /// Replaces the current input paths with input_paths, showing them in the input box.  
+    /// This can be undone, until clear_input_history() is called.
+    pub fn set_last_input_paths(&mut self, input_paths: Vec<PathBuf>) {
+        let last_input_paths_ref = (&self.last_input_paths).clone();
+        let mut last_input_paths = last_input_paths_ref.as_ref().borrow_mut();
+        let input_box_ref = (&self.ux_input_box).clone();
+        let mut input_box = input_box_ref.as_ref().borrow_mut();
+        if *last_input_paths != input_paths {
+            self.input_history.as_ref().borrow_mut().record(last_input_paths.clone());
+        }//end if there's something to be able to undo
+        *last_input_paths = input_paths;
+        GUI::update_input_box(&mut input_box, &last_input_paths);
+    }//end set_last_input_paths()
+
+    /// Forgets all changes to the input file list, so they can't be undone or redone.
+    pub fn clear_input_history(&mut self) {
+        self.input_history.as_ref().borrow_mut().clear();
+    }//end clear_input_history()
+
+    /// Updates the input box to show the file names of the given input paths.
+    fn update_input_box(input_box: &mut TextDisplay, input_paths: &Vec<PathBuf>) {
+        let mut input_buf = input_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        let mut name_vec = Vec::new();
+        for path in input_paths.iter() {
+            match path.file_name() {
+                None => name_vec.push("FilenameInvalid".to_string()),
+                Some(name) => name_vec.push(name.to_string_lossy().to_string()),
+            }//end matching whether we can get the filename
+        }//end putting filename of each file in the input_box buf
+        input_buf.set_text(&name_vec.join(", "));
+        input_box.set_buffer(input_buf);
+    }//end update_input_box()
+
+    /// Shows the given native file dialog, returning the error message if the dialog couldn't be shown.
+    /// Canceling the dialog isn't treated as an error.
+    fn show_native_dialog(dialog: &mut NativeFileChooser) -> Option<String> {
+        dialog.show();
+        let dialog_error = dialog.error_message().unwrap_or_else(|| "".to_string()).replace("No error", "");
+        if dialog_error.trim() == "" {None} else {Some(dialog_error)}
+    }//end show_native_dialog()
+
+    /// Shows FLTK's built-in file chooser, for use when the native dialog fails to open.
+    /// The chooser starts in directory, or the current folder if that's None.
+    /// Blocks until the chooser is closed, then returns the chosen paths, which is empty if the user canceled.
+    fn show_fallback_chooser(title: &str, filter: &str, chooser_type: FileChooserType, directory: Option<&str>) -> Vec<PathBuf> {
+        let mut chooser = FileChooser::new(directory.unwrap_or("."), filter, chooser_type, title);
+        chooser.show();
+        while chooser.shown() {app::wait();}
+        let mut paths = Vec::new();
+        for i in 1..=chooser.count() {
+            if let Some(path) = chooser.value(i) {paths.push(PathBuf::from(path));}
+        }//end collecting each chosen path
+        paths
+    }//end show_fallback_chooser()
+
+    /// Gets the folder containing path, to start a file dialog in next time.
+    fn get_parent_dir(path: &PathBuf) -> Option<String> {
+        match path.parent() {
+            Some(parent) if parent.as_os_str().len() > 0 => Some(parent.to_string_lossy().to_string()),
+            _ => None,
+        }//end matching whether path has a parent folder
+    }//end get_parent_dir()
+
+    /// Asks the user where to save an exported config, returning None if they cancel.
+    pub fn get_config_export_path(&mut self) -> Option<PathBuf> {
+        let mut dialog = NativeFileChooser::new(FileDialogType::BrowseSaveFile);
+        dialog.set_option(FileDialogOptions::SaveAsConfirm);
+        dialog.set_filter("*.json");
+        dialog.set_title("Please choose where to save the config.");
+        let path = match GUI::show_native_dialog(&mut dialog) {
+            None => dialog.filename(),
+            Some(dialog_error) => {
+                GUI::notify_dialog_fallback("config export path", &dialog_error);
+                GUI::show_fallback_chooser("Please choose where to save the config.", "*.json", FileChooserType::Create, None).into_iter().next()?
+            },
+        };//end matching whether the native dialog could be shown
+        if path.as_os_str().is_empty() {return None;}
+        // the native dialog doesn't always add the extension from the filter
+        if path.extension().is_none() {Some(path.with_extension("json"))} else {Some(path)}
+    }//end get_config_export_path()
+
+    /// Asks the user for a config file to import, returning None if they cancel.
+    pub fn get_config_import_path(&mut self) -> Option<PathBuf> {
+        let mut dialog = NativeFileChooser::new(FileDialogType::BrowseFile);
+        dialog.set_option(FileDialogOptions::UseFilterExt);
+        dialog.set_filter("*.json");
+        dialog.set_title("Please choose a config to import.");
+        let path = match GUI::show_native_dialog(&mut dialog) {
+            None => dialog.filename(),
+            Some(dialog_error) => {
+                GUI::notify_dialog_fallback("config to import", &dialog_error);
+                GUI::show_fallback_chooser("Please choose a config to import.", "*.json", FileChooserType::Single, None).into_iter().next()?
+            },
+        };//end matching whether the native dialog could be shown
+        if path.as_os_str().is_empty() {None} else {Some(path)}
+    }//end get_config_import_path()
+
+    /// Asks the user for a folder of input files, returning None if they cancel.  
+    /// The dialog starts in the folder the input dialog was last used in, and that's
+    /// updated to the chosen folder.
+    pub fn get_input_folder_path(&mut self) -> Option<PathBuf> {
+        let mut last_input_dir = self.last_input_dir.try_borrow_mut().ok()?;
+        let mut dialog = NativeFileChooser::new(FileDialogType::BrowseDir);
+        dialog.set_title("Please Select an Input Folder");
+        if let Some(dir) = last_input_dir.as_ref() {
+            dialog.set_directory(dir).unwrap_or_else(|e| logging::log_warn(&format!("Couldn't start the input folder dialog in {} due to {}", dir, e)));
+        }//end if we know where the input dialog was last used
+        let path = match GUI::show_native_dialog(&mut dialog) {
+            None => dialog.filename(),
+            Some(dialog_error) => {
+                GUI::notify_dialog_fallback("input folder", &dialog_error);
+                GUI::show_fallback_chooser("Please Select an Input Folder", "*", FileChooserType::Directory, last_input_dir.as_deref()).into_iter().next()?
+            },
+        };//end matching whether the native dialog could be shown
+        if path.as_os_str().is_empty() {return None;}
+        *last_input_dir = Some(path.to_string_lossy().to_string());
+        Some(path)
+    }//end get_input_folder_path()
+
+    /// Sets the input files to those found in folder, and shows how many there
+    /// are in the input box instead of listing every name.
+    pub fn set_input_folder_paths(&mut self, folder: &Path, input_paths: Vec<PathBuf>) {
+        let file_count = input_paths.len();
+        self.set_last_input_paths(input_paths);
+        let mut input_box = self.ux_input_box.as_ref().borrow_mut();
+        let mut input_buf = input_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        input_buf.set_text(&format!("{} file{} from {}", file_count, if file_count == 1 {""} else {"s"}, folder.to_string_lossy()));
+        input_box.set_buffer(input_buf);
+    }//end set_input_folder_paths()
+
+    /// Lets the user know that the native dialog failed and the built-in chooser is being used instead.
+    fn notify_dialog_fallback(purpose: &str, dialog_error: &str) {
+        logging::log_warn(&format!("We encountered a dialog error while getting the {}:\n{}", purpose, dialog_error));
+        dialog::message_default(&format!("The system file dialog couldn't be opened, so a basic file chooser will be used to pick the {} instead.\n\nIf that doesn't work either, check that a desktop portal or zenity is installed, then restart the program.\n\nDialog error: {}", purpose, dialog_error.trim()));
+    }//end notify_dialog_fallback()
+
     /// Clears all memory or display of currently stored output path.
     pub fn clear_last_output_path(&mut self) {
         let last_output_path_ref = (&self.last_output_path).clone();
@@ -410,11 +1054,135 @@ impl GUI {
         self.ux_main_window.set_cursor(fltk::enums::Cursor::Wait);
     }//end start_wait(self)
 
-    /// Clears the visual indication from start_wait()
+    /// Clears the visual indication from start_wait(), and resets the progress bar.
     pub fn end_wait(&mut self) {
         self.ux_main_window.set_cursor(fltk::enums::Cursor::Default);
+        self.set_progress(0, 0);
     }//end end_wait(self)
 
+    /// Fills the profile menu with the given profile names, selecting active.
+    pub fn set_profile_names(&mut self, names: &[String], active: &str) {
+        self.ux_profile_choice.clear();
+        for name in names {
+            let label = GUI::escape_menu_label(name);
+            let s = self.msg_sender.clone();
+            self.ux_profile_choice.add(&label, Shortcut::None, MenuFlag::Normal, move |_| s.send(InterfaceMessage::ProfileChanged));
+        }//end adding each profile to the menu
+        self.profile_names = names.to_vec();
+        if let Some(idx) = names.iter().position(|name| name == active) {
+            self.ux_profile_choice.set_value(idx as i32);
+        }//end if the active profile is in the list
+        // there's nothing to switch between with just one profile
+        if names.len() > 1 {self.ux_profile_choice.activate();}
+        else {self.ux_profile_choice.deactivate();}
+    }//end set_profile_names()
+
+    /// Escapes the characters in label that menus would otherwise treat specially,
+    /// since slashes would make submenus, and ampersands shortcuts.
+    fn escape_menu_label(label: &str) -> String {
+        label.replace('\\', "\\\\").replace('/', "\\/").replace('&', "&&")
+    }//end escape_menu_label()
+
+    /// Shows the given sets of input files in the recent inputs menu, most recent first.  
+    /// Picking one replaces the current input files with that set, which can be undone.
+    pub fn set_recent_inputs(&mut self, recent_inputs: &[Vec<String>]) {
+        self.ux_recent_inputs_menu.clear();
+        for inputs in recent_inputs {
+            let paths: Vec<PathBuf> = inputs.iter().map(PathBuf::from).collect();
+            let label = GUI::escape_menu_label(&GUI::get_recent_inputs_label(&paths));
+            let input_box_ref = (&self.ux_input_box).clone();
+            let last_input_path_ref = (&self.last_input_paths).clone();
+            let input_history_ref = (&self.input_history).clone();
+            self.ux_recent_inputs_menu.add(&label, Shortcut::None, MenuFlag::Normal, move |_| {
+                let (Ok(mut input_box), Ok(mut last_input_path), Ok(mut input_history)) = (
+                    input_box_ref.try_borrow_mut(),
+                    last_input_path_ref.try_borrow_mut(),
+                    input_history_ref.try_borrow_mut(),
+                ) else {return;};
+                if *last_input_path != paths {input_history.record(last_input_path.clone());}
+                *last_input_path = paths.clone();
+                GUI::update_input_box(&mut input_box, &last_input_path);
+            });
+        }//end adding each set of inputs to the menu
+        self.recent_inputs = recent_inputs.to_vec();
+        if recent_inputs.is_empty() {self.ux_recent_inputs_menu.deactivate();}
+        else {self.ux_recent_inputs_menu.activate();}
+    }//end set_recent_inputs()
+
+    /// Gets a short label for a set of input files in the recent inputs menu,
+    /// like "run1.txt, run2.txt, and 3 more".
+    fn get_recent_inputs_label(paths: &[PathBuf]) -> String {
+        const NAMES_SHOWN: usize = 2;
+        let names: Vec<String> = paths.iter().take(NAMES_SHOWN)
+            .map(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_else(|| path.to_string_lossy().into_owned()))
+            .collect();
+        if paths.len() > NAMES_SHOWN {format!("{}, and {} more", names.join(", "), paths.len() - NAMES_SHOWN)}
+        else {names.join(", ")}
+    }//end get_recent_inputs_label()
+
+    /// Gets the name of the profile currently selected in the profile menu.
+    pub fn get_selected_profile(&self) -> Option<String> {
+        let idx = self.ux_profile_choice.value();
+        if idx < 0 {return None;}
+        self.profile_names.get(idx as usize).cloned()
+    }//end get_selected_profile()
+
+    /// Shows txt in the integrated dialog along with a Cancel button, without waiting
+    /// for it to be clicked, so that long running work can be stopped.  
+    /// Use cancel_requested() to check whether Cancel was clicked, and
+    /// clear_integrated_dialog() once the work is done.
+    pub fn start_cancelable_wait(&mut self, txt: &str) {
+        self.start_wait();
+        *self.cancel_requested.borrow_mut() = false;
+        self.activate_dialog();
+        let mut dialog_buffer = self.ux_dialog_box.buffer().unwrap_or_else(|| TextBuffer::default());
+        dialog_buffer.set_text(txt);
+        self.ux_dialog_box.set_buffer(dialog_buffer);
+
+        self.ux_dialog_btns_flx.clear();
+        let mut button = Button::default().with_label("Cancel");
+        button.set_frame(DIALOG_BTN_FRAME);
+        button.set_down_frame(DIALOG_BTN_DOWN_FRAME);
+        button.set_color(DIALOG_BTN_COLOR);
+        button.set_selection_color(DIALOG_BTN_DOWN_COLOR);
+        button.set_callback({
+            let cancel_requested_ref = (&self.cancel_requested).clone();
+            let s = self.msg_sender.clone();
+            move |b| {
+                *cancel_requested_ref.borrow_mut() = true;
+                b.deactivate();
+                s.send(InterfaceMessage::CancelProcess);
+            }//end closure
+        });
+        self.ux_dialog_btns_flx.add(&button);
+        self.ux_dialog_btns_flx.redraw();
+        app::flush();
+    }//end start_cancelable_wait()
+
+    /// Lets the gui handle any events waiting on it, such as a click on
+    /// the Cancel button from start_cancelable_wait(), then returns
+    /// true if Cancel has been clicked.
+    pub fn cancel_requested(&mut self) -> bool {
+        app::check();
+        self.cancel_requested.try_borrow().is_ok_and(|canceled| *canceled)
+    }//end cancel_requested()
+
+    /// Updates the progress bar to show that done out of total steps are finished.  
+    /// The bar is hidden if total is 0.  
+    /// Redraws right away, so it can be called while the program is busy processing.
+    pub fn set_progress(&mut self, done: usize, total: usize) {
+        if total == 0 {
+            self.ux_progress_bar.set_value(0.);
+            self.ux_progress_bar.hide();
+        } else {
+            self.ux_progress_bar.set_maximum(total as f64);
+            self.ux_progress_bar.set_value(done.min(total) as f64);
+            self.ux_progress_bar.show();
+        }//end else we should show how far along we are
+        self.ux_progress_bar.redraw();
+        app::flush();
+    }//end set_progress()
+
     /// Closes the application.
     pub fn quit() {
         app::App::default().quit();
@@ -451,6 +1219,35 @@ impl GUI {
         self.integrated_dialog_message_choice(txt, vec!["Ok"]);
     }//end integrated_dialog_message()
 
+    /// Shows the rows parsed from the first of data_files in the integrated dialog,
+    /// so they can be checked before writing any output.
+    pub fn show_data_preview(&mut self, data_files: &Vec<Data>, config: &ConfigStore) {
+        match data_files.first() {
+            None => self.integrated_dialog_alert("There isn't any data to preview."),
+            Some(data) => {
+                let mut preview = process::get_preview_text(data, config);
+                if data_files.len() > 1 {
+                    preview.push_str(&format!("\n\nOnly the first of {} file(s) is shown.", data_files.len()));
+                }//end if there are other files not shown
+                self.integrated_dialog_message(&preview);
+            },
+        }//end matching whether there's a file to preview
+    }//end show_data_preview()
+
+    /// Shows err in the integrated dialog, along with the lines around the one it's
+    /// about from contents, the text of the file it came from, with the problem pointed out.  
+    /// The dialog uses a monospace font while this is shown, so the pointer lines up.
+    pub fn show_parse_error(&mut self, contents: &str, err: &DataParseError) {
+        let txt = match data::get_error_context(contents, err) {
+            Some(context) => format!("{}\n\n{}", err, context),
+            None => err.to_string(),
+        };//end matching whether we can show where the error is
+        let previous_font = self.ux_dialog_box.text_font();
+        self.ux_dialog_box.set_text_font(Font::Courier);
+        self.integrated_dialog_alert(&txt);
+        self.ux_dialog_box.set_text_font(previous_font);
+    }//end show_parse_error()
+
     /// Creates a modal error message that is integrated into the
     /// main window of the application.
     pub fn integrated_dialog_alert(&mut self, txt: &str) {
@@ -468,13 +1265,41 @@ impl GUI {
     }//end integrated_dialog_yes_no()
 
     /// Creates a modal dialog message which forces the user to choose
-    /// between the options specified.  
+    /// between the options specified, waiting until they do.  
     /// The buttons for options have auto-generated sizes, so if there are too
     /// many options, or they are too wordy, text might not be readable.  
     /// If this function is passed an empty vec for options, it will immediately
     /// return None. Without any options to end dialog, the user wouldn't be able
-    /// to continue.
+    /// to continue.  
+    /// This is a blocking wrapper around start_dialog_message_choice(). While it
+    /// waits, it runs the fltk event loop itself, so other callbacks still run, and
+    /// any messages they send are only received by main after this returns. Callers
+    /// partway through something, such as processing, shouldn't assume the rest of
+    /// the gui is unchanged once this returns.
     pub fn integrated_dialog_message_choice(&mut self, txt: &str, options: Vec<&str>) -> Option<usize> {
+        let pending_choice = self.start_dialog_message_choice(txt, options)?;
+        // wait for user to click a button
+        let mut button_index_to_return = None;
+        while self.app.wait() {
+            if let Some(pushed_index) = pending_choice.get() {button_index_to_return = Some(pushed_index); break;}
+        }//end continuing application while we wait for button to be pressed
+
+        self.clear_integrated_dialog();
+        return button_index_to_return;
+    }//end integrated_dialog_message_choice(self, txt, options)
+
+    /// Shows a dialog message with a button for each of the options specified,
+    /// and returns right away, without waiting for the user to choose.  
+    /// The returned PendingChoice can be polled, such as from the main loop, to find
+    /// which button was clicked. Once it has a choice, the buttons stay until
+    /// clear_integrated_dialog() or another dialog replaces them.  
+    /// If this function is passed an empty vec for options, it will immediately
+    /// return None, since the user wouldn't have any way to answer.  
+    /// Unlike integrated_dialog_message_choice(), this never runs the event loop
+    /// itself, so it's safe to call from anywhere, including while another
+    /// dialog is being waited on. Showing a new dialog replaces the buttons of
+    /// the old one, whose PendingChoice then never gets a choice.
+    pub fn start_dialog_message_choice(&mut self, txt: &str, options: Vec<&str>) -> Option<PendingChoice> {
         self.activate_dialog();
         // input validation for options being empty
         if options.len() == 0 {return None;}
@@ -483,7 +1308,7 @@ impl GUI {
         dialog_buffer.set_text(txt);
         self.ux_dialog_box.set_buffer(dialog_buffer);
         // update buttons based on type
-        let button_pressed_index = Rc::from(RefCell::from(None));
+        let pending_choice = PendingChoice::default();
 
         self.ux_dialog_btns_flx.clear();
         for (idx, option) in options.iter().enumerate() {
@@ -493,28 +1318,18 @@ impl GUI {
             button.set_color(DIALOG_BTN_COLOR);
             button.set_selection_color(DIALOG_BTN_DOWN_COLOR);
             button.set_callback({
-                let button_index_ref = (&button_pressed_index).clone();
+                let pending_choice = pending_choice.clone();
                 move |_| {
-                    let mut button_index = button_index_ref.borrow_mut();
-                    *button_index = Some(idx);
+                    pending_choice.set(idx);
+                    // make sure anything waiting on the event loop gets to see the choice
+                    app::awake();
                 }//end closure
             });
             self.ux_dialog_btns_flx.add(&button);
         }//end creating each button and handler
         self.ux_dialog_btns_flx.redraw();
-
-        // wait for user to click a button
-        let button_pressed_index_ref = (&button_pressed_index).clone();
-        let mut button_index_to_return = None;
-        while self.app.wait() {
-            if let Ok(pushed_index) = button_pressed_index_ref.try_borrow() {
-                if pushed_index.is_some() {button_index_to_return = pushed_index.clone(); break;}
-            }
-        }//end continuing application while we wait for button to be pressed
-
-        self.clear_integrated_dialog();
-        return button_index_to_return;
-    }//end integrated_dialog_message(self, txt)
+        Some(pending_choice)
+    }//end start_dialog_message_choice(self, txt, options)
 
     /// Sets up all the properties and appearances of
     /// various widgets and UI settings.
@@ -527,17 +1342,29 @@ impl GUI {
         main_window.end();
         match PngImage::load("icon.png") {
             Ok(icon) => main_window.set_icon(Some(icon)),
-            Err(err) => eprintln!("Couldn't load icon image because of {}",err),
+            Err(err) => logging::log_warn(&format!("Couldn't load icon image because of {}",err)),
         }//end matching whether we could load the icon image alright
 
         let (s,r) = app::channel();
 
         let mut tile_group = Tile::default()
             .with_pos(0,0)
-            .with_size(main_window.w(), main_window.h());
+            .with_size(main_window.w(), main_window.h() - STATUS_BAR_HEIGHT);
         tile_group.end();
         main_window.add(&tile_group);
 
+        // the status bar keeps its height while the rest of the window resizes
+        let mut status_bar = Frame::default()
+            .with_pos(0, tile_group.h())
+            .with_size(main_window.w(), STATUS_BAR_HEIGHT)
+            .with_align(Align::Inside.union(Align::Left))
+            .with_label("Ready");
+        status_bar.set_frame(FrameType::ThinDownBox);
+        status_bar.set_color(STATUS_BAR_COLOR);
+        status_bar.set_label_size(STATUS_BAR_TEXT_SIZE);
+        main_window.add(&status_bar);
+        main_window.resizable(&tile_group);
+
         // set up header information
         let mut header_group = Flex::default()
             .with_pos(0,0)
@@ -584,7 +1411,7 @@ impl GUI {
             .with_label("Select Input File(s)");
         input_btn.set_frame(IO_BTN_FRAME);
         input_btn.set_down_frame(IO_BTN_DOWN_FRAME);
-        input_btn.set_tooltip("Click this button to choose an input file.");
+        input_btn.set_tooltip("Click this button to choose an input file. (Ctrl+O)");
         input_btn.clear_visible_focus();
         input_btn.set_color(IO_BTN_COLOR);
         input_btn.set_selection_color(IO_BTN_DOWN_COLOR);
@@ -593,22 +1420,48 @@ impl GUI {
         let input_buf = TextBuffer::default();
         let mut input_box = TextDisplay::default()
             .with_pos(input_btn.x() + input_btn.w() + IO_BOX_PADDING, input_btn.y())
-            .with_size(io_controls_group.w() - (input_btn.w() + (3 * IO_BOX_PADDING)), IO_BOX_HEIGHT);
+            .with_size(io_controls_group.w() - (input_btn.w() + IO_BTN_WIDTH + IO_RECENT_BTN_WIDTH + (5 * IO_BOX_PADDING)), IO_BOX_HEIGHT);
         input_box.set_frame(IO_BOX_FRAME);
         input_box.set_scrollbar_align(Align::Bottom);
         input_box.set_scrollbar_size(7);
         input_box.set_color(IO_INPUT_BOX_COLOR);
         input_box.set_buffer(input_buf);
-        input_box.set_tooltip("This box shows all the input files you currently have selected.");
+        input_box.set_tooltip("This box shows all the input files you currently have selected.\nUse Ctrl+Z and Ctrl+Y to undo or redo changes to the selection.");
         io_controls_group.add_resizable(&input_box);
 
+        let mut input_folder_btn = Button::default()
+            .with_pos(input_box.x() + input_box.w() + IO_BOX_PADDING, input_box.y())
+            .with_size(IO_BTN_WIDTH, IO_BOX_HEIGHT)
+            .with_label("Select Input Folder");
+        input_folder_btn.emit(s, InterfaceMessage::SelectInputFolder);
+        input_folder_btn.set_frame(IO_BTN_FRAME);
+        input_folder_btn.set_down_frame(IO_BTN_DOWN_FRAME);
+        input_folder_btn.set_tooltip("Click this button to use every txt file in a folder as input. (Ctrl+Shift+O)");
+        input_folder_btn.clear_visible_focus();
+        input_folder_btn.set_color(IO_BTN_COLOR);
+        input_folder_btn.set_selection_color(IO_BTN_DOWN_COLOR);
+        io_controls_group.add(&input_folder_btn);
+
+        let mut recent_inputs_menu = MenuButton::default()
+            .with_pos(input_folder_btn.x() + input_folder_btn.w() + IO_BOX_PADDING, input_box.y())
+            .with_size(IO_RECENT_BTN_WIDTH, IO_BOX_HEIGHT)
+            .with_label("Recent");
+        recent_inputs_menu.set_frame(IO_BTN_FRAME);
+        recent_inputs_menu.set_down_frame(IO_BTN_DOWN_FRAME);
+        recent_inputs_menu.clear_visible_focus();
+        recent_inputs_menu.set_color(IO_BTN_COLOR);
+        recent_inputs_menu.set_selection_color(IO_BTN_DOWN_COLOR);
+        recent_inputs_menu.set_tooltip("Click this to pick a set of input files you've processed recently.");
+        recent_inputs_menu.deactivate();
+        io_controls_group.add(&recent_inputs_menu);
+
         let mut output_btn = Button::default()
             .with_pos(input_btn.x(), input_btn.y() + input_btn.h() + IO_BTN_PADDING)
             .with_size(IO_BTN_WIDTH, IO_BTN_HEIGHT)
             .with_label("Select Output File");
         output_btn.set_frame(IO_BTN_FRAME);
         output_btn.set_down_frame(IO_BTN_DOWN_FRAME);
-        output_btn.set_tooltip("Click this button to set where the output file will be located.\nOr, just type a name in the box to the right.");
+        output_btn.set_tooltip("Click this button to set where the output file will be located. (Ctrl+S)\nOr, just type a name in the box to the right.");
         output_btn.clear_visible_focus();
         output_btn.set_color(IO_BTN_COLOR);
         output_btn.set_selection_color(IO_BTN_DOWN_COLOR);
@@ -617,7 +1470,7 @@ impl GUI {
         let output_buf = TextBuffer::default();
         let mut output_box = TextEditor::default()
             .with_pos(output_btn.x() + output_btn.w() + IO_BOX_PADDING, output_btn.y())
-            .with_size(io_controls_group.w() - (output_btn.w() + (3 * IO_BOX_PADDING)), IO_BOX_HEIGHT);
+            .with_size(io_controls_group.w() - (output_btn.w() + IO_SHEET_LABEL_WIDTH + IO_SHEET_BOX_WIDTH + (4 * IO_BOX_PADDING)), IO_BOX_HEIGHT);
         output_box.set_frame(IO_BOX_FRAME);
         output_box.set_scrollbar_align(Align::Bottom);
         output_box.set_scrollbar_size(7);
@@ -625,9 +1478,18 @@ impl GUI {
         output_box.set_tooltip("This box shows the output file you have selected.");
         io_controls_group.add_resizable(&output_box);
 
+        let mut sheet_name_box = Input::default()
+            .with_pos(output_box.x() + output_box.w() + IO_BOX_PADDING + IO_SHEET_LABEL_WIDTH, output_box.y())
+            .with_size(IO_SHEET_BOX_WIDTH, IO_BOX_HEIGHT)
+            .with_align(Align::Left)
+            .with_label("Sheet");
+        sheet_name_box.set_frame(IO_BOX_FRAME);
+        sheet_name_box.set_tooltip("The name of the sheet to write data to. If this is blank, the Sheet Name Template is used,\nor the name of the output file if the template is also blank.");
+        io_controls_group.add(&sheet_name_box);
+
         let mut process_btn = Button::default()
             .with_pos(io_controls_group.x() + (io_controls_group.w() / 2) - (IO_PRC_BTN_WIDTH / 2), output_btn.y() + output_btn.h() + IO_PRC_BTN_PADDING)
-            .with_size(IO_PRC_BTN_WIDTH,(io_controls_group.y() + io_controls_group.h()) - (output_btn.y() + output_btn.h()) - (2 * IO_PRC_BTN_PADDING))
+            .with_size(IO_PRC_BTN_WIDTH,(io_controls_group.y() + io_controls_group.h()) - (output_btn.y() + output_btn.h()) - (2 * IO_PRC_BTN_PADDING) - IO_PROGRESS_HEIGHT)
             .with_label("Process Data");
         process_btn.emit(s, InterfaceMessage::Process);
         process_btn.set_frame(IO_BTN_FRAME);
@@ -635,9 +1497,88 @@ impl GUI {
         process_btn.clear_visible_focus();
         process_btn.set_color(IO_BTN_COLOR);
         process_btn.set_selection_color(IO_BTN_DOWN_COLOR);
-        process_btn.set_tooltip("Once you've selected an input and output, click this to process your files.");
+        process_btn.set_tooltip("Once you've selected an input and output, click this to process your files. (Ctrl+Enter)");
         io_controls_group.add_resizable(&process_btn);
 
+        let mut preview_btn = Button::default()
+            .with_pos(process_btn.x() + process_btn.w() + IO_PRC_BTN_PADDING, process_btn.y())
+            .with_size(((io_controls_group.x() + io_controls_group.w()) - (process_btn.x() + process_btn.w()) - (3 * IO_PRC_BTN_PADDING)) / 2, process_btn.h())
+            .with_label("Preview");
+        preview_btn.emit(s, InterfaceMessage::Preview);
+        preview_btn.set_frame(IO_BTN_FRAME);
+        preview_btn.set_down_frame(IO_BTN_DOWN_FRAME);
+        preview_btn.clear_visible_focus();
+        preview_btn.set_color(IO_BTN_COLOR);
+        preview_btn.set_selection_color(IO_BTN_DOWN_COLOR);
+        preview_btn.set_tooltip("Click this to see the data read from the first input file, without saving a file.");
+        io_controls_group.add(&preview_btn);
+
+        let mut copy_tsv_btn = Button::default()
+            .with_pos(preview_btn.x() + preview_btn.w() + IO_PRC_BTN_PADDING, process_btn.y())
+            .with_size((io_controls_group.x() + io_controls_group.w()) - (preview_btn.x() + preview_btn.w()) - (2 * IO_PRC_BTN_PADDING), process_btn.h())
+            .with_label("Copy\nTSV");
+        copy_tsv_btn.emit(s, InterfaceMessage::CopyToClipboard);
+        copy_tsv_btn.set_frame(IO_BTN_FRAME);
+        copy_tsv_btn.set_down_frame(IO_BTN_DOWN_FRAME);
+        copy_tsv_btn.clear_visible_focus();
+        copy_tsv_btn.set_color(IO_BTN_COLOR);
+        copy_tsv_btn.set_selection_color(IO_BTN_DOWN_COLOR);
+        copy_tsv_btn.set_tooltip("Click this to copy the results for your input files to the clipboard as tab-separated text, without saving a file.");
+        io_controls_group.add(&copy_tsv_btn);
+
+        let mut reprocess_btn = Button::default()
+            .with_pos(io_controls_group.x() + IO_PRC_BTN_PADDING, process_btn.y())
+            .with_size((process_btn.x() - io_controls_group.x() - (4 * IO_PRC_BTN_PADDING)) / 3, process_btn.h())
+            .with_label("Reprocess\nLast");
+        reprocess_btn.emit(s, InterfaceMessage::ReprocessLast);
+        reprocess_btn.set_frame(IO_BTN_FRAME);
+        reprocess_btn.set_down_frame(IO_BTN_DOWN_FRAME);
+        reprocess_btn.clear_visible_focus();
+        reprocess_btn.set_color(IO_BTN_COLOR);
+        reprocess_btn.set_selection_color(IO_BTN_DOWN_COLOR);
+        reprocess_btn.set_tooltip("Click this to process the same input files into the same output file as your last run, using the current settings.");
+        reprocess_btn.deactivate();
+        io_controls_group.add(&reprocess_btn);
+
+        let mut repeat_btn = Button::default()
+            .with_pos(reprocess_btn.x() + reprocess_btn.w() + IO_PRC_BTN_PADDING, process_btn.y())
+            .with_size(reprocess_btn.w(), process_btn.h())
+            .with_label("Repeat\nExport");
+        repeat_btn.emit(s, InterfaceMessage::RepeatLastExport);
+        repeat_btn.set_frame(IO_BTN_FRAME);
+        repeat_btn.set_down_frame(IO_BTN_DOWN_FRAME);
+        repeat_btn.clear_visible_focus();
+        repeat_btn.set_color(IO_BTN_COLOR);
+        repeat_btn.set_selection_color(IO_BTN_DOWN_COLOR);
+        repeat_btn.set_tooltip("Click this to put the input files from your last run back and process them again, using the current settings and output name.");
+        repeat_btn.deactivate();
+        io_controls_group.add(&repeat_btn);
+
+        let mut validate_btn = Button::default()
+            .with_pos(repeat_btn.x() + repeat_btn.w() + IO_PRC_BTN_PADDING, process_btn.y())
+            .with_size(process_btn.x() - (repeat_btn.x() + repeat_btn.w()) - (2 * IO_PRC_BTN_PADDING), process_btn.h())
+            .with_label("Check\nFiles");
+        validate_btn.emit(s, InterfaceMessage::Validate);
+        validate_btn.set_frame(IO_BTN_FRAME);
+        validate_btn.set_down_frame(IO_BTN_DOWN_FRAME);
+        validate_btn.clear_visible_focus();
+        validate_btn.set_color(IO_BTN_COLOR);
+        validate_btn.set_selection_color(IO_BTN_DOWN_COLOR);
+        validate_btn.set_tooltip("Click this to check that every input file can be processed, and that no test names are repeated, without saving a file.");
+        io_controls_group.add(&validate_btn);
+
+        let mut progress_bar = Progress::default()
+            .with_pos(io_controls_group.x() + IO_PRC_BTN_PADDING, process_btn.y() + process_btn.h() + (IO_PRC_BTN_PADDING / 2))
+            .with_size(io_controls_group.w() - (2 * IO_PRC_BTN_PADDING), IO_PROGRESS_HEIGHT);
+        progress_bar.set_frame(FrameType::FlatBox);
+        progress_bar.set_color(IO_CONTROLS_GROUP_COLOR);
+        progress_bar.set_selection_color(IO_PROGRESS_COLOR);
+        progress_bar.set_minimum(0.);
+        progress_bar.set_maximum(1.);
+        progress_bar.set_value(0.);
+        progress_bar.hide();
+        io_controls_group.add(&progress_bar);
+
         // set up group with configuration options
         let mut config_group = Group::default()
             .with_pos(io_controls_group.x() + io_controls_group.w(), 0)
@@ -656,9 +1597,39 @@ impl GUI {
         config_group_label.set_label_size(16);
         config_group.add(&config_group_label);
 
+        let mut profile_choice = Choice::default()
+            .with_pos(config_group.x() + config_group.w() - CONF_PROFILE_CHOICE_WIDTH - (CONF_CHOICE_HOR_PADDING * 2), config_group_label.y())
+            .with_size(CONF_PROFILE_CHOICE_WIDTH, CONF_CHOICE_HEIGHT);
+        profile_choice.set_color(CONF_CHOICE_COLOR);
+        profile_choice.set_selection_color(CONF_CHOICE_SELECTION_COLOR);
+        profile_choice.set_text_color(CONF_CHOICE_TEXT_COLOR);
+        profile_choice.set_frame(CONF_CHOICE_MENU_FRAME);
+        profile_choice.set_down_frame(CONF_CHOICE_SELECTION_FRAME);
+        profile_choice.set_text_size(CONF_CHOICE_TEXT_SIZE);
+        profile_choice.clear_visible_focus();
+        profile_choice.deactivate();
+        profile_choice.set_tooltip("The config profile whose settings are shown. Profiles are added in the config file. See Help for details.");
+        config_group.add(&profile_choice);
+
+        let mut config_tabs = Tabs::default()
+            .with_pos(config_group.x(), config_group_label.y() + config_group_label.h() + CONF_TABS_PADDING)
+            .with_size(config_group.w(), (config_group.y() + config_group.h()) - (config_group_label.y() + config_group_label.h() + CONF_TABS_PADDING) - CONF_BUTTON_HEIGHT - CONF_TABS_PADDING);
+        config_tabs.end();
+        config_tabs.set_selection_color(CONF_TAB_SELECTION_COLOR);
+        config_group.add_resizable(&config_tabs);
+
+        let mut cf_input_tab = Group::default()
+            .with_pos(config_tabs.x(), config_tabs.y() + CONF_TAB_HEIGHT)
+            .with_size(config_tabs.w(), config_tabs.h() - CONF_TAB_HEIGHT)
+            .with_label("Input");
+        cf_input_tab.end();
+        cf_input_tab.set_frame(FrameType::FlatBox);
+        cf_input_tab.set_color(CONFIG_GROUP_COLOR);
+        config_tabs.add_resizable(&cf_input_tab);
+
         let mut read_start_mode_choice = Choice::default()
-            .with_pos(config_group.x() + CONF_CHOICE_HOR_PADDING, config_group_label.y() + config_group_label.h() + CONF_CHOICE_VER_PADDING)
-            .with_size((config_group.width() / 2) - (CONF_CHOICE_HOR_PADDING * 2), CONF_CHOICE_HEIGHT)
+            .with_pos(cf_input_tab.x() + CONF_CHOICE_HOR_PADDING, cf_input_tab.y() + CONF_CHOICE_VER_PADDING)
+            .with_size((cf_input_tab.width() / 2) - (CONF_CHOICE_HOR_PADDING * 2), CONF_CHOICE_HEIGHT)
             .with_align(CONF_CHOICE_ALIGN)
             .with_label("Read Start Mode");
         read_start_mode_choice.add_choice("Header|Index");
@@ -673,10 +1644,10 @@ impl GUI {
         read_start_mode_choice.clear_visible_focus();
         read_start_mode_choice.set_value(0);
         read_start_mode_choice.set_tooltip("The method by which the program finds the header above the rows of data in the file. See Help for details.");
-        config_group.add(&read_start_mode_choice);
+        cf_input_tab.add(&read_start_mode_choice);
 
         let mut read_row_mode_choice = Choice::default()
-            .with_pos(config_group.x() + (config_group.w() / 2) + CONF_CHOICE_HOR_PADDING, config_group_label.y() + config_group_label.h() + CONF_CHOICE_VER_PADDING)
+            .with_pos(cf_input_tab.x() + (cf_input_tab.w() / 2) + CONF_CHOICE_HOR_PADDING, cf_input_tab.y() + CONF_CHOICE_VER_PADDING)
             .with_size(read_start_mode_choice.width(), CONF_CHOICE_HEIGHT)
             .with_align(CONF_CHOICE_ALIGN)
             .with_label("Read Row Mode");
@@ -692,7 +1663,7 @@ impl GUI {
         read_row_mode_choice.clear_visible_focus();
         read_row_mode_choice.set_value(0);
         read_row_mode_choice.set_tooltip("The method by which the program finds rows of data under the start header in the file. See Help for details.");
-        config_group.add(&read_row_mode_choice);
+        cf_input_tab.add(&read_row_mode_choice);
 
         let mut read_start_idx_input = IntInput::default()
             .with_pos(read_start_mode_choice.x(), read_start_mode_choice.y() + read_start_mode_choice.h() + CONF_CHOICE_VER_PADDING)
@@ -701,7 +1672,7 @@ impl GUI {
             .with_label("Read Start Idx");
         read_start_idx_input.set_frame(CONF_INPUT_FRAME);
         read_start_idx_input.set_tooltip("If using Read Start Mode of Index, sets the 0-based index where the start header is located. See Help for details.");
-        config_group.add(&read_start_idx_input);
+        cf_input_tab.add(&read_start_idx_input);
 
         let mut read_rows_max_input = IntInput::default()
             .with_pos(read_row_mode_choice.x(), read_start_idx_input.y())
@@ -710,7 +1681,7 @@ impl GUI {
             .with_label("Read Rows Max");
         read_rows_max_input.set_frame(CONF_INPUT_FRAME);
         read_rows_max_input.set_tooltip("If using Read Rows Mode of Max, sets the number of rows after the start index to read. See Help for details.");
-        config_group.add(&read_rows_max_input);
+        cf_input_tab.add(&read_rows_max_input);
 
         let read_start_header_buf = TextBuffer::default();
         let mut read_start_header_box = TextEditor::default()
@@ -724,13 +1695,13 @@ impl GUI {
         read_start_header_box.set_buffer(read_start_header_buf);
         read_start_header_box.set_cursor_style(fltk::text::Cursor::Simple);
         read_start_header_box.set_tooltip("If using Read Start Mode of Header, sets the exact value of header to look for as the start header. See Help for details.");
-        config_group.add(&read_start_header_box);
+        cf_input_tab.add(&read_start_header_box);
 
         let mut cf_multiline_flex = Flex::default()
             .with_pos(read_start_header_box.x(), read_start_header_box.y() + read_start_header_box.h() + CONF_CHOICE_VER_PADDING)
             .with_size(read_start_header_box.w(), CONF_MULTI_INPUT_HEIGHT)
             .with_type(FlexType::Row);
-        config_group.add_resizable(&cf_multiline_flex);
+        cf_input_tab.add_resizable(&cf_multiline_flex);
 
         let read_row_headers_buf = TextBuffer::default();
         let mut read_row_headers_box = TextEditor::default()
@@ -774,7 +1745,7 @@ impl GUI {
         split_char_box.set_frame(CONF_INPUT_FRAME);
         split_char_box.set_cursor_style(fltk::text::Cursor::Simple);
         split_char_box.set_tooltip("Sets the split character between header and value in data rows. See Help for details.");
-        config_group.add(&split_char_box);
+        cf_input_tab.add(&split_char_box);
 
         let test_name_prefix_buf = TextBuffer::default();
         let mut test_name_prefix_box = TextEditor::default()
@@ -788,7 +1759,254 @@ impl GUI {
         test_name_prefix_box.set_scrollbar_align(Align::Bottom);
         test_name_prefix_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
         test_name_prefix_box.set_tooltip("Sets the prefix to the test name to look for in each file. See Help for details.");
-        config_group.add(&test_name_prefix_box);
+        cf_input_tab.add(&test_name_prefix_box);
+
+        let mut cf_output_tab = Group::default()
+            .with_pos(cf_input_tab.x(), cf_input_tab.y())
+            .with_size(cf_input_tab.w(), cf_input_tab.h())
+            .with_label("Output");
+        cf_output_tab.end();
+        cf_output_tab.set_frame(FrameType::FlatBox);
+        cf_output_tab.set_color(CONFIG_GROUP_COLOR);
+        config_tabs.add(&cf_output_tab);
+
+        let mut non_finite_format_choice = Choice::default()
+            .with_pos(cf_output_tab.x() + CONF_CHOICE_HOR_PADDING, cf_output_tab.y() + CONF_CHOICE_VER_PADDING)
+            .with_size((cf_output_tab.w() / 2) - (CONF_CHOICE_HOR_PADDING * 2), CONF_CHOICE_HEIGHT)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Non-Finite Values");
+        non_finite_format_choice.add_choice("Blank|Text|Flagged");
+        GUI::format_config_choice(&mut non_finite_format_choice);
+        non_finite_format_choice.set_tooltip("How values that aren't numbers, such as the result of dividing by zero, are written to the output. See Help for details.");
+        cf_output_tab.add(&non_finite_format_choice);
+
+        let mut flag_color_choice = Choice::default()
+            .with_pos(cf_output_tab.x() + (cf_output_tab.w() / 2) + CONF_CHOICE_HOR_PADDING, non_finite_format_choice.y())
+            .with_size(non_finite_format_choice.w(), CONF_CHOICE_HEIGHT)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Flag Color");
+        flag_color_choice.add_choice("Red|Orange|Yellow|Blue|Purple|Gray");
+        GUI::format_config_choice(&mut flag_color_choice);
+        flag_color_choice.set_tooltip("The color used to highlight flagged cells in the output.");
+        cf_output_tab.add(&flag_color_choice);
+
+        let mut flag_style_choice = Choice::default()
+            .with_pos(non_finite_format_choice.x(), non_finite_format_choice.y() + non_finite_format_choice.h() + CONF_CHOICE_VER_PADDING)
+            .with_size(non_finite_format_choice.w(), CONF_CHOICE_HEIGHT)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Flag Style");
+        flag_style_choice.add_choice("Fill|Font|Bold|Italic");
+        GUI::format_config_choice(&mut flag_style_choice);
+        flag_style_choice.set_tooltip("How flagged cells stand out in the output. Fill and Font use the flag color, while Bold and Italic don't use color at all.");
+        cf_output_tab.add(&flag_style_choice);
+
+        let mut output_format_choice = Choice::default()
+            .with_pos(flag_color_choice.x(), flag_style_choice.y())
+            .with_size(flag_color_choice.w(), CONF_CHOICE_HEIGHT)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Output Format");
+        output_format_choice.add_choice("Xlsx|Csv|Json");
+        GUI::format_config_choice(&mut output_format_choice);
+        output_format_choice.set_tooltip("The kind of file to write output to. Typing an output name ending in .xlsx, .csv, or .json will switch this to match. See Help for details.");
+        cf_output_tab.add(&output_format_choice);
+
+        let sheet_name_template_buf = TextBuffer::default();
+        let mut output_layout_choice = Choice::default()
+            .with_pos(non_finite_format_choice.x(), flag_style_choice.y() + flag_style_choice.h() + CONF_CHOICE_VER_PADDING)
+            .with_size(non_finite_format_choice.w(), CONF_CHOICE_HEIGHT)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Output Layout");
+        output_layout_choice.add_choice("Wide|Pivot|Merged");
+        GUI::format_config_choice(&mut output_layout_choice);
+        output_layout_choice.set_tooltip("Wide gives a row for each input file. Pivot gives a row for each measurement, with a column for each input file.\nMerged stacks every input file in one sheet, with its source file, for pivot tables. See Help for details.");
+        cf_output_tab.add(&output_layout_choice);
+
+        let mut sheet_name_template_box = TextEditor::default()
+            .with_pos(flag_color_choice.x(), output_layout_choice.y())
+            .with_size(flag_color_choice.w(), CONF_CHOICE_HEIGHT + CONF_INPUT_SCROLLBAR_SIZE)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Sheet Name Template");
+        sheet_name_template_box.set_buffer(sheet_name_template_buf);
+        sheet_name_template_box.set_frame(CONF_INPUT_FRAME);
+        sheet_name_template_box.set_cursor_style(fltk::text::Cursor::Simple);
+        sheet_name_template_box.set_scrollbar_align(Align::Bottom);
+        sheet_name_template_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        sheet_name_template_box.set_tooltip("Sets the name of the output sheet. {date}, {time}, and {index} are filled in when processing. See Help for details.");
+        cf_output_tab.add(&sheet_name_template_box);
+
+        let mut average_all_inputs_check = CheckButton::default()
+            .with_pos(non_finite_format_choice.x(), sheet_name_template_box.y() + sheet_name_template_box.h() + CONF_CHOICE_VER_PADDING)
+            .with_size(non_finite_format_choice.w(), CONF_CHOICE_HEIGHT)
+            .with_label("Average All Inputs");
+        average_all_inputs_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        average_all_inputs_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        average_all_inputs_check.set_tooltip("If checked, all input files are averaged together into a single row of output. See Help for details.");
+        cf_output_tab.add(&average_all_inputs_check);
+
+        let average_row_name_buf = TextBuffer::default();
+        let mut average_row_name_box = TextEditor::default()
+            .with_pos(flag_color_choice.x(), average_all_inputs_check.y())
+            .with_size(flag_color_choice.w(), sheet_name_template_box.h())
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Average Row Name");
+        average_row_name_box.set_buffer(average_row_name_buf);
+        average_row_name_box.set_frame(CONF_INPUT_FRAME);
+        average_row_name_box.set_cursor_style(fltk::text::Cursor::Simple);
+        average_row_name_box.set_scrollbar_align(Align::Bottom);
+        average_row_name_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        average_row_name_box.set_tooltip("Sets the test name of the averaged row when Average All Inputs is checked.");
+        cf_output_tab.add(&average_row_name_box);
+
+        let default_output_dir_buf = TextBuffer::default();
+        let mut default_output_dir_box = TextEditor::default()
+            .with_pos(non_finite_format_choice.x(), average_row_name_box.y() + average_row_name_box.h() + CONF_CHOICE_VER_PADDING)
+            .with_size(non_finite_format_choice.w(), average_row_name_box.h())
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Output Folder");
+        default_output_dir_box.set_buffer(default_output_dir_buf);
+        default_output_dir_box.set_frame(CONF_INPUT_FRAME);
+        default_output_dir_box.set_cursor_style(fltk::text::Cursor::Simple);
+        default_output_dir_box.set_scrollbar_align(Align::Bottom);
+        default_output_dir_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        default_output_dir_box.set_tooltip("Sets the folder that typed output names are saved to. If blank, the folder of the first input file is used.");
+        cf_output_tab.add(&default_output_dir_box);
+
+        let output_name_template_buf = TextBuffer::default();
+        let mut output_name_template_box = TextEditor::default()
+            .with_pos(flag_color_choice.x(), default_output_dir_box.y())
+            .with_size(flag_color_choice.w(), default_output_dir_box.h())
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Output Name Template");
+        output_name_template_box.set_buffer(output_name_template_buf);
+        output_name_template_box.set_frame(CONF_INPUT_FRAME);
+        output_name_template_box.set_cursor_style(fltk::text::Cursor::Simple);
+        output_name_template_box.set_scrollbar_align(Align::Bottom);
+        output_name_template_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        output_name_template_box.set_tooltip("Sets the output name filled in after processing. {date} and {time} are filled in. If blank, nothing is filled in. See Help for details.");
+        cf_output_tab.add(&output_name_template_box);
+
+        let mut include_headers_browser = CheckBrowser::default()
+            .with_pos(non_finite_format_choice.x(), default_output_dir_box.y() + default_output_dir_box.h() + CONF_CHOICE_VER_PADDING)
+            .with_size(non_finite_format_choice.w(), cf_output_tab.y() + cf_output_tab.h() - (default_output_dir_box.y() + default_output_dir_box.h() + CONF_CHOICE_VER_PADDING) - CONF_TABS_PADDING)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Output Columns");
+        include_headers_browser.set_frame(CONF_INPUT_FRAME);
+        include_headers_browser.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        include_headers_browser.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        include_headers_browser.set_tooltip("Uncheck a row header to leave it out of the output. Headers are taken from the Read Row Headers setting. See Help for details.");
+        cf_output_tab.add_resizable(&include_headers_browser);
+
+        let column_display_buf = TextBuffer::default();
+        let mut column_display_box = TextEditor::default()
+            .with_pos(flag_color_choice.x(), include_headers_browser.y())
+            .with_size(flag_color_choice.w(), include_headers_browser.h())
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Column Names");
+        column_display_box.set_buffer(column_display_buf);
+        column_display_box.set_frame(CONF_INPUT_FRAME);
+        column_display_box.set_cursor_style(fltk::text::Cursor::Simple);
+        column_display_box.set_scrollbar_align(CONF_MULIT_INPUT_SCROLLBAR_ALIGN);
+        column_display_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        column_display_box.set_tooltip("Sets the order of output columns, and optionally a name to show each as, one per line like \"W = Work\". Headers not listed come after. See Help for details.");
+        cf_output_tab.add(&column_display_box);
+
+        let mut cf_advanced_tab = Group::default()
+            .with_pos(cf_input_tab.x(), cf_input_tab.y())
+            .with_size(cf_input_tab.w(), cf_input_tab.h())
+            .with_label("Advanced");
+        cf_advanced_tab.end();
+        cf_advanced_tab.set_frame(FrameType::FlatBox);
+        cf_advanced_tab.set_color(CONFIG_GROUP_COLOR);
+        config_tabs.add(&cf_advanced_tab);
+
+        let read_end_header_buf = TextBuffer::default();
+        let mut read_end_header_box = TextEditor::default()
+            .with_pos(cf_advanced_tab.x() + CONF_CHOICE_HOR_PADDING, cf_advanced_tab.y() + CONF_CHOICE_VER_PADDING)
+            .with_size(cf_advanced_tab.w() - (CONF_CHOICE_HOR_PADDING * 2), CONF_CHOICE_HEIGHT + CONF_INPUT_SCROLLBAR_SIZE)
+            .with_align(CONF_CHOICE_ALIGN)
+            .with_label("Read End Header");
+        read_end_header_box.set_frame(CONF_INPUT_FRAME);
+        read_end_header_box.set_scrollbar_align(Align::Bottom);
+        read_end_header_box.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        read_end_header_box.set_buffer(read_end_header_buf);
+        read_end_header_box.set_cursor_style(fltk::text::Cursor::Simple);
+        read_end_header_box.set_tooltip("The line after the last data row, where reading stops. If this is empty, data rows are read to the end of the file. See Help for details.");
+        cf_advanced_tab.add(&read_end_header_box);
+
+        let mut header_match_case_insensitive_check = CheckButton::default()
+            .with_pos(read_end_header_box.x(), read_end_header_box.y() + read_end_header_box.h() + CONF_CHOICE_HOR_PADDING)
+            .with_size(read_end_header_box.w() / 2, CONF_CHOICE_HEIGHT)
+            .with_label("Header Match Ignores Case");
+        header_match_case_insensitive_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        header_match_case_insensitive_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        header_match_case_insensitive_check.set_tooltip("If checked, the start and end headers are matched after trimming spaces, ignoring upper and lower case. See Help for details.");
+        cf_advanced_tab.add(&header_match_case_insensitive_check);
+
+        let mut normalize_header_match_check = CheckButton::default()
+            .with_pos(header_match_case_insensitive_check.x(), header_match_case_insensitive_check.y() + header_match_case_insensitive_check.h() + CONF_CHOICE_HOR_PADDING)
+            .with_size(read_end_header_box.w() / 2, CONF_CHOICE_HEIGHT)
+            .with_label("Loose Header Match");
+        normalize_header_match_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        normalize_header_match_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        normalize_header_match_check.set_tooltip("If checked, the start and end headers are matched ignoring trailing punctuation and differences in spaces. See Help for details.");
+        cf_advanced_tab.add(&normalize_header_match_check);
+
+        let mut normalize_headers_check = CheckButton::default()
+            .with_pos(normalize_header_match_check.x(), normalize_header_match_check.y() + normalize_header_match_check.h() + CONF_CHOICE_HOR_PADDING)
+            .with_size(read_end_header_box.w() / 2, CONF_CHOICE_HEIGHT)
+            .with_label("Normalize Row Headers");
+        normalize_headers_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        normalize_headers_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        normalize_headers_check.set_tooltip("If checked, spaces are trimmed and collapsed in the header of each data row, so the same measurement always gets the same column. See Help for details.");
+        cf_advanced_tab.add(&normalize_headers_check);
+
+        let mut parse_row_flags_check = CheckButton::default()
+            .with_pos(read_end_header_box.x() + (read_end_header_box.w() / 2), header_match_case_insensitive_check.y())
+            .with_size(read_end_header_box.w() / 2, CONF_CHOICE_HEIGHT)
+            .with_label("Read Row Flags");
+        parse_row_flags_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        parse_row_flags_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        parse_row_flags_check.set_tooltip("If checked, the quality flag after each value, such as \"OK\" or \"LOW\", is read and written next to it. See Help for details.");
+        cf_advanced_tab.add(&parse_row_flags_check);
+
+        let mut decimal_comma_check = CheckButton::default()
+            .with_pos(parse_row_flags_check.x(), parse_row_flags_check.y() + parse_row_flags_check.h() + CONF_CHOICE_HOR_PADDING)
+            .with_size(read_end_header_box.w() / 2, CONF_CHOICE_HEIGHT)
+            .with_label("Decimal Comma");
+        decimal_comma_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        decimal_comma_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        decimal_comma_check.set_tooltip("If checked, values like \"283,4\" are read with a comma as the decimal mark. See Help for details.");
+        cf_advanced_tab.add(&decimal_comma_check);
+
+        let mut excel_freeze_and_filter_check = CheckButton::default()
+            .with_pos(decimal_comma_check.x(), decimal_comma_check.y() + decimal_comma_check.h() + CONF_CHOICE_HOR_PADDING)
+            .with_size(read_end_header_box.w() / 2, CONF_CHOICE_HEIGHT)
+            .with_label("Freeze and Filter Headers");
+        excel_freeze_and_filter_check.set_label_size(CONF_CHOICE_LABEL_SIZE);
+        excel_freeze_and_filter_check.set_label_color(CONF_CHOICE_LABEL_COLOR);
+        excel_freeze_and_filter_check.set_tooltip("If checked, the header row of xlsx output is frozen in place and given filter buttons. See Help for details.");
+        cf_advanced_tab.add(&excel_freeze_and_filter_check);
+
+        let mut cf_log_tab = Group::default()
+            .with_pos(cf_input_tab.x(), cf_input_tab.y())
+            .with_size(cf_input_tab.w(), cf_input_tab.h())
+            .with_label("Log");
+        cf_log_tab.end();
+        cf_log_tab.set_frame(FrameType::FlatBox);
+        cf_log_tab.set_color(CONFIG_GROUP_COLOR);
+        config_tabs.add(&cf_log_tab);
+
+        let mut log_display = TextDisplay::default()
+            .with_pos(cf_log_tab.x() + CONF_CHOICE_HOR_PADDING, cf_log_tab.y() + CONF_CHOICE_HOR_PADDING)
+            .with_size(cf_log_tab.w() - (2 * CONF_CHOICE_HOR_PADDING), cf_log_tab.h() - (2 * CONF_CHOICE_HOR_PADDING));
+        log_display.set_buffer(TextBuffer::default());
+        log_display.set_frame(IO_BOX_FRAME);
+        log_display.set_color(IO_INPUT_BOX_COLOR);
+        log_display.set_scrollbar_align(CONF_MULIT_INPUT_SCROLLBAR_ALIGN);
+        log_display.set_scrollbar_size(CONF_INPUT_SCROLLBAR_SIZE);
+        log_display.wrap_mode(WrapMode::AtBounds, 0);
+        log_display.set_tooltip("Warnings from the last run, such as files read with another encoding or test names taken from file names.");
+        cf_log_tab.add_resizable(&log_display);
 
         let mut cf_button_flex = Flex::default()
             .with_pos(cf_multiline_flex.x(), config_group.y() + config_group.h() - CONF_BUTTON_HEIGHT)
@@ -799,20 +2017,56 @@ impl GUI {
 
         let mut cf_reset_btn = Button::default()
             .with_label("Config Reset");
+        cf_reset_btn.set_label_size(CONF_BTN_LABEL_SIZE);
         cf_reset_btn.set_frame(CONF_BTN_FRAME);
         cf_reset_btn.set_down_frame(CONF_BTN_DOWN_FRAME);
         cf_reset_btn.clear_visible_focus();
         cf_reset_btn.emit(s, InterfaceMessage::ConfigReset);
-        cf_reset_btn.set_tooltip("Resets all configuration settings to the default values.");
+        cf_reset_btn.set_tooltip("Resets all configuration settings to the default values. (Ctrl+R)");
         cf_button_flex.add(&cf_reset_btn);
 
         let mut cf_help_btn = Button::default()
             .with_label("Help");
+        cf_help_btn.set_label_size(CONF_BTN_LABEL_SIZE);
         cf_help_btn.set_frame(CONF_BTN_FRAME);
         cf_help_btn.set_down_frame(CONF_BTN_DOWN_FRAME);
         cf_help_btn.clear_visible_focus();
         cf_help_btn.set_tooltip("Provides detailed help information on using and configuring the program.");
         cf_button_flex.add(&cf_help_btn);
+        cf_button_flex.fixed(&cf_help_btn, CONF_SMALL_BTN_WIDTH);
+
+        let mut cf_config_location_btn = Button::default()
+            .with_label("Config File");
+        cf_config_location_btn.set_label_size(CONF_BTN_LABEL_SIZE);
+        cf_config_location_btn.set_frame(CONF_BTN_FRAME);
+        cf_config_location_btn.set_down_frame(CONF_BTN_DOWN_FRAME);
+        cf_config_location_btn.clear_visible_focus();
+        cf_config_location_btn.set_shortcut(Shortcut::Ctrl | Shortcut::Shift | 'c');
+        cf_config_location_btn.emit(s, InterfaceMessage::OpenConfigLocation);
+        cf_config_location_btn.set_tooltip("Opens the folder containing the config file, for advanced troubleshooting. (Ctrl+Shift+C)");
+        cf_button_flex.add(&cf_config_location_btn);
+
+        let mut cf_export_btn = Button::default()
+            .with_label("Export");
+        cf_export_btn.set_label_size(CONF_BTN_LABEL_SIZE);
+        cf_export_btn.set_frame(CONF_BTN_FRAME);
+        cf_export_btn.set_down_frame(CONF_BTN_DOWN_FRAME);
+        cf_export_btn.clear_visible_focus();
+        cf_export_btn.emit(s, InterfaceMessage::ExportConfig);
+        cf_export_btn.set_tooltip("Saves the current configuration settings to a json file, such as for sharing them.");
+        cf_button_flex.add(&cf_export_btn);
+        cf_button_flex.fixed(&cf_export_btn, CONF_SMALL_BTN_WIDTH);
+
+        let mut cf_import_btn = Button::default()
+            .with_label("Import");
+        cf_import_btn.set_label_size(CONF_BTN_LABEL_SIZE);
+        cf_import_btn.set_frame(CONF_BTN_FRAME);
+        cf_import_btn.set_down_frame(CONF_BTN_DOWN_FRAME);
+        cf_import_btn.clear_visible_focus();
+        cf_import_btn.emit(s, InterfaceMessage::ImportConfig);
+        cf_import_btn.set_tooltip("Replaces the current configuration settings with ones from a json file saved with Export.");
+        cf_button_flex.add(&cf_import_btn);
+        cf_button_flex.fixed(&cf_import_btn, CONF_SMALL_BTN_WIDTH);
         cf_help_btn.set_callback({
             move |_| {
                 let mut dialog_window = Window::default()
@@ -820,7 +2074,7 @@ impl GUI {
                     .with_label("Help Dialog");
                 match PngImage::load("icon.png") {
                     Ok(icon) => dialog_window.set_icon(Some(icon)),
-                    Err(err) => eprintln!("Couldn't load icon image because of {}",err),
+                    Err(err) => logging::log_warn(&format!("Couldn't load icon image because of {}",err)),
                 }//end matching whether we could load the icon image alright
                 dialog_window.make_resizable(true);
                 let mut help_box = HelpView::default_fill();
@@ -873,60 +2127,150 @@ impl GUI {
         let last_input_path_ref = Rc::from(RefCell::from(Vec::new()));
         let output_box_ref = Rc::from(RefCell::from(output_box));
         let last_output_path_ref = Rc::from(RefCell::from(None));
+        let last_input_dir_ref: Rc<RefCell<Option<String>>> = Rc::from(RefCell::from(None));
+        let last_output_dir_ref: Rc<RefCell<Option<String>>> = Rc::from(RefCell::from(None));
+
+        let input_history_ref = Rc::from(RefCell::from(InputHistory::default()));
 
         input_btn.set_callback({
             let input_box_ref = (&input_box_ref).clone();
             let last_input_path_ref = (&last_input_path_ref).clone();
+            let input_history_ref = (&input_history_ref).clone();
+            let last_input_dir_ref = (&last_input_dir_ref).clone();
             move |_| {
                 // get valid references to everything we need from outside
                 let mut input_box = input_box_ref.as_ref().borrow_mut();
                 let mut last_input_path = last_input_path_ref.as_ref().borrow_mut();
-                let mut input_buf = input_box.buffer().unwrap_or_else(|| TextBuffer::default());
+                let mut last_input_dir = last_input_dir_ref.as_ref().borrow_mut();
                 // create a dialog to show
                 let mut dialog = NativeFileChooser::new(FileDialogType::BrowseMultiFile);
                 dialog.set_option(FileDialogOptions::UseFilterExt);
                 dialog.set_filter("*.txt");
                 dialog.set_title("Please Select an Input File");
-                dialog.show();
-                let dialog_error = dialog.error_message().unwrap_or_else(|| "".to_string()).replace("No error","");
-                if dialog_error != "" {println!("We encountered a dialog error while getting input file:\n{}", dialog_error)}
-                *last_input_path = dialog.filenames();
-                let mut name_vec = Vec::new();
-                for path in last_input_path.iter() {
-                    match path.file_name() {
-                        None => name_vec.push("FilenameInvalid".to_string()),
-                        Some(name) => name_vec.push(name.to_string_lossy().to_string()),
-                    }//end matching whether we can get the filename
-                }//end putting filename of each file in the input_box buf
-                input_buf.set_text(&name_vec.join(", "));
+                if let Some(dir) = last_input_dir.as_ref() {
+                    // a folder that's since been removed just means starting in the default place
+                    dialog.set_directory(dir).unwrap_or_else(|e| logging::log_warn(&format!("Couldn't start the input dialog in {} due to {}", dir, e)));
+                }//end if we know where the dialog was last used
+                let new_input_paths = match GUI::show_native_dialog(&mut dialog) {
+                    None => dialog.filenames(),
+                    Some(dialog_error) => {
+                        GUI::notify_dialog_fallback("input files", &dialog_error);
+                        GUI::show_fallback_chooser("Please Select an Input File", "*.txt", FileChooserType::Multi, last_input_dir.as_deref())
+                    },
+                };//end matching whether the native dialog could be shown
+                if let Some(dir) = new_input_paths.first().and_then(GUI::get_parent_dir) {
+                    *last_input_dir = Some(dir);
+                }//end if the user chose at least one file
+                if new_input_paths != *last_input_path {
+                    input_history_ref.as_ref().borrow_mut().record(last_input_path.clone());
+                }//end if the input list is actually changing
+                *last_input_path = new_input_paths;
                 drop(dialog);
-                // make sure we still have our buffer
-                input_box.set_buffer(input_buf);
+                GUI::update_input_box(&mut input_box, &last_input_path);
+            }//end closure
+        });
+
+        main_window.handle({
+            let input_box_ref = (&input_box_ref).clone();
+            let last_input_path_ref = (&last_input_path_ref).clone();
+            let input_history_ref = (&input_history_ref).clone();
+            let last_input_dir_ref = (&last_input_dir_ref).clone();
+            let io_controls_group = io_controls_group.clone();
+            let mut input_btn = input_btn.clone();
+            let mut output_btn = output_btn.clone();
+            move |_, ev| {
+                match ev {
+                    // accept files dragged anywhere over the window, unless io controls are disabled
+                    Event::DndEnter | Event::DndDrag | Event::DndRelease => io_controls_group.active(),
+                    Event::DndLeave => true,
+                    Event::Paste => {
+                        if !io_controls_group.active() {return false;}
+                        let (Ok(mut input_box), Ok(mut last_input_path), Ok(mut input_history), Ok(mut last_input_dir)) = (
+                            input_box_ref.try_borrow_mut(),
+                            last_input_path_ref.try_borrow_mut(),
+                            input_history_ref.try_borrow_mut(),
+                            last_input_dir_ref.try_borrow_mut(),
+                        ) else {return false;};
+                        let new_input_paths = process::parse_dropped_paths(&app::event_text());
+                        if new_input_paths.is_empty() {
+                            s.send(InterfaceMessage::NoValidDrop);
+                            return true;
+                        }//end if none of the dropped files can be used
+                        if let Some(dir) = new_input_paths.first().and_then(GUI::get_parent_dir) {
+                            *last_input_dir = Some(dir);
+                        }//end if we can tell where the files came from
+                        if new_input_paths != *last_input_path {
+                            input_history.record(last_input_path.clone());
+                        }//end if the input list is actually changing
+                        *last_input_path = new_input_paths;
+                        GUI::update_input_box(&mut input_box, &last_input_path);
+                        true
+                    },
+                    Event::KeyDown | Event::Shortcut => {
+                        // ignore shortcuts while io controls are disabled, such as during dialog
+                        if !app::is_event_ctrl() || !io_controls_group.active() {return false;}
+                        let key = app::event_key();
+                        // the buttons' callbacks borrow the shared state themselves, so run them before we do
+                        if key == Key::from_char('o') && app::is_event_shift() {s.send(InterfaceMessage::SelectInputFolder); return true;}
+                        else if key == Key::from_char('o') {input_btn.do_callback(); return true;}
+                        else if key == Key::from_char('s') {output_btn.do_callback(); return true;}
+                        else if key == Key::Enter || key == Key::KPEnter {s.send(InterfaceMessage::Process); return true;}
+                        else if key == Key::from_char('r') {s.send(InterfaceMessage::ConfigReset); return true;}
+                        let (Ok(mut input_box), Ok(mut last_input_path), Ok(mut input_history)) = (
+                            input_box_ref.try_borrow_mut(),
+                            last_input_path_ref.try_borrow_mut(),
+                            input_history_ref.try_borrow_mut(),
+                        ) else {return false;};
+                        let changed_paths = if key == Key::from_char('z') {input_history.undo(last_input_path.clone())}
+                            else if key == Key::from_char('y') {input_history.redo(last_input_path.clone())}
+                            else {return false;};
+                        match changed_paths {
+                            None => false,
+                            Some(paths) => {
+                                *last_input_path = paths;
+                                GUI::update_input_box(&mut input_box, &last_input_path);
+                                true
+                            },
+                        }//end matching whether there was anything to undo or redo
+                    },
+                    _ => false,
+                }//end matching event
             }//end closure
         });
 
         output_btn.set_callback({
             let output_box_ref = (&output_box_ref).clone();
             let last_output_path_ref = (&last_output_path_ref).clone();
+            let last_output_dir_ref = (&last_output_dir_ref).clone();
             move |_| {
                 // get valid references to everything we need from outside
                 let mut output_box = output_box_ref.as_ref().borrow_mut();
                 let mut last_output_path = last_output_path_ref.as_ref().borrow_mut();
+                let mut last_output_dir = last_output_dir_ref.as_ref().borrow_mut();
                 let mut output_buf = output_box.buffer().unwrap_or_else(|| TextBuffer::default());
                 // create a dialog to show
                 let mut dialog = NativeFileChooser::new(FileDialogType::BrowseSaveFile);
                 dialog.set_option(FileDialogOptions::SaveAsConfirm);
-                dialog.set_filter("*.xlsx");
+                dialog.set_filter("*.{xlsx,csv,json}");
                 dialog.set_title("Please select a path for the output file.");
-                dialog.show();
-                let dialog_error = dialog.error_message().unwrap_or_else(|| "".to_string()).replace("No error", "");
-                if dialog_error != "" {
-                    println!("We encountered a dialog error while getting the output file path:\n{}", dialog_error);
-                    *last_output_path = None;
-                    return;
-                }//end if we cauldn't get dialog
-                *last_output_path = Some(dialog.filename());
-                match dialog.filename().file_name() {
+                if let Some(dir) = last_output_dir.as_ref() {
+                    dialog.set_directory(dir).unwrap_or_else(|e| logging::log_warn(&format!("Couldn't start the output dialog in {} due to {}", dir, e)));
+                }//end if we know where the dialog was last used
+                let new_output_path = match GUI::show_native_dialog(&mut dialog) {
+                    None => dialog.filename(),
+                    Some(dialog_error) => {
+                        GUI::notify_dialog_fallback("output file path", &dialog_error);
+                        match GUI::show_fallback_chooser("Please select a path for the output file.", "*.{xlsx,csv,json}", FileChooserType::Create, last_output_dir.as_deref()).into_iter().next() {
+                            Some(path) => path,
+                            None => {*last_output_path = None; return;},
+                        }//end matching whether the fallback chooser got a path
+                    },
+                };//end matching whether the native dialog could be shown
+                if let Some(dir) = GUI::get_parent_dir(&new_output_path) {
+                    *last_output_dir = Some(dir);
+                }//end if the user chose a file
+                *last_output_path = Some(new_output_path.clone());
+                match new_output_path.file_name() {
                     Some(name) => output_buf.set_text(&name.to_string_lossy().to_string()),
                     None => output_buf.set_text("Invalid output filename"),
                 }//end matching whether we can get the filename and update buffer
@@ -955,7 +2299,7 @@ impl GUI {
                                 read_start_idx_input.activate();
                                 read_start_header_box.deactivate();
                             },
-                            _ => eprintln!("Unknown menu value {} for read_start_mode_choice!!!", c.value()),
+                            _ => logging::log_error(&format!("Unknown menu value {} for read_start_mode_choice!!!", c.value())),
                         }//end matching value of choice
                         true
                     },
@@ -984,7 +2328,7 @@ impl GUI {
                                 read_rows_max_input.activate();
                                 read_row_headers_box.deactivate();
                             },
-                            _ => eprintln!("Unkown menu value {} for read_row_mode_choice!!!", c.value()),
+                            _ => logging::log_error(&format!("Unkown menu value {} for read_row_mode_choice!!!", c.value())),
                         }
                         true
                     },
@@ -993,6 +2337,22 @@ impl GUI {
             }//end closure
         });
 
+        // list any newly typed row headers whenever the output tab is shown
+        config_tabs.set_callback({
+            let read_row_headers_box_ref_clone = (&read_row_headers_box_ref).clone();
+            let mut include_headers_browser = include_headers_browser.clone();
+            let cf_output_tab = cf_output_tab.clone();
+            move |tabs| {
+                let on_output_tab = tabs.value().is_some_and(|tab| tab.is_same(&cf_output_tab));
+                if !on_output_tab {return;}
+                let read_row_headers_box = read_row_headers_box_ref_clone.as_ref().borrow();
+                if let Some(buf) = read_row_headers_box.buffer() {
+                    let headers: Vec<String> = buf.text().split("\n").map(|s| s.to_string()).collect();
+                    GUI::update_include_headers_browser(&mut include_headers_browser, &headers, &[]);
+                }//end if we can access the read_row_headers buffer
+            }//end closure
+        });
+
         main_window.show();
         main_window.emit(s, InterfaceMessage::AppClosing);
         GUI {
@@ -1002,8 +2362,14 @@ impl GUI {
             msg_receiver: r,
             ux_input_box: input_box_ref,
             last_input_paths: last_input_path_ref,
+            input_history: input_history_ref,
+            ux_recent_inputs_menu: recent_inputs_menu,
+            recent_inputs: Vec::new(),
             ux_output_box: output_box_ref,
+            ux_sheet_name_box: sheet_name_box,
             last_output_path: last_output_path_ref,
+            last_input_dir: last_input_dir_ref,
+            last_output_dir: last_output_dir_ref,
             ux_config_group: config_group,
             ux_io_controls_group: io_controls_group,
             ux_dialog_group: dialog_group,
@@ -1018,6 +2384,36 @@ impl GUI {
             ux_cf_row_order_pref_box: row_order_pref_box,
             ux_cf_split_char_box: split_char_box,
             ux_cf_test_name_prefix_box: test_name_prefix_box,
+            ux_cf_non_finite_format_choice: non_finite_format_choice,
+            ux_cf_flag_color_choice: flag_color_choice,
+            ux_cf_flag_style_choice: flag_style_choice,
+            ux_cf_output_format_choice: output_format_choice,
+            ux_cf_output_layout_choice: output_layout_choice,
+            ux_cf_sheet_name_template_box: sheet_name_template_box,
+            ux_cf_average_all_inputs_check: average_all_inputs_check,
+            ux_cf_average_row_name_box: average_row_name_box,
+            ux_cf_default_output_dir_box: default_output_dir_box,
+            ux_cf_output_name_template_box: output_name_template_box,
+            ux_cf_include_headers_browser: include_headers_browser,
+            ux_cf_column_display_box: column_display_box,
+            ux_cf_read_end_header_box: read_end_header_box,
+            ux_cf_header_match_case_insensitive_check: header_match_case_insensitive_check,
+            ux_cf_normalize_header_match_check: normalize_header_match_check,
+            ux_cf_normalize_headers_check: normalize_headers_check,
+            ux_cf_parse_row_flags_check: parse_row_flags_check,
+            ux_cf_decimal_comma_check: decimal_comma_check,
+            ux_cf_excel_freeze_and_filter_check: excel_freeze_and_filter_check,
+            ux_cf_config_location_btn: cf_config_location_btn,
+            ux_reprocess_btn: reprocess_btn,
+            ux_repeat_btn: repeat_btn,
+            ux_progress_bar: progress_bar,
+            cancel_requested: Rc::from(RefCell::from(false)),
+            ux_profile_choice: profile_choice,
+            profile_names: Vec::new(),
+            last_config_store: ConfigStore::default(),
+            ux_status_bar: status_bar,
+            status_message: String::from("Ready"),
+            ux_log_display: log_display,
         }//end struct construction
     }//end initialize()
 }//end impl for GUI
\ No newline at end of file
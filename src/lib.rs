@@ -12,6 +12,14 @@ pub mod config_store;
 /// that data to a file.
 pub mod process;
 
+/// This module contains code for logging messages
+/// with levels, kept in a bounded in-memory buffer.
+pub mod logging;
+
+/// This module contains code for reading the
+/// arguments used to run without the gui.
+pub mod cli;
+
 /// This module contains automated testing for
 /// various functions in other modules
 #[cfg(test)]
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, fs::OpenOptions, io::{self, Write}, path::{Path, PathBuf}, sync::{Mutex, Once}};
+use time::OffsetDateTime;
+
+/// The most log entries kept in memory at once.
+/// Once this many are held, the oldest entry is dropped for each new one.
+pub const LOG_CAPACITY: usize = 500;
+
+/// The log entries for this session, shared by the whole program.
+static LOG_BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer::new(LOG_CAPACITY));
+
+/// Where log entries go besides the buffer, and which are kept at all.
+static LOG_OUTPUT: Mutex<LogOutput> = Mutex::new(LogOutput { min_level: LogLevel::Info, file_path: None });
+
+/// The logger registered with the log crate, which every entry goes through.
+static LOGGER: AppLogger = AppLogger;
+
+/// Makes sure LOGGER is only registered once.
+static LOGGER_INSTALLED: Once = Once::new();
+
+/// An enum to represent how serious a log entry is.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Deserialize, Serialize)]
+pub enum LogLevel {
+    /// Details of what the program is doing, only useful for tracking down problems.
+    Debug,
+    /// Something happened that's worth noting, but nothing went wrong.
+    Info,
+    /// Something went wrong, but the program could carry on.
+    Warn,
+    /// Something failed, and whatever was being done couldn't be finished.
+    Error,
+}//end enum LogLevel
+
+impl LogLevel {
+    /// Returns string representation of variant.
+    pub fn to_string(&self) -> String {
+        match self {
+            LogLevel::Debug => "DEBUG".to_string(),
+            LogLevel::Info => "INFO".to_string(),
+            LogLevel::Warn => "WARN".to_string(),
+            LogLevel::Error => "ERROR".to_string(),
+        }//end matching self
+    }//end to_string()
+
+    /// Attempts to match label to variant.
+    pub fn from_str(str: &str) -> Option<LogLevel> {
+        match str {
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" => Some(LogLevel::Warn),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
+        }//end matching str
+    }//end from_str()
+
+    /// Returns the log crate's level for this variant.
+    pub fn to_log_level(&self) -> log::Level {
+        match self {
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        }//end matching self
+    }//end to_log_level()
+
+    /// Matches a level from the log crate to a variant.
+    /// Trace is treated as Debug, since there's nothing finer here.
+    pub fn from_log_level(level: log::Level) -> LogLevel {
+        match level {
+            log::Level::Trace | log::Level::Debug => LogLevel::Debug,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Warn => LogLevel::Warn,
+            log::Level::Error => LogLevel::Error,
+        }//end matching level
+    }//end from_log_level()
+}//end impl for LogLevel
+
+/// A single message in the log.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LogEntry {
+    /// When the entry was logged, in UTC.
+    pub time: OffsetDateTime,
+    pub level: LogLevel,
+    pub message: String,
+}//end struct LogEntry
+
+impl LogEntry {
+    /// Formats the entry as a single line of text, such as
+    /// "2024-05-01 13:04:59 [WARN] message".
+    /// Line breaks in the message are kept, so long messages may span several lines.
+    pub fn to_line(&self) -> String {
+        format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} [{}] {}",
+            self.time.year(),
+            self.time.month() as u8,
+            self.time.day(),
+            self.time.hour(),
+            self.time.minute(),
+            self.time.second(),
+            self.level.to_string(),
+            self.message,
+        )
+    }//end to_line()
+}//end impl LogEntry
+
+/// A bounded buffer of log entries, which drops the oldest
+/// entries once it's full, so memory use stays fixed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LogBuffer {
+    capacity: usize,
+    entries: VecDeque<LogEntry>,
+}//end struct LogBuffer
+
+impl LogBuffer {
+    /// Creates a new, empty LogBuffer that holds at most capacity entries.
+    pub const fn new(capacity: usize) -> LogBuffer {LogBuffer{capacity, entries: VecDeque::new()}}
+
+    /// Adds an entry to the end of the buffer, dropping the oldest entry if it's full.
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.capacity == 0 {return;}
+        while self.entries.len() >= self.capacity {self.entries.pop_front();}
+        self.entries.push_back(entry);
+    }//end push()
+
+    /// Gets all the entries currently held, from oldest to newest.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.entries.iter().cloned().collect()
+    }//end entries()
+}//end impl LogBuffer
+
+/// The settings for where log entries are written.
+#[derive(Clone, PartialEq, Debug)]
+struct LogOutput {
+    /// Entries less serious than this are ignored.
+    min_level: LogLevel,
+    /// The file each entry is appended to, if any.
+    file_path: Option<PathBuf>,
+}//end struct LogOutput
+
+/// The log crate backend for this program. Each record at the level from
+/// init_file_logger() or set_min_level() or above is kept in the buffer,
+/// and written to the log file if there is one.
+struct AppLogger;
+
+impl log::Log for AppLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        LogLevel::from_log_level(metadata.level()) >= LOG_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()).min_level
+    }//end enabled()
+
+    fn log(&self, record: &log::Record) {
+        let output = LOG_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let level = LogLevel::from_log_level(record.level());
+        if level < output.min_level {return;}
+        let entry = LogEntry { time: OffsetDateTime::now_utc(), level, message: record.args().to_string() };
+        if let Some(file_path) = &output.file_path {
+            // the log file can't be used to report its own problems
+            if let Err(err) = append_to_log_file(file_path, &entry) {eprintln!("Couldn't write to the log file {}: {}", file_path.to_string_lossy(), err);}
+        }//end if entries should be written to a file
+        // a panic while logging shouldn't stop anything else from being logged
+        LOG_BUFFER.lock().unwrap_or_else(|e| e.into_inner()).push(entry);
+    }//end log()
+
+    fn flush(&self) {}
+}//end impl log::Log for AppLogger
+
+/// Registers this program's logger with the log crate, so that messages from the
+/// log macros and the log_* functions here are kept. Only the first call does anything,
+/// and if another logger was already registered, that one is left in place.
+pub fn install_logger() {
+    LOGGER_INSTALLED.call_once(|| {
+        if log::set_logger(&LOGGER).is_ok() {log::set_max_level(log::LevelFilter::Trace);}
+    });
+}//end install_logger()
+
+/// Starts appending every log entry at min_level or above to the file at path,
+/// creating it if needed. Entries logged before this aren't written to the file.
+pub fn init_file_logger(path: PathBuf, min_level: LogLevel) {
+    install_logger();
+    let mut output = LOG_OUTPUT.lock().unwrap_or_else(|e| e.into_inner());
+    output.file_path = Some(path);
+    output.min_level = min_level;
+}//end init_file_logger()
+
+/// Sets the least serious level of entry that's logged.
+pub fn set_min_level(min_level: LogLevel) {
+    LOG_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()).min_level = min_level;
+}//end set_min_level()
+
+/// Gets the path of the file log entries are written to, if there is one.
+pub fn get_log_file_path() -> Option<PathBuf> {
+    LOG_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()).file_path.clone()
+}//end get_log_file_path()
+
+/// Appends a single entry to the end of the log file at path, creating it if needed.
+pub fn append_to_log_file(path: &Path, entry: &LogEntry) -> Result<(),io::Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry.to_line())
+}//end append_to_log_file()
+
+/// Adds a message with the given level to the log, through the log crate.  
+/// If a log file was set with init_file_logger(), the message is also written there.
+/// Messages less serious than the level from init_file_logger() or set_min_level() are ignored.
+pub fn log(level: LogLevel, message: &str) {
+    install_logger();
+    log::log!(level.to_log_level(), "{}", message);
+}//end log()
+
+/// Adds a detailed message to the log, which is ignored unless the level is set to Debug.
+pub fn log_debug(message: &str) {log(LogLevel::Debug, message);}
+
+/// Adds an informational message to the log.
+pub fn log_info(message: &str) {log(LogLevel::Info, message);}
+
+/// Adds a warning to the log.
+pub fn log_warn(message: &str) {log(LogLevel::Warn, message);}
+
+/// Adds an error to the log.
+pub fn log_error(message: &str) {log(LogLevel::Error, message);}
+
+/// Gets every entry currently in the log, from oldest to newest.
+pub fn get_log_entries() -> Vec<LogEntry> {
+    LOG_BUFFER.lock().unwrap_or_else(|e| e.into_inner()).entries()
+}//end get_log_entries()
+
+/// Gets the whole log as text, with one entry per line.
+pub fn get_log_text() -> String {
+    get_log_entries().iter()
+        .map(|entry| entry.to_line())
+        .collect::<Vec<String>>()
+        .join("\n")
+}//end get_log_text()
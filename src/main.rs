@@ -1,36 +1,75 @@
 #![cfg_attr(not(debug_assertions),windows_subsystem = "windows")]
-use std::{fs::{self}, io::Write, path::PathBuf, slice::Iter, time::{Duration, Instant}};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, time::{Duration, Instant}};
 
-use alveograph_exporter::{config_store::{self, ConfigStore}, data::{self, Data}, process::{close_workbook, get_workbook, write_output_to_sheet}};
+use alveograph_exporter::{cli::{self, CliArgs}, config_store::{self, ConfigProfiles, ConfigReadError, ConfigStore, EmptyInputAction, OutputFormat}, data, logging, process::{self, OutputSpec}};
 use gui::GUI;
 
 mod gui;
 
-const ERROR_LOG_NAME: &str = "errors.log";
+const CONFIG_NAME: &str = "config";
+const LOG_NAME: &str = "alveograph-exporter";
 
 fn main() {
+    // run without the gui if we were given arguments
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parse_args(&args) {
+        Ok(None) => {},
+        Ok(Some(cli_args)) => std::process::exit(run_cli(&cli_args, CONFIG_NAME)),
+        Err(msg) => {
+            eprintln!("{}\n{}", msg, cli::USAGE);
+            std::process::exit(2);
+        },
+    }//end matching whether we should run from the command line
+
     // setup gui
     let mut gui = GUI::initialize();
     let recv = gui.get_receiver();
 
     // get config information
-    let config_name = "config";
+    let config_name = CONFIG_NAME;
     let mut config_path: Option<PathBuf> = None;
     let mut config_store: ConfigStore = ConfigStore::default();
+    // every saved config profile, with config_store's profile active
+    let mut config_profiles = ConfigProfiles::default();
+    // the input files from the last successful run, for EmptyInputAction::LastFiles
+    let mut last_run_inputs: Vec<PathBuf> = Vec::new();
+    // the output from the last successful run, for reprocessing it
+    let mut last_run_output: Option<OutputSpec> = None;
+    // the sheets of each xlsx workbook written this session, so more can be appended to them
+    let mut written_workbooks: HashMap<PathBuf, Vec<process::SheetRecord>> = HashMap::new();
 
     // make sure we get config information, update gui, walk user through fix if necessary
-    ensure_config_valid(&mut gui, &mut config_store, &mut config_path, config_name);
+    ensure_config_valid(&mut gui, &mut config_profiles, &mut config_path, config_name);
+    config_store = config_profiles.get_active().clone();
+    match config_store::try_get_log_path(LOG_NAME, config_path.as_deref()) {
+        Ok(log_path) => logging::init_file_logger(log_path, config_store.log_level),
+        Err(msg) => logging::log_warn(&format!("Couldn't determine the path to the log file, so the log won't be saved:\n{}", msg)),
+    }//end matching whether we can get the log path
+    logging::log_info(&format!("Started version {}", env!("CARGO_PKG_VERSION")));
+    if let Some(config_path_v) = &config_path {
+        logging::log_info(&format!("Using the config file at {}", config_path_v.to_string_lossy()));
+    }//end if we know where the config file is
     // update gui with given config store
-    let _ = gui.set_config_store(&config_store);
+    if let Err(msg) = gui.set_config_store(&config_store) {
+        gui.integrated_dialog_alert(&format!("Couldn't show the loaded config settings:\n{}", msg));
+    }//end if we couldn't update the gui with the config
+    gui.set_profile_names(&config_profiles.get_profile_names(), config_profiles.get_active_name());
+    gui.restore_window_geometry(&config_store);
+    gui.set_config_location_available(config_path.is_some());
+    apply_output_defaults(&mut gui, &config_store);
+    if config_path.is_some() {gui.schedule_config_auto_save(config_store.auto_save_interval_secs);}
 
     while gui.wait() {
+        // the input files can be changed by the gui without sending a message
+        gui.update_status_bar();
         match recv.recv() {
             Some(gui::InterfaceMessage::AppClosing) => {
                 if let Some(config_path_v) = config_path {
                     match gui.get_config_store() {
                         Err(msg) => gui.integrated_dialog_alert(&format!("Couldn't get save config store because:\n{}", msg)),
                         Ok(config) => {
-                            if let Err(msg) = config_store::try_write_config(&config_path_v, &config) {
+                            config_profiles.update_active(config);
+                            if let Err(msg) = config_store::try_write_config(&config_path_v, &config_profiles) {
                                 gui.integrated_dialog_alert(&format!("We weren't able to save the config file. Error message is:\n{}", msg));
                             }//end if writing is not successful
                         },
@@ -40,81 +79,303 @@ fn main() {
                 }//end if we have valid config_path
                 GUI::quit();
             },
+            Some(gui::InterfaceMessage::NoValidDrop) => {
+                gui.integrated_dialog_alert("None of the dropped files were .txt files, so the input files weren't changed.");
+            },
             Some(gui::InterfaceMessage::ConfigReset) => {
                 if let Err(msg) = gui.set_config_store(&ConfigStore::default()) {
                     gui.integrated_dialog_alert(&format!("There was an issue resetting the config!:\n{}", msg));
                 }//end if we had an error while trying to reset config store
+                apply_output_defaults(&mut gui, &ConfigStore::default());
+                gui.set_status("Reset the settings to their defaults");
+            },
+            Some(gui::InterfaceMessage::ProfileChanged) => {
+                // keep any changes made to the profile we're switching away from
+                match gui.get_config_store() {
+                    Ok(config) => config_profiles.update_active(config),
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so the profile wasn't switched.\n{}", msg));
+                        gui.set_profile_names(&config_profiles.get_profile_names(), config_profiles.get_active_name());
+                        continue;
+                    },
+                }//end matching whether we could get the config store
+                let Some(profile_name) = gui.get_selected_profile() else {continue;};
+                if let Err(msg) = config_profiles.set_active(&profile_name) {
+                    gui.integrated_dialog_alert(&msg);
+                    gui.set_profile_names(&config_profiles.get_profile_names(), config_profiles.get_active_name());
+                    continue;
+                }//end if we couldn't switch to the profile
+                config_store = config_profiles.get_active().clone();
+                if let Err(msg) = gui.set_config_store(&config_store) {
+                    gui.integrated_dialog_alert(&format!("Couldn't show the settings for the \"{}\" profile:\n{}", profile_name, msg));
+                }//end if we couldn't update the gui with the config
+                apply_output_defaults(&mut gui, &config_store);
+                logging::set_min_level(config_store.log_level);
+                gui.set_status(&format!("Switched to the \"{}\" profile", profile_name));
+            },
+            Some(gui::InterfaceMessage::ExportConfig) => {
+                let config = match gui.get_config_store() {
+                    Ok(config) => config,
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so nothing was exported.\n{}", msg));
+                        continue;
+                    },
+                };//end matching whether we could get the config store
+                let Some(export_path) = gui.get_config_export_path() else {continue;};
+                match config_store::export_config_json(&config, &export_path) {
+                    Ok(()) => {
+                        gui.set_status("Exported the settings");
+                        gui.integrated_dialog_message(&format!("Saved the configuration settings to \"{}\".", export_path.to_string_lossy()));
+                    },
+                    Err(msg) => gui.integrated_dialog_alert(&format!("Couldn't export the configuration settings:\n{}", msg)),
+                }//end matching whether the export worked
+            },
+            Some(gui::InterfaceMessage::ImportConfig) => {
+                let Some(import_path) = gui.get_config_import_path() else {continue;};
+                match config_store::import_config_json(&import_path) {
+                    Err(msg) => gui.integrated_dialog_alert(&format!("The configuration settings weren't changed.\n{}", msg)),
+                    Ok(config) => {
+                        if let Err(msg) = gui.set_config_store(&config) {
+                            gui.integrated_dialog_alert(&format!("Couldn't show the imported config settings:\n{}", msg));
+                        }//end if we couldn't update the gui with the config
+                        apply_output_defaults(&mut gui, &config);
+                        logging::set_min_level(config.log_level);
+                        config_store = config;
+                        gui.set_status("Imported settings");
+                    },
+                }//end matching whether the import worked
+            },
+            Some(gui::InterfaceMessage::SelectInputFolder) => {
+                let Some(input_folder) = gui.get_input_folder_path() else {continue;};
+                let recursive = gui.get_config_store().map(|config| config.input_folder_recursive).unwrap_or(config_store.input_folder_recursive);
+                match process::find_input_files(&input_folder, recursive) {
+                    Err(msg) => gui.integrated_dialog_alert(&format!("The input files weren't changed.\n{}", msg)),
+                    Ok(input_paths) if input_paths.is_empty() => {
+                        gui.integrated_dialog_alert(&format!("There aren't any txt files in \"{}\", so the input files weren't changed.", input_folder.to_string_lossy()));
+                    },
+                    Ok(input_paths) => {
+                        gui.set_status(&format!("Found {} txt file(s) in the input folder", input_paths.len()));
+                        gui.set_input_folder_paths(&input_folder, input_paths);
+                    },
+                }//end matching whether we could find files in the folder
+            },
+            Some(gui::InterfaceMessage::OpenConfigLocation) => {
+                match &config_path {
+                    None => gui.integrated_dialog_alert("The location of the config file couldn't be determined, so there's nothing to open."),
+                    Some(config_path_v) => {
+                        if let Err(e) = opener::reveal(config_path_v) {
+                            gui.integrated_dialog_alert(&format!("Couldn't open the config file location \"{}\":\n{}", config_path_v.to_string_lossy(), e));
+                        }//end if we couldn't reveal the config file
+                    },
+                }//end matching whether we know where config file is
+            },
+            Some(gui::InterfaceMessage::AutoSaveConfig) => {
+                // problems here are only logged, since the user didn't ask for this save
+                if let Some(config_path_v) = &config_path {
+                    match gui.get_config_store() {
+                        Err(msg) => {
+                            logging::log_error(&format!("Couldn't get config store for auto-save\n{}", msg));
+                            gui.schedule_config_auto_save(config_store.auto_save_interval_secs);
+                        },
+                        Ok(config) => {
+                            gui.schedule_config_auto_save(config.auto_save_interval_secs);
+                            config_profiles.update_active(config);
+                            if let Err(msg) = config_store::try_write_config(config_path_v, &config_profiles) {
+                                logging::log_error(&format!("Couldn't auto-save config file\n{}", msg));
+                            }//end if writing is not successful
+                        },
+                    }//end matching whether or not we can get the config store
+                }//end if we have valid config_path
+            },
+            Some(gui::InterfaceMessage::CopyToClipboard) => {
+                config_store = match gui.get_config_store() {
+                    Ok(config) => config,
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so nothing was copied.\n{}", msg));
+                        continue;
+                    },
+                };//end matching whether we could get the config store
+                if let Err(problems) = config_store.validate() {
+                    gui.integrated_dialog_alert(&format!("There are problem(s) with the configuration settings. Please fix them before copying.\n\n{}", problems.join("\n")));
+                    continue;
+                }//end if the config can't be used for processing
+                let Some(input_paths) = validate_input_paths(gui.get_last_input_paths(), &config_store, &last_run_inputs, &mut gui) else {continue;};
+                gui.start_wait();
+                let (data_files, files) = process::parse_inputs(&input_paths, &config_store);
+                let report = process::RunReport { files, ..process::RunReport::default() };
+                let issues = report.issue_messages();
+                if issues.len() > 0 {
+                    gui.integrated_dialog_alert(&format!("There were issue(s) while reading. {} of {} file(s) were used. The issues will be displayed below.\n\n{}", report.files_parsed(), input_paths.len(), issues.join("\n")));
+                }//end if there was at least one issue
+                if data_files.is_empty() {
+                    gui.integrated_dialog_alert("None of the input files could be used, so nothing was copied.");
+                } else {
+                    let data_files = process::combine_data(data::filter_by_test_name(data_files, &config_store), &config_store);
+                    gui.copy_to_clipboard(&process::get_wide_tsv(&data_files, &config_store));
+                    gui.set_status(&format!("Copied {} file(s) to the clipboard", report.files_parsed()));
+                    gui.integrated_dialog_message(&format!("Copied results for {} file(s) to the clipboard.", report.files_parsed()));
+                }//end else we have something to copy
+                gui.end_wait();
+            },
+            Some(gui::InterfaceMessage::CancelProcess) => {
+                // the cancel button already stopped the run it was shown for, by the time this arrives
+            },
+            Some(gui::InterfaceMessage::Preview) => {
+                config_store = match gui.get_config_store() {
+                    Ok(config) => config,
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so nothing can be previewed.\n{}", msg));
+                        continue;
+                    },
+                };//end matching whether we could get the config store
+                if let Err(problems) = config_store.validate() {
+                    gui.integrated_dialog_alert(&format!("There are problem(s) with the configuration settings. Please fix them before previewing.\n\n{}", problems.join("\n")));
+                    continue;
+                }//end if the config can't be used for processing
+                let Some(input_paths) = validate_input_paths(gui.get_last_input_paths(), &config_store, &last_run_inputs, &mut gui) else {continue;};
+                gui.start_wait();
+                let (data_files, files) = process::parse_inputs(&input_paths, &config_store);
+                let report = process::RunReport { files, ..process::RunReport::default() };
+                let issues = report.issue_messages();
+                if issues.len() > 0 {
+                    gui.integrated_dialog_alert(&format!("There were issue(s) while reading. {} of {} file(s) could be read. The issues will be displayed below.\n\n{}", report.files_parsed(), input_paths.len(), issues.join("\n")));
+                    if report.files.first().is_some_and(|file| file.result != Ok(Vec::new())) {
+                        show_line_error(&mut gui, &input_paths[0], &config_store);
+                    }//end if the first file had any issues
+                }//end if there was at least one issue
+                // a file that couldn't be parsed at all would give a misleading preview of the next one
+                if report.files.first().is_some_and(|file| file.result.is_ok()) {
+                    gui.show_data_preview(&data_files, &config_store);
+                    gui.set_status(&format!("Previewed {} file(s)", report.files_parsed()));
+                }//end if the first file could be parsed
+                gui.end_wait();
+            },
+            Some(gui::InterfaceMessage::Validate) => {
+                config_store = match gui.get_config_store() {
+                    Ok(config) => config,
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so nothing can be checked.\n{}", msg));
+                        continue;
+                    },
+                };//end matching whether we could get the config store
+                if let Err(problems) = config_store.validate() {
+                    gui.integrated_dialog_alert(&format!("There are problem(s) with the configuration settings. Please fix them before checking.\n\n{}", problems.join("\n")));
+                    continue;
+                }//end if the config can't be used for processing
+                let Some(input_paths) = validate_input_paths(gui.get_last_input_paths(), &config_store, &last_run_inputs, &mut gui) else {continue;};
+                gui.set_progress(0, input_paths.len() + 1);
+                gui.start_cancelable_wait(&format!("Checking {} file(s)...", input_paths.len()));
+                let parsed = process::parse_inputs_with_progress(&input_paths, &config_store, &mut |done,total| {
+                    gui.set_progress(done, total);
+                    !gui.cancel_requested()
+                });
+                let summary = process::validate_parsed(&parsed, &config_store);
+                gui.clear_integrated_dialog();
+                gui.set_status(&format!("Checked {} file(s)", parsed.files.len()));
+                logging::log_info(&format!("Checked {} file(s) without writing output:\n{}", parsed.files.len(), summary.lines.join("\n")));
+                match summary.passed {
+                    true => gui.integrated_dialog_message(&format!("Every file can be processed, and nothing was written.\n\n{}", summary.lines.join("\n"))),
+                    false => gui.integrated_dialog_alert(&format!("Some files have problems that should be fixed before processing. Nothing was written.\n\n{}", summary.lines.join("\n"))),
+                }//end matching whether every file passed
+                gui.end_wait();
             },
             Some(gui::InterfaceMessage::Process) => {
-                // get input and output paths from gui/user
-                let input_paths = gui.get_last_input_paths();
-                let output_path = gui.get_last_output_paths();
-                // make sure we have valid input and output paths
-                let input_valid = validate_input_paths(&input_paths, &mut gui);
-                let output_path = validate_output_path(output_path, &mut gui);
-                if !input_valid || output_path.is_err() {continue;}
-                let output_path = output_path.expect("We already checked it wasn't an error.");
                 // grab configuration details from the gui
-                config_store = gui.get_config_store().unwrap();
+                config_store = match gui.get_config_store() {
+                    Ok(config) => config,
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so processing was stopped.\n{}", msg));
+                        continue;
+                    },
+                };//end matching whether we could get the config store
+                if let Err(problems) = config_store.validate() {
+                    gui.integrated_dialog_alert(&format!("There are problem(s) with the configuration settings. Please fix them before processing.\n\n{}", problems.join("\n")));
+                    continue;
+                }//end if the config can't be used for processing
+                // get input and output paths from gui/user, and make sure they're valid
+                let Some(input_paths) = validate_input_paths(gui.get_last_input_paths(), &config_store, &last_run_inputs, &mut gui) else {continue;};
+                let output_path = gui.get_last_output_paths();
+                // make sure we have a valid output path, which might change the output format
+                let Ok((output_path, output_format, append)) = validate_output_path(output_path, &input_paths, &config_store, &written_workbooks, &mut gui) else {continue;};
+                if output_format != config_store.output_format {
+                    config_store.output_format = output_format;
+                    if let Err(msg) = gui.set_config_store(&config_store) {
+                        gui.integrated_dialog_alert(&format!("Couldn't show the new output format in the settings:\n{}", msg));
+                    }//end if we couldn't update the gui with the new format
+                }//end if the output name picked a different format
                 // proceed with processing calls
+                let (sheet_name, sheet_name_changed) = process::resolve_sheet_name(&gui.get_sheet_name(), &output_path, &config_store);
+                if sheet_name_changed && !gui.integrated_dialog_yes_no(&format!("The sheet name \"{}\" can't be used by Excel as it is, since sheet names are limited to 31 characters and can't use any of []:*?/\\ or start or end with an apostrophe. Would you like to use \"{}\" instead?", gui.get_sheet_name().trim(), sheet_name)) {
+                    continue;
+                }//end if the user doesn't want the changed sheet name
                 gui.start_wait();
-                let start = Instant::now();
-                let mut data_files: Vec<Data> = Vec::new();
-                for (i,input_path) in input_paths.iter().enumerate() {
-                    match fs::read_to_string(input_path) {
-                        Err(msg) => gui.integrated_dialog_alert(&format!("There was an error reading from path \"{}\":\n{}",input_path.to_string_lossy(),msg)),
-                        Ok(file_contents) => {
-                            let filename = match input_path.file_name() {
-                                Some(osstr) => osstr.to_string_lossy().into_owned(),
-                                None => "UNKNOWN FILENAME".to_string(),
-                            };
-                            match data::read_data_from_file(&filename, &file_contents, &config_store) {
-                                Err(msg) => {
-									append_error_log(ERROR_LOG_NAME, "Issue Reading from Single File", vec![""].iter()).unwrap_or_else(|e| gui.integrated_dialog_alert(&format!("Couldn't save error to log file:{e}")));
-                                    if i >= input_paths.len() - 1 {
-                                        gui.integrated_dialog_alert(&format!("There was an issue reading from path \"{}\". The issue was:\n{}",input_path.to_string_lossy(),msg));
-                                    }//end if this is last file
-                                    else {
-                                        if !gui.integrated_dialog_yes_no(&format!("There was an issue reading from path \"{}\". The issue will be displayed below.\n\tDo you want to continue processing?\n\n{}",input_path.to_string_lossy(),msg)) {
-                                            break;} else {continue;}
-                                    }//end else there are a bunch more files
-                                },
-                                Ok((data,errs)) => {
-                                    if errs.len() > 0 {
-										append_error_log(ERROR_LOG_NAME, "Non-Fatal Errors while Processing Files", errs.iter().map(|s| s.as_str()).collect::<Vec<&str>>().iter()).unwrap_or_else(|e| gui.integrated_dialog_alert(&format!("Couldn't save error to log file:{e}")));
-                                        if !gui.integrated_dialog_yes_no(&format!("There were issue(s) parsing data from path {}. The issues will be displayed below.\n\tDo you still want to use output from this file?\n\n{}",input_path.to_string_lossy(),errs.join("\n"))) {
-                                            if i >= input_paths.len() - 1 && !gui.integrated_dialog_yes_no(&format!("Since you said you don't want to use the current file, do you want to continue processing?")) {
-                                                break;} else {continue;}
-                                        }//end if user said they don't want to include current, potentially broken file
-                                    }//end if there is at least one error
-                                    data_files.push(data);
-                                },
-                            }//end matching whether we can read data from this file
-                        },
-                    }//end matching whether or not we can get a string from the input file
-                }//end looping over each input file to read from
-
-                let mut wb = get_workbook();
-                let mut wrote_to_output = false;
-                let mut closed_output = false;
-                if let Err(err) = write_output_to_sheet(&mut wb, &data_files, "alveograph-exporter-output") {
-                    gui.integrated_dialog_alert(&format!("There was an issue writing output data to the sheet:\n{}",err));
-                }//end if there was an error writing to the sheet
-                else {wrote_to_output = true;}
-                if let Err(err) = close_workbook(&mut wb, &output_path) {
-                    gui.integrated_dialog_alert(&format!("There was an issue closing the workbook \"{}\". \nIs it open? \n{}", output_path.to_string_lossy(),err));
-                }//end if there was an error closing the workbook
-                else {closed_output = true;}
+                let mut output_spec = OutputSpec::new(output_path, &sheet_name);
+                output_spec.profile_name = Some(config_profiles.get_active_name().to_string());
+                if append {
+                    output_spec.append_to = written_workbooks.get(&output_spec.path).cloned().unwrap_or_default();
+                }//end if the new sheets should go after the ones already written
+                let succeeded = run_and_report(&mut gui, &input_paths, &output_spec, &config_store, &mut written_workbooks);
 
                 // perform cleanup after finishing processing
                 gui.clear_last_input_paths();
                 gui.clear_last_output_path();
-                if wrote_to_output && closed_output {
-                    eprintln!("Finished processing file(s).");
-                    let total_duration = start.elapsed();
-                    if gui.integrated_dialog_yes_no(&format!("Processing has completed successfully in {} miliseconds. Would you like to open the folder where the output file is located?", format_milliseconds(total_duration))) {
-                        opener::reveal(output_path).unwrap_or_else(|e| eprintln!("Couldn't reveal output due to {}", e));
-                    }//end if user want to open folder
+                apply_output_defaults(&mut gui, &config_store);
+                if succeeded {
+                    config_store.add_recent_inputs(&input_paths);
+                    gui.set_recent_inputs(&config_store.recent_inputs);
+                    last_run_inputs = input_paths;
+                    last_run_output = Some(output_spec);
+                    gui.set_reprocess_available(true);
+                    gui.clear_input_history();
+                }//end if output file seems to be created ok
+                gui.end_wait();
+            },
+            Some(gui::InterfaceMessage::RepeatLastExport) => {
+                if last_run_inputs.is_empty() {continue;}
+                gui.set_last_input_paths(last_run_inputs.clone());
+                // the output path is worked out again from the inputs, using the last output's name if none is typed
+                if gui.get_output_path_text().trim().is_empty() {
+                    if let Some(output_name) = last_run_output.as_ref().and_then(|spec| spec.path.file_name()) {
+                        gui.set_output_path_text(&output_name.to_string_lossy());
+                    }//end if there's a name to reuse
+                }//end if there's no output name to use
+                gui.send_message(gui::InterfaceMessage::Process);
+            },
+            Some(gui::InterfaceMessage::ReprocessLast) => {
+                let Some(last_output) = last_run_output.clone() else {continue;};
+                config_store = match gui.get_config_store() {
+                    Ok(config) => config,
+                    Err(msg) => {
+                        gui.integrated_dialog_alert(&format!("Couldn't read the current configuration settings, so processing was stopped.\n{}", msg));
+                        continue;
+                    },
+                };//end matching whether we could get the config store
+                if let Err(problems) = config_store.validate() {
+                    gui.integrated_dialog_alert(&format!("There are problem(s) with the configuration settings. Please fix them before processing.\n\n{}", problems.join("\n")));
+                    continue;
+                }//end if the config can't be used for processing
+                // make sure the input files from last time are still around
+                let (input_paths, missing_paths): (Vec<PathBuf>, Vec<PathBuf>) = last_run_inputs.iter().cloned().partition(|path| path.exists());
+                if input_paths.is_empty() {
+                    gui.integrated_dialog_alert("None of the input files from the last run exist anymore, so there's nothing to reprocess.");
+                    continue;
+                } else if missing_paths.len() > 0 {
+                    let missing_names: Vec<String> = missing_paths.iter().map(|path| path.to_string_lossy().to_string()).collect();
+                    if !gui.integrated_dialog_yes_no(&format!("{} of the input files from the last run no longer exist:\n{}\n\nWould you like to reprocess the remaining {} file(s) anyway?", missing_paths.len(), missing_names.join("\n"), input_paths.len())) {continue;}
+                }//end else if some of the input files are missing
+                // the output file keeps its extension, so the format follows it like it did last time
+                let (output_path, output_format) = process::resolve_output_path(last_output.path, config_store.output_format);
+                config_store.output_format = output_format;
+                gui.start_wait();
+                // keep the sheet name and any sheets appended to last time, so the workbook comes out the same way
+                let output_spec = OutputSpec {
+                    path: output_path,
+                    profile_name: Some(config_profiles.get_active_name().to_string()),
+                    ..last_output
+                };
+                if run_and_report(&mut gui, &input_paths, &output_spec, &config_store, &mut written_workbooks) {
+                    last_run_inputs = input_paths;
+                    last_run_output = Some(output_spec);
                 }//end if output file seems to be created ok
                 gui.end_wait();
             },
@@ -123,6 +384,148 @@ fn main() {
     }//end main application loop
 }//end main function
 
+/// Processes the files given on the command line without ever showing the gui.  
+/// A config file given with --config is used in place of the usual one.  
+/// Issues are printed to stderr, and the output path is printed to stdout on success.  
+/// Returns the exit code for the program.
+fn run_cli(cli_args: &CliArgs, config_name: &str) -> i32 {
+    let config_path = match &cli_args.config {
+        Some(config_path) if !config_path.exists() => {
+            eprintln!("The config file {} doesn't exist.", config_path.to_string_lossy());
+            return 1;
+        },
+        Some(config_path) => Ok(config_path.clone()),
+        None => config_store::try_read_config_path(config_name, false),
+    };//end matching whether we were given a config file to use
+    let (mut config, profile_name) = match config_path {
+        Err(msg) => {
+            eprintln!("Could not determine the path to a config file:\n{}", msg);
+            return 1;
+        },
+        Ok(config_path) if !config_path.exists() => (ConfigStore::default(), None),
+        Ok(config_path) => match config_store::try_read_config(&config_path) {
+            Ok(config_profiles) => (config_profiles.get_active().clone(), Some(config_profiles.get_active_name().to_string())),
+            // problems in other profiles don't matter here, and the active one is checked below
+            Err(ConfigReadError::Invalid{config_profiles, ..}) => (config_profiles.get_active().clone(), Some(config_profiles.get_active_name().to_string())),
+            Err(err) => {
+                eprintln!("Couldn't read the config file at {}:\n{}", config_path.to_string_lossy(), err);
+                return 1;
+            },
+        },
+    };//end matching whether we can read the config file
+    if let Err(problems) = config.validate() {
+        eprintln!("The config file has invalid settings:\n{}", problems.join("\n"));
+        return 1;
+    }//end if the config isn't usable
+
+    if cli_args.dry_run {
+        let parsed = process::parse_inputs_with_progress(&cli_args.inputs, &config, &mut |_,_| true);
+        let summary = process::validate_parsed(&parsed, &config);
+        for line in summary.lines {println!("{}", line);}
+        return if summary.passed {0} else {1};
+    }//end if we should only check the inputs
+    let (output_path, output_format) = process::resolve_output_path(cli_args.output.clone(), config.output_format);
+    config.output_format = output_format;
+    let mut output_spec = OutputSpec::new(output_path, &process::get_sheet_name(&config, 1));
+    output_spec.profile_name = profile_name;
+    let report = process::run(&cli_args.inputs, &output_spec, &config);
+    for issue in report.issue_messages() {eprintln!("{}", issue);}
+    for msg in &report.out_of_range {eprintln!("Out of range: {}", msg);}
+    if let Some(msg) = &report.write_error {eprintln!("{}", msg);}
+    if !report.succeeded() {return 1;}
+    println!("{}", report.output_path.to_string_lossy());
+    0
+}//end run_cli()
+
+/// Processes the input files into the given output, showing the user any issues
+/// along the way, and offering to open the output folder if it worked.  
+/// If an xlsx workbook is written, its sheets are saved into written_workbooks.  
+/// Returns true if the output was written successfully.
+fn run_and_report(gui: &mut GUI, input_paths: &[PathBuf], output_spec: &OutputSpec, config: &ConfigStore, written_workbooks: &mut HashMap<PathBuf, Vec<process::SheetRecord>>) -> bool {
+    let start = Instant::now();
+    gui.clear_log();
+    gui.set_progress(0, input_paths.len() + 1);
+    gui.start_cancelable_wait(&format!("Processing {} file(s)...", input_paths.len()));
+    let parsed = process::parse_inputs_with_progress(input_paths, config, &mut |done,total| {
+        gui.set_progress(done, total);
+        !gui.cancel_requested()
+    });
+    // both callbacks need the gui, but they're never called at the same time
+    let gui_cell = RefCell::new(&mut *gui);
+    let report = process::export_parsed(parsed, output_spec, config, &mut |duplicates| {
+        let mut gui = gui_cell.borrow_mut();
+        gui.clear_integrated_dialog();
+        let duplicate_list = duplicates.iter().map(|(name, count)| format!("\"{}\" is used by {} files", name, count)).collect::<Vec<String>>().join("\n");
+        let choice = gui.integrated_dialog_message_choice(&format!("Some of the input files have the same test name, so their rows couldn't be told apart in the output.\n\n{}\n\nWould you like to number the repeated names, like \"name (2)\" and \"name (3)\"?", duplicate_list), vec!["Number Them", "Keep Names"]);
+        if choice == Some(0) {
+            for (name, count) in duplicates.iter() {gui.append_log(&format!("Numbered the {} files with the test name \"{}\".", count, name));}
+        }//end if the repeated names should be numbered
+        gui.start_cancelable_wait("Writing output...");
+        choice == Some(0)
+    }, &mut |done,total| {
+        let mut gui = gui_cell.borrow_mut();
+        gui.set_progress(done, total);
+        !gui.cancel_requested()
+    });
+    gui.clear_integrated_dialog();
+    let issues = report.issue_messages();
+    if issues.len() > 0 {
+        logging::log_error(&format!("Issues while Processing Files\n{}", issues.join("\n")));
+    }//end if there was at least one issue
+    // issues that didn't stop a file from being used go to the log tab instead of a dialog
+    let export_report = report.clone().into_export_report();
+    for issue in export_report.issues.iter().chain(export_report.out_of_range.iter()) {gui.append_log(issue);}
+    if export_report.zero_values_skipped > 0 {gui.append_log(&format!("Left out {} zero value(s).", export_report.zero_values_skipped));}
+    if export_report.skipped.len() > 0 {
+        for skipped in export_report.skipped.iter() {gui.append_log(skipped);}
+        gui.integrated_dialog_alert(&format!("{} of {} file(s) couldn't be used in the output. The reasons will be displayed below.\n\n{}", export_report.skipped.len(), input_paths.len(), export_report.skipped.join("\n")));
+    }//end if any files couldn't be used
+    if let Some(msg) = &report.write_error {
+        logging::log_error(msg);
+        gui.integrated_dialog_alert(msg);
+    }//end if there was an error writing the output
+    if report.succeeded() && report.sheets.len() > 0 {
+        written_workbooks.insert(report.output_path.clone(), report.sheets.clone());
+    }//end if we wrote a workbook that could be appended to
+    let output_name = report.output_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    match (report.canceled, report.succeeded()) {
+        (true, true) => gui.set_status(&format!("Canceled, but exported {} file(s) to {}", report.files_parsed(), output_name)),
+        (true, false) => gui.set_status("Canceled processing"),
+        (false, true) => gui.set_status(&format!("Exported {} file(s) to {}", report.files_parsed(), output_name)),
+        (false, false) => gui.set_status("Processing failed"),
+    }//end matching how the run ended
+    if report.canceled {
+        logging::log_info("Processing was canceled.");
+        if report.succeeded() {
+            gui.integrated_dialog_message(&format!("Processing was canceled after reading {} of {} file(s). The {} file(s) that could be used were written to the output.", report.files.len(), input_paths.len(), report.files_parsed()));
+        } else {
+            gui.integrated_dialog_message(&format!("Processing was canceled after reading {} of {} file(s), and no output was written.", report.files.len(), input_paths.len()));
+        }//end else the output couldn't be written
+    }//end if the user canceled processing
+    else if report.succeeded() {
+        logging::log_info(&format!("Finished processing {} of {} file(s) into {}.", report.files_parsed(), input_paths.len(), report.output_path.to_string_lossy()));
+        let total_duration = start.elapsed();
+        let mut summary = export_report.summary_message(report.duplicates_renamed, config);
+        if export_report.issues.len() > 0 {summary.push_str(&format!("\n{} warning(s) were added to the Log tab.", export_report.issues.len()));}
+        let completed = format!("Processing has completed successfully in {} miliseconds.\n{}", format_milliseconds(total_duration), summary);
+        if !config.prompt_open_output {
+            gui.integrated_dialog_message(&completed);
+            return true;
+        }//end if the user doesn't want to be asked about opening the output
+        let choice = gui.integrated_dialog_message_choice(&format!("{}\n\nWould you like to open the output file, or the folder it's in?", completed), vec!["Open File", "Open Folder", "Close"]);
+        let opened = match choice {
+            Some(0) => opener::open(&report.output_path),
+            Some(1) => opener::reveal(&report.output_path),
+            _ => Ok(()),
+        };//end matching what the user wants to open
+        if let Err(e) = opened {
+            logging::log_warn(&format!("Couldn't open output due to {}", e));
+            gui.integrated_dialog_alert(&format!("The output was saved, but it couldn't be opened:\n{}", e));
+        }//end if the output couldn't be opened
+    }//end if output file seems to be created ok
+    report.succeeded()
+}//end run_and_report()
+
 /// Given a duration, gives a string of a float representation of the number
 /// of milliseconds. If the parse fails, it will return the whole
 /// number of milliseconds as a string.
@@ -133,121 +536,168 @@ fn format_milliseconds(duration: Duration) -> String {
 	}//end matching whether we can parse float-micros
 }//end format_milliseconds(duration)
 
-/// Returns true if the input paths are more than 0 and valid for processing.  
-/// If invalid, shows dialog message about issue.
-fn validate_input_paths(input_paths: &Vec<PathBuf>, gui: &mut GUI) -> bool {
-    if input_paths.len() > 0 {true}
-    else {
-        gui.integrated_dialog_alert("There are no input files selected. Please select one before processing.");
-        false
-    }
+/// If reading the file at path runs into a problem with a specific line, shows the
+/// first one along with the lines around it, so it's easier to see what's wrong.
+fn show_line_error(gui: &mut GUI, path: &PathBuf, config: &ConfigStore) {
+    let Ok(file_bytes) = std::fs::read(path) else {return;};
+    let (file_contents, _) = data::read_file_bytes_to_string(&file_bytes);
+    let filename = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let line_err = match data::read_data_from_file(&filename, &file_contents, config) {
+        Err(err) => Some(err).filter(|err| err.line().is_some()),
+        Ok((_, errs)) => errs.into_iter().find(|err| err.line().is_some()),
+    };//end matching whether the file could be read
+    if let Some(err) = line_err {gui.show_parse_error(&file_contents, &err);}
+}//end show_line_error()
+
+/// Returns the input paths to process, if there are any.  
+/// If no input paths are selected, config on_empty_input decides whether to show
+/// a dialog message about it, reuse last_run_inputs, or read the inputs from a manifest.
+fn validate_input_paths(input_paths: Vec<PathBuf>, config: &ConfigStore, last_run_inputs: &Vec<PathBuf>, gui: &mut GUI) -> Option<Vec<PathBuf>> {
+    if input_paths.len() > 0 {return Some(input_paths);}
+    match &config.on_empty_input {
+        EmptyInputAction::Error => {
+            gui.integrated_dialog_alert("There are no input files selected. Please select one before processing.");
+            None
+        },
+        EmptyInputAction::LastFiles => {
+            if last_run_inputs.len() > 0 {Some(last_run_inputs.clone())}
+            else {
+                gui.integrated_dialog_alert("There are no input files selected, and no files have been processed yet to use again. Please select one before processing.");
+                None
+            }//end else there are no last files to use
+        },
+        EmptyInputAction::Manifest(manifest_path) => match process::read_manifest(manifest_path) {
+            Ok(manifest_inputs) => Some(manifest_inputs),
+            Err(msg) => {
+                gui.integrated_dialog_alert(&format!("There are no input files selected, and the manifest couldn't be used:\n{}", msg));
+                None
+            },
+        },
+    }//end matching what to do without input files
 }//end validate_input_paths()
 
-/// Returns true if the output_path given is valid for processing.  
+/// Returns the output path and format to use if the output_path given is valid for processing.  
+/// If the output file name has a recognized extension, the format is switched to match it,
+/// otherwise the extension for config output_format is added. If the name ends in an extension
+/// we can't write, the user is shown the supported ones and asked before adding to it. Typed names are saved
+/// into config default_output_dir, or the folder of the first input if that isn't set.  
+/// The last value returned is true if the user chose to append to a workbook in written_workbooks.  
 /// If invalid, shows dialog message about issue.
-fn validate_output_path(output_path: Option<PathBuf>, gui: &mut GUI) -> Result<PathBuf,()> {
+fn validate_output_path(output_path: Option<PathBuf>, input_paths: &Vec<PathBuf>, config: &ConfigStore, written_workbooks: &HashMap<PathBuf, Vec<process::SheetRecord>>, gui: &mut GUI) -> Result<(PathBuf,OutputFormat,bool),()> {
     let output_txt = gui.get_output_path_text();
     if output_txt.len() == 0 {
         gui.integrated_dialog_alert("No output path selected. Please select one before processing.");
         return Err(());
     }//end if no selected file OR user deleted selection
     else if output_path.is_some() {
-        return Ok(output_path.expect("Already checked that output_path is_some()"));
+        let output_path = output_path.expect("Already checked that output_path is_some()");
+        if !confirm_unsupported_extension(&output_path, config.output_format, gui) {return Err(());}
+        let (resolved_path, resolved_format) = process::resolve_output_path(output_path.clone(), config.output_format);
+        // the file dialog already asked about overwriting the path it gave us, but not about appending
+        let can_append = resolved_format == OutputFormat::Xlsx && written_workbooks.contains_key(&resolved_path);
+        if resolved_path == output_path && !can_append {return Ok((resolved_path, resolved_format, false));}
+        match confirm_existing_output(&resolved_path, can_append, gui) {
+            Some(append) => return Ok((resolved_path, resolved_format, append)),
+            None => return Err(()),
+        }//end matching what to do about an existing output file
     }//end else case that both txt and path are valid, all seems good
     else {
-        let input_paths = gui.get_last_input_paths();
-        let input_dir = match input_paths.first() {
-            Some(first_input_path) => match first_input_path.parent() {
-                Some(parent_path) => parent_path.to_string_lossy().to_string(),
-                None => "".to_string(),
+        match process::get_output_dir(config, input_paths) {
+            Some(output_dir) => {
+                let output_pathbuf = output_dir.join(output_txt);
+                if !confirm_unsupported_extension(&output_pathbuf, config.output_format, gui) {return Err(());}
+                let (output_pathbuf, output_format) = process::resolve_output_path(output_pathbuf, config.output_format);
+                let can_append = output_format == OutputFormat::Xlsx && written_workbooks.contains_key(&output_pathbuf);
+                match confirm_existing_output(&output_pathbuf, can_append, gui) {
+                    Some(append) => return Ok((output_pathbuf, output_format, append)),
+                    None => return Err(()),
+                }//end matching what to do about an existing output file
             },
-            None => "".to_string(),
-        };
-        if input_dir != "" {
-            let mut output_pathbuf = PathBuf::new();
-            output_pathbuf.push(input_dir);
-            output_pathbuf.push(output_txt);
-            output_pathbuf.set_extension("xlsx");
-            if !output_pathbuf.exists() || gui.integrated_dialog_yes_no("The output file you specified already exists. Are you sure you want to overwrite it?") {
-                return Ok(output_pathbuf);
-            } else {return Err(());}
-        } else {
-            gui.integrated_dialog_alert("Couldn't use input paths to determine output path for typed name. Please select valid input files.");
-            return Err(());
-        }//end else we couldn't figure out input dir
+            None => {
+                gui.integrated_dialog_alert("Couldn't use input paths to determine output path for typed name. Please select valid input files.");
+                return Err(());
+            },
+        }//end matching whether we could figure out the output dir
     }//end else case that txt is valid, but path is not, must generate path
 }//end validate_output_path()
 
+/// If output_path ends in an extension that isn't a supported output format, tells the user
+/// which extensions are supported and asks whether to add the extension for format instead.  
+/// Returns true if the path can be used, either because its extension is fine or the user agreed.
+fn confirm_unsupported_extension(output_path: &PathBuf, format: OutputFormat, gui: &mut GUI) -> bool {
+    match process::get_unsupported_extension(output_path) {
+        None => true,
+        Some(ext) => {
+            let file_name = output_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            gui.integrated_dialog_yes_no(&format!("The output file name \"{}\" ends in \".{}\", which isn't a supported output type. The supported extensions are {}.\n\nWould you like to save it as \"{}.{}\" instead? Choose no to change the name yourself.", file_name, ext, OutputFormat::supported_extensions(), file_name, format.extension()))
+        },
+    }//end matching whether the extension is one we can't write
+}//end confirm_unsupported_extension()
+
+/// If output_path already exists, asks the user whether to overwrite it, or if can_append,
+/// whether to overwrite it or append new sheets to it.  
+/// Returns Some(true) to append, Some(false) to overwrite or if the file doesn't exist,
+/// or None if the user doesn't want to use the file.
+fn confirm_existing_output(output_path: &PathBuf, can_append: bool, gui: &mut GUI) -> Option<bool> {
+    if !output_path.exists() {return Some(false);}
+    if !can_append {
+        return match gui.integrated_dialog_yes_no("The output file you specified already exists. Are you sure you want to overwrite it?") {
+            true => Some(false),
+            false => None,
+        };
+    }//end if we can only overwrite the file
+    match gui.integrated_dialog_message_choice("The output file you specified already exists, and was written earlier in this session. Would you like to overwrite it, or append the new sheets to it?\n\nAppending writes the workbook again with the sheets from earlier, so any changes made to it outside this program will be lost.", vec!["Overwrite", "Append", "Cancel"]) {
+        Some(0) => Some(false),
+        Some(1) => Some(true),
+        _ => None,
+    }//end matching the user's choice
+}//end confirm_existing_output()
+
+/// Fills in the output box with the default output name from the config, if there is one.
+fn apply_output_defaults(gui: &mut GUI, config: &ConfigStore) {
+    if let Some(output_name) = process::get_default_output_name(config, time::OffsetDateTime::now_utc()) {
+        gui.set_output_path_text(&output_name);
+    }//end if the config has a default output name
+}//end apply_output_defaults()
+
 /// Gets the config information from the config file.
 /// If we encounter issues with that, lets the user know through the gui.
 fn ensure_config_valid(
     gui: &mut GUI,
-    config_store: &mut ConfigStore,
+    config_profiles: &mut ConfigProfiles,
     config_path: &mut Option<PathBuf>,
     config_name: &str
 ) {
-    *config_store = ConfigStore::default();
+    *config_profiles = ConfigProfiles::default();
     *config_path = None;
 
-    match config_store::try_read_config_path(config_name, false) {
+    // a missing config file is created here, in the program's folder or the current folder
+    match config_store::try_read_config_path(config_name, true) {
         Ok(config_path_tmp) => {
-            if !config_path_tmp.exists() {
-                match config_store::try_write_config(&config_path_tmp, &config_store) {
-                    Ok(_) => {
-                        *config_path = Some(config_path_tmp);
-                    },
-                    Err(msg) => gui.integrated_dialog_alert(&format!("I couldn't find an exisitng configuration file, so I tried creating one, but that also failed...\nYou can use the default config, but it won't be saved when you exit.\nIf you contine seeing this message, please contact the developer. Error message below:\n{}", msg)),
-                }//end matching whether we can write the default config
-            }//end if the config file does not already exist
-            else {
-                match config_store::try_read_config(&config_path_tmp) {
-                    Ok(config_store_tmp) => *config_store = config_store_tmp,
-                    Err(msg) => {
-                        gui.integrated_dialog_alert(&format!("I found a config file, but I couldn't read it. Things like this can happen during version changes or if the file is edited incorrectly. I'm going to go ahead and create a new file with the default settings for you. Here's the error message:\n{}",msg));
-                        match config_store::try_write_config(&config_path_tmp, config_store) {
-                            Ok(_) => {},
-                            Err(msg) => gui.integrated_dialog_alert(&format!("Ok, so I tried writing a new config file, but I wasn't able to. Was it open? Either way, if you keep seeing messages like this, please contact the developer. You can still use the program with the default config and even edit the settings while you use it, but I can't keep track of those changes after you close the program. Error message below:\n{}", msg)),
-                        }
-                    },
-                }//end matching whether or not we can read from the config file we have
-                *config_path = Some(config_path_tmp);
-            }//end else we do have a config file to read
+            if config_store::is_fallback_config_path(&config_path_tmp, config_name) {
+                gui.set_status(&format!("Settings are saved in {}, since the program's folder couldn't be used", config_path_tmp.to_string_lossy()));
+            }//end if the config file isn't in the usual place
+            match config_store::try_read_config(&config_path_tmp) {
+                Ok(config_profiles_tmp) => *config_profiles = config_profiles_tmp,
+                Err(ConfigReadError::Invalid{config_profiles: config_profiles_tmp, problems}) => {
+                    let choice = gui.integrated_dialog_message_choice(&format!("The config file has settings that can't be used:\n{}\n\nYou can keep these settings and fix them here, use the default settings instead, which will replace the file when you close the program, or close the program now to fix the file yourself.", problems.join("\n")), vec!["Keep", "Use Defaults", "Close"]);
+                    match choice {
+                        Some(0) => *config_profiles = config_profiles_tmp,
+                        Some(1) => {},
+                        _ => std::process::exit(0),
+                    }//end matching what the user wants to do about the problems
+                },
+                Err(ConfigReadError::Unreadable(msg)) => {
+                    gui.integrated_dialog_alert(&format!("I found a config file, but I couldn't read it, since it isn't valid json. Things like this can happen if the file is edited incorrectly. I'm going to go ahead and create a new file with the default settings for you. Here's the error message:\n{}",msg));
+                    match config_store::try_write_config(&config_path_tmp, config_profiles) {
+                        Ok(_) => {},
+                        Err(msg) => gui.integrated_dialog_alert(&format!("Ok, so I tried writing a new config file, but I wasn't able to. Was it open? Either way, if you keep seeing messages like this, please contact the developer. You can still use the program with the default config and even edit the settings while you use it, but I can't keep track of those changes after you close the program. Error message below:\n{}", msg)),
+                    }
+                },
+            }//end matching whether or not we can read from the config file we have
+            *config_path = Some(config_path_tmp);
         },
-        Err(msg) => gui.integrated_dialog_alert(&format!("Could not determine the path to a config file:\n{}", msg)),
+        Err(msg) => gui.integrated_dialog_alert(&format!("I couldn't find an existing configuration file, or create one next to the program or in the current folder.\nYou can use the default config, but it won't be saved when you exit.\nIf you continue seeing this message, please contact the developer. Error message below:\n{}", msg)),
     }//end matching whether or not we can get config path
 }//end ensure_config_valid()
 
-/// Appends error information to the error log
-/// # Parameters
-/// - log_name : the name of the log file, including the file extension
-/// - error_context : some text to give context about where the error came from, such as config reading, data reading, etc.
-/// - error_msgs : a collection of error messages to print out below the context
-fn append_error_log(log_name: &str, error_context: &str, error_msgs: Iter<&str>) -> Result<(),String> {
-	match std::fs::OpenOptions::new()
-		.write(true)
-		.append(true)
-		.open(log_name)
-	{
-		Err(msg) => return Err(format!("Encountered error when trying to create a file called {log_name}:\n{msg}")),
-		Ok(mut file) => {
-			let now = time::OffsetDateTime::now_utc();
-			file.write(b"\n").unwrap_or_else(|e| {println!("Error {e} prevented writing to log file."); 0});
-			file.write(format!("{}-{}-{},{}:{}:{}:{}\t",
-				now.date().year(),
-				now.date().month(),
-				now.date().day(),
-				now.time().hour(),
-				now.time().minute(),
-				now.time().second(),
-				now.time().millisecond(),
-			).as_bytes()).unwrap_or_else(|e| {println!("Error {e} prevented writing to log file."); 0});
-			file.write(error_context.as_bytes()).unwrap_or_else(|e| {println!("Error {e} prevented writing to log file."); 0});
-			file.write(b"\n").unwrap_or_else(|e| {println!("Error {e} prevented writing to log file."); 0});
-			for error_msg in error_msgs {
-				file.write(error_msg.as_bytes()).unwrap_or_else(|e| {println!("Error {e} prevented writing to log file."); 0});
-				file.write(b"\n").unwrap_or_else(|e| {println!("Error {e} prevented writing to log file."); 0});
-			}//end looping over each error message
-		},
-	}//end matching whether the file can be created
-	Ok(())
-}//end append_error_log()
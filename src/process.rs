@@ -1,6 +1,7 @@
-use std::path::PathBuf;
-use rust_xlsxwriter::{Format, FormatAlign, FormatBorder, Workbook, XlsxError};
-use crate::data::Data;
+use std::{fs::{self, File}, io::{self, Write}, path::{Path, PathBuf}};
+use rust_xlsxwriter::{utility, Chart, ChartType, Color, Format, FormatAlign, FormatBorder, Formula, Workbook, Worksheet, XlsxError};
+use time::OffsetDateTime;
+use crate::{config_store::{ConfigStore, FlagStyle, NonFiniteFormat, OutputFormat, OutputLayout, SummaryStat, MAX_DECIMAL_PLACES}, data::{self, Data, HeaderSummary}, logging};
 
 /// The border style to use for all the cells we write to.
 const BORDER_FORMAT: FormatBorder = FormatBorder::Thin;
@@ -10,9 +11,1212 @@ const FONT_SIZE_HEADER: i32 = 14;
 const FONT_SIZE_TEST_NAME: i32 = 11;
 /// The font size for cells with numeric data in them.
 const FONT_SIZE_DATA: i32 = 11;
+/// The maximum number of characters excel allows in a sheet name.
+const SHEET_NAME_MAX_LEN: usize = 31;
+/// The header of the column holding each row's source file, if it's written.
+const SOURCE_FILE_HEADER: &str = "Source File";
+/// The name of the sheet holding summary statistics, if it's written.
+const SUMMARY_SHEET_NAME: &str = "Summary";
+/// The name of the sheet with metadata about the export, if config include_metadata_sheet is set.
+const METADATA_SHEET_NAME: &str = "Metadata";
 /// The row upon which the header starts.
 /// This acts as a vertical offset for the whole output.
 const HEADER_START_ROW: u32 = 2;
+/// The narrowest a column is made when fitting it to its contents, in characters.
+const MIN_COLUMN_WIDTH: f64 = 8.43;
+/// The widest a column is made when fitting it to its contents, in characters.
+const MAX_COLUMN_WIDTH: f64 = 50.;
+/// The extra width added to each column, in characters, so the contents
+/// don't run into the borders or the autofilter button.
+const COLUMN_WIDTH_PADDING: f64 = 2.;
+
+/// Describes where and how run() should write its output.
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct OutputSpec {
+    /// The path of the output file to create or overwrite.
+    pub path: PathBuf,
+    /// The name of the sheet to write data into.
+    pub sheet_name: String,
+    /// The name of the config profile used, for the metadata sheet, if known.
+    pub profile_name: Option<String>,
+    /// Sheets written to the output earlier, such as from RunReport sheets, which are
+    /// written again before the new ones so that the new sheets are appended to them.
+    /// New sheets are renamed if needed so they don't clash. If empty, the workbook starts out empty.
+    pub append_to: Vec<SheetRecord>,
+}//end struct OutputSpec
+
+impl OutputSpec {
+    /// Creates a new OutputSpec with the given path and sheet name.
+    pub fn new(path: PathBuf, sheet_name: &str) -> OutputSpec {
+        OutputSpec { path, sheet_name: sheet_name.to_string(), profile_name: None, append_to: Vec::new() }
+    }//end new()
+}//end impl OutputSpec
+
+/// Where the values of a sheet in the wide layout were written, so that
+/// formulas elsewhere in the workbook can refer to them.
+#[derive(Clone,PartialEq,Eq,Debug,Default)]
+pub struct SheetRange {
+    /// The name of the sheet the values are on.
+    pub sheet_name: String,
+    /// The first row holding a file's values, 0-based.
+    pub first_row: u32,
+    /// The last row holding a file's values, 0-based.
+    pub last_row: u32,
+    /// Each header written to the sheet, with the column its values are in.
+    pub header_cols: Vec<(String, u16)>,
+}//end struct SheetRange
+
+impl SheetRange {
+    /// Gets an A1 style reference to the cells holding the values of header,
+    /// like 'Sheet 1'!B4:B40, or None if the header isn't on this sheet.
+    pub fn get_header_ref(&self, header: &str) -> Option<String> {
+        let (_, col) = self.header_cols.iter().find(|(col_header,_)| col_header == header)?;
+        Some(format!("'{}'!{}", self.sheet_name.replace('\'', "''"), utility::cell_range(self.first_row, *col, self.last_row, *col)))
+    }//end get_header_ref()
+}//end impl SheetRange
+
+/// A sheet written to an xlsx workbook, kept so the workbook can be written again with
+/// more sheets added, since rust_xlsxwriter can only create files and can't read them back.
+#[derive(Clone,PartialEq,Debug)]
+pub struct SheetRecord {
+    /// The name the sheet was written with.
+    pub sheet_name: String,
+    /// What was written to the sheet.
+    pub contents: SheetContents,
+    /// The config the sheet was written with.
+    pub config: ConfigStore,
+}//end struct SheetRecord
+
+/// The kinds of sheet kept in a SheetRecord, with what's needed to write each again.
+#[derive(Clone,PartialEq,Debug)]
+pub enum SheetContents {
+    /// A sheet of data, written with write_sheet_for_layout().
+    Data(Vec<Data>),
+    /// A summary sheet, written with write_summary_sheet(), along with the
+    /// ranges its formulas refer to, if any.
+    Summary(Vec<HeaderSummary>, Vec<SheetRange>),
+    /// A metadata sheet, written with write_metadata_sheet(), holding the
+    /// pairs from get_metadata() so the time written stays the same.
+    Metadata(Vec<(String,String)>),
+}//end enum SheetContents
+
+/// The outcome of reading and parsing a single input file.
+#[derive(Clone,PartialEq,Debug)]
+pub struct FileOutcome {
+    /// The path of the input file.
+    pub path: PathBuf,
+    /// If the file was parsed, holds any non-fatal issues found while parsing.  
+    /// If the file couldn't be read or parsed, holds a message describing why.
+    pub result: Result<Vec<String>,String>,
+}//end struct FileOutcome
+
+/// Summarizes everything that happened during a call to run().
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct RunReport {
+    /// The path output was written to, or would have been written to.
+    pub output_path: PathBuf,
+    /// The outcome of each input file, in the order they were given.
+    pub files: Vec<FileOutcome>,
+    /// Any issues not tied to a single input file.
+    pub warnings: Vec<String>,
+    /// If output couldn't be written, holds a message describing why.
+    pub write_error: Option<String>,
+    /// True if the run was stopped early, in which case files only holds
+    /// the input files read before stopping.
+    pub canceled: bool,
+    /// A message for each value outside its range in config value_ranges.
+    /// These are highlighted in xlsx output rather than being treated as issues.
+    pub out_of_range: Vec<String>,
+    /// Every sheet in the xlsx workbook written, including any appended to,
+    /// so that more can be appended later. Empty unless an xlsx file was written.
+    pub sheets: Vec<SheetRecord>,
+    /// The number of rows of data written, which is one per test, or
+    /// just one if config average_all_inputs is set.
+    pub rows_written: usize,
+    /// The number of values left out by config skip_zero_rows.
+    pub zero_values_skipped: usize,
+    /// Each test name used by more than one input, with how many inputs use it.
+    pub duplicates: Vec<(String,usize)>,
+    /// True if the repeated test names in duplicates were numbered before writing.
+    pub duplicates_renamed: bool,
+}//end struct RunReport
+
+impl RunReport {
+    /// Returns true if output was written successfully.
+    pub fn succeeded(&self) -> bool {self.write_error.is_none()}
+
+    /// Returns the number of input files that were parsed and included in the output.
+    pub fn files_parsed(&self) -> usize {
+        self.files.iter().filter(|f| f.result.is_ok()).count()
+    }//end files_parsed()
+
+    /// Gets a message for every issue in the report, fatal or not, each
+    /// labelled with the file it came from when relevant.
+    pub fn issue_messages(&self) -> Vec<String> {
+        let mut msgs = Vec::new();
+        for file in self.files.iter() {
+            match &file.result {
+                Err(msg) => msgs.push(format!("Couldn't use file \"{}\":\n{}", file.path.to_string_lossy(), msg)),
+                Ok(errs) => for err in errs {
+                    msgs.push(format!("Issue in file \"{}\":\n{}", file.path.to_string_lossy(), err));
+                },
+            }//end matching whether file could be read
+        }//end adding messages for each file
+        msgs.extend(self.warnings.iter().cloned());
+        msgs
+    }//end issue_messages()
+
+    /// Converts this report into an ExportReport, for a run that wrote its output.
+    pub fn into_export_report(self) -> ExportReport {
+        let files_processed = self.files_parsed();
+        let skipped = self.files.iter()
+            .filter_map(|file| file.result.as_ref().err().map(|msg| format!("Couldn't use \"{}\":\n{}", file.path.to_string_lossy(), msg)))
+            .collect();
+        let issues = self.files.iter()
+            .filter_map(|file| file.result.as_ref().ok().map(|errs| (file, errs)))
+            .flat_map(|(file, errs)| errs.iter().map(move |err| format!("Issue in \"{}\":\n{}", file.path.to_string_lossy(), err)))
+            .chain(self.warnings.iter().cloned())
+            .collect();
+        ExportReport {
+            output_path: self.output_path,
+            files_processed,
+            rows_written: self.rows_written,
+            skipped,
+            issues,
+            duplicates: self.duplicates,
+            out_of_range: self.out_of_range,
+            zero_values_skipped: self.zero_values_skipped,
+        }
+    }//end into_export_report()
+}//end impl RunReport
+
+/// Summarizes a call to run_export() that wrote its output.
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct ExportReport {
+    /// The path output was written to, with the extension for its format.
+    pub output_path: PathBuf,
+    /// The number of inputs that were parsed and included in the output.
+    pub files_processed: usize,
+    /// The number of rows of data written, which is one per test, or
+    /// just one if config average_all_inputs is set.
+    pub rows_written: usize,
+    /// A message for each input that couldn't be used, labelled with its name.
+    pub skipped: Vec<String>,
+    /// A message for each non-fatal issue found in the inputs that were used.
+    pub issues: Vec<String>,
+    /// Each test name used by more than one input, with how many inputs use it.
+    pub duplicates: Vec<(String,usize)>,
+    /// A message for each value outside its range in config value_ranges.
+    pub out_of_range: Vec<String>,
+    /// The number of values left out by config skip_zero_rows.
+    pub zero_values_skipped: usize,
+}//end struct ExportReport
+
+impl ExportReport {
+    /// Gets a short description of what was done, like "Processed 12 file(s), wrote 12 row(s)
+    /// to report.xlsx (2 duplicate(s) renamed)." The counts of skipped zero values and values
+    /// out of range are only mentioned if config skip_zero_rows or value_ranges are in use.  
+    /// duplicates_renamed should be true if the repeated test names in duplicates were numbered.
+    pub fn summary_message(&self, duplicates_renamed: bool, config: &ConfigStore) -> String {
+        let file_name = self.output_path.file_name().unwrap_or(self.output_path.as_os_str()).to_string_lossy();
+        let mut message = format!("Processed {} file(s), wrote {} row(s) to {}", self.files_processed, self.rows_written, file_name);
+        let mut details = Vec::new();
+        // the first file with a repeated name keeps it, so only the rest are renamed
+        let repeats: usize = self.duplicates.iter().map(|(_, count)| count.saturating_sub(1)).sum();
+        if repeats > 0 {
+            details.push(if duplicates_renamed {format!("{} duplicate(s) renamed", repeats)} else {format!("{} duplicate test name(s) kept", repeats)});
+        }//end if any test names were repeated
+        if config.skip_zero_rows {details.push(format!("{} zero value(s) skipped", self.zero_values_skipped));}
+        if !config.value_ranges.is_empty() {details.push(format!("{} value(s) out of range", self.out_of_range.len()));}
+        if !details.is_empty() {message += &format!(" ({})", details.join(", "));}
+        message + "."
+    }//end summary_message()
+}//end impl ExportReport
+
+/// The ways run_export() can fail to write any output.
+#[derive(Clone,PartialEq,Debug)]
+pub enum ExportError {
+    /// The config has settings that can't be used, from ConfigStore::validate().
+    InvalidConfig(Vec<String>),
+    /// None of the inputs could be parsed. Holds a message for each input, labelled with its name.
+    NoUsableInputs(Vec<String>),
+    /// The output couldn't be written. Holds a message describing why.
+    Write(String),
+}//end enum ExportError
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::InvalidConfig(problems) => write!(f, "The config has settings that can't be used:\n{}", problems.join("\n")),
+            ExportError::NoUsableInputs(msgs) if msgs.is_empty() => write!(f, "There were no inputs to export."),
+            ExportError::NoUsableInputs(msgs) => write!(f, "None of the inputs could be used:\n{}", msgs.join("\n")),
+            ExportError::Write(msg) => write!(f, "{}", msg),
+        }//end matching self
+    }//end fmt()
+}//end impl Display for ExportError
+
+impl std::error::Error for ExportError {}
+
+/// Gets the list of input files from the contents of a manifest file, which
+/// has one path per line. Blank lines and lines starting with '#' are skipped,
+/// and relative paths are taken as relative to base_dir.
+pub fn parse_manifest(contents: &str, base_dir: &Path) -> Vec<PathBuf> {
+    contents.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base_dir.join(line))
+        .collect()
+}//end parse_manifest()
+
+/// Reads the manifest file at the given path, giving back the list of input
+/// files in it. See process::parse_manifest() for the format.
+pub fn read_manifest(manifest_path: &Path) -> Result<Vec<PathBuf>,String> {
+    let contents = fs::read_to_string(manifest_path)
+        .map_err(|e| format!("Couldn't read the manifest file \"{}\":\n{}", manifest_path.to_string_lossy(), e))?;
+    let base_dir = manifest_path.parent().unwrap_or(Path::new(""));
+    let inputs = parse_manifest(&contents, base_dir);
+    if inputs.is_empty() {return Err(format!("The manifest file \"{}\" doesn't list any input files.", manifest_path.to_string_lossy()));}
+    Ok(inputs)
+}//end read_manifest()
+
+/// Finds every .txt file in the folder dir, giving back their paths sorted by name.  
+/// If recursive, files in subfolders, and their subfolders, are included as well.
+pub fn find_input_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>,String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Couldn't read the folder \"{}\":\n{}", dir.to_string_lossy(), e))?;
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Couldn't read the contents of the folder \"{}\":\n{}", dir.to_string_lossy(), e))?
+            .path();
+        if path.is_dir() {
+            if recursive {inputs.extend(find_input_files(&path, recursive)?);}
+        }//end if this is a subfolder
+        else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("txt")) {
+            inputs.push(path);
+        }//end else if this is a txt file
+    }//end looking at each thing in the folder
+    inputs.sort();
+    Ok(inputs)
+}//end find_input_files()
+
+/// Parses the text given by a drag and drop of files into the paths of any
+/// .txt files in it. Each path is on its own line, and may be given as a
+/// file:// url, as some file managers do. Anything else is left out.
+pub fn parse_dropped_paths(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.strip_prefix("file://") {
+            Some(url_path) => {
+                let url_path = decode_url_escapes(url_path);
+                // windows urls look like file:///C:/folder, which shouldn't keep the first slash
+                match url_path.strip_prefix('/') {
+                    Some(drive_path) if drive_path.get(1..2) == Some(":") => PathBuf::from(drive_path),
+                    _ => PathBuf::from(url_path),
+                }//end matching whether the url starts with a drive letter
+            },
+            None => PathBuf::from(line),
+        })
+        .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("txt")))
+        .collect()
+}//end parse_dropped_paths()
+
+/// Replaces %XX escapes in a url path, such as %20, with the characters they stand for.
+/// Escapes that aren't valid are left as they are.
+fn decode_url_escapes(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = if bytes[i] == b'%' && i + 2 < bytes.len() {
+            std::str::from_utf8(&bytes[i+1..i+3]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {None};
+        match escaped {
+            Some(byte) => {decoded.push(byte); i += 3;},
+            None => {decoded.push(bytes[i]); i += 1;},
+        }//end matching whether this is a valid escape
+    }//end looping over each byte
+    String::from_utf8_lossy(&decoded).into_owned()
+}//end decode_url_escapes()
+
+/// Reads and parses every input file, using the given config.  
+/// Files which can't be read or parsed are skipped, but still show up in
+/// the returned list of outcomes, which is in the same order as inputs.
+pub fn parse_inputs(inputs: &[PathBuf], config: &ConfigStore) -> (Vec<Data>, Vec<FileOutcome>) {
+    let parsed = parse_inputs_with_progress(inputs, config, &mut |_,_| true);
+    (parsed.data_files, parsed.files)
+}//end parse_inputs()
+
+/// Reads and parses a single input file, using the given config.  
+/// Gives back the data, if it could be read, along with the outcome for the file.
+fn parse_input(input_path: &PathBuf, config: &ConfigStore) -> (Option<Data>, FileOutcome) {
+    let filename = match input_path.file_name() {
+        Some(osstr) => osstr.to_string_lossy().into_owned(),
+        None => "UNKNOWN FILENAME".to_string(),
+    };
+    let (data, result) = match fs::read(input_path) {
+        Err(msg) => {
+            logging::log_error(&format!("Couldn't read {}: {}", input_path.to_string_lossy(), msg));
+            (None, Err(format!("There was an error reading from the file:\n{}", msg)))
+        },
+        Ok(file_bytes) => {
+            let (file_contents, encoding) = data::read_file_bytes_to_string(&file_bytes);
+            logging::log_debug(&format!("Read {} bytes from {} as {}", file_bytes.len(), input_path.to_string_lossy(), encoding));
+            parse_contents(&filename, &file_contents, encoding, config)
+        },
+    };
+    (data, FileOutcome { path: input_path.clone(), result })
+}//end parse_input()
+
+/// Parses the contents of a single input file, which were read as the given encoding.  
+/// Gives back the data, if it could be parsed, along with the result for a FileOutcome.
+fn parse_contents(filename: &str, file_contents: &str, encoding: &str, config: &ConfigStore) -> (Option<Data>, Result<Vec<String>,String>) {
+    match data::read_data_from_file(filename, file_contents, config) {
+        Err(err) => {
+            logging::log_warn(&format!("Couldn't parse {}: {}", filename, err));
+            match encoding {
+                "UTF-8" => (None, Err(err.to_string())),
+                _ => (None, Err(format!("{}\n(The file isn't UTF-8, so it was read as {}.)", err, encoding))),
+            }//end matching whether to mention the encoding
+        },
+        Ok((file_data,errs)) => {
+            logging::log_info(&format!("Parsed {}: test \"{}\" with {} row(s) and {} issue(s)", filename, file_data.test_name, file_data.row_data.len(), errs.len()));
+            for err in errs.iter() {logging::log_debug(&format!("Issue in {}: {}", filename, err));}
+            let mut msgs: Vec<String> = errs.iter().map(|err| err.to_string()).collect();
+            // let the user know whenever we had to assume something other than utf-8
+            if encoding != "UTF-8" {msgs.push(format!("The file isn't UTF-8, so it was read as {}. If any text looks wrong, check the file's encoding.", encoding));}
+            (Some(file_data), Ok(msgs))
+        },
+    }//end matching whether the data could be read
+}//end parse_contents()
+
+/// Returns the extension of path, without a dot, if it looks like a file type
+/// extension but isn't one of the output formats, such as "ods" or "xls".  
+/// Only endings made entirely of letters count, so that a name like "run.v2"
+/// is still treated as part of the name, as in resolve_output_path().
+pub fn get_unsupported_extension(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_string_lossy().to_string();
+    if ext.is_empty() || !ext.chars().all(|c| c.is_ascii_alphabetic()) {return None;}
+    match OutputFormat::from_extension(&ext) {
+        Some(_) => None,
+        None => Some(ext),
+    }//end matching whether we recognize the extension
+}//end get_unsupported_extension()
+
+/// Works out the final output path and format from a path chosen or typed by the user.  
+/// If the file name already ends in a recognized extension, such as ".csv", it's kept
+/// and the format is switched to match. Otherwise, the extension for the given format
+/// is added on, so that a name like "run.v2" becomes "run.v2.xlsx" rather than losing ".v2".
+pub fn resolve_output_path(path: PathBuf, format: OutputFormat) -> (PathBuf, OutputFormat) {
+    let existing_format = path.extension()
+        .and_then(|ext| OutputFormat::from_extension(&ext.to_string_lossy()));
+    match existing_format {
+        Some(existing_format) => (path, existing_format),
+        None => {
+            let mut file_name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+            file_name.push(".");
+            file_name.push(format.extension());
+            (path.with_file_name(file_name), format)
+        },
+    }//end matching whether the path already has an extension we know
+}//end resolve_output_path()
+
+/// Applies any settings which combine the data from separate files, such as
+/// config average_all_inputs, giving back the data that should be written out.
+pub fn combine_data(data_files: Vec<Data>, config: &ConfigStore) -> Vec<Data> {
+    if config.average_all_inputs {
+        let mut averaged = data::average_data(&get_summary_data(&data_files, config), &config.average_row_name);
+        averaged.row_data = data::sort_row_data(averaged.row_data, config);
+        vec![averaged]
+    } else {data_files}
+}//end combine_data()
+
+/// Gets the data that statistics such as averages should be taken from, which
+/// leaves out the rows skipped by config skip_zero_rows, if it's set.
+pub fn get_summary_data(data_files: &[Data], config: &ConfigStore) -> Vec<Data> {
+    if !config.skip_zero_rows {return data_files.to_vec();}
+    data_files.iter()
+        .map(|data_file| Data::new2(data_file.test_name.clone(), data_file.nonzero_rows(config.zero_epsilon).into_iter().cloned().collect(), data_file.source_file.clone()))
+        .collect()
+}//end get_summary_data()
+
+/// Splits the data into the sheets it should be written to, as pairs of sheet name
+/// and data. If config sheet_per_file is set, each Data gets its own sheet, named by
+/// get_file_sheet_names(). Otherwise, if config max_rows_per_sheet is set and the wide
+/// layout is used, each sheet gets at most that many rows, with sheets named like
+/// "name_1", "name_2", etc. Otherwise, all the data goes on a single sheet called sheet_name.
+pub fn split_into_sheets(sheet_name: &str, data: Vec<Data>, config: &ConfigStore) -> Vec<(String, Vec<Data>)> {
+    // the merged layout is always a single sheet, so it can be used for pivot tables
+    if config.output_layout == OutputLayout::Merged {return vec![(sheet_name.to_string(), data)];}
+    if config.sheet_per_file {
+        return get_file_sheet_names(&data).into_iter()
+            .zip(data.into_iter().map(|data_file| vec![data_file]))
+            .collect();
+    }//end if each file gets its own sheet
+    let max_rows = match (config.max_rows_per_sheet, config.output_layout) {
+        (Some(max_rows), OutputLayout::Wide) if max_rows > 0 && data.len() > max_rows => max_rows,
+        _ => return vec![(sheet_name.to_string(), data)],
+    };
+    data.chunks(max_rows)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let suffix = format!("_{}", idx + 1);
+            // leave room for the suffix within excel's limit on sheet name length
+            let base: String = sheet_name.chars().take(SHEET_NAME_MAX_LEN - suffix.len()).collect();
+            (format!("{}{}", base, suffix), chunk.to_vec())
+        })
+        .collect()
+}//end split_into_sheets()
+
+/// Gets a unique sheet name for each Data, in the same order, based on its
+/// test_name, or its source_file if the test name is empty.  
+/// Names are made safe with sanitize_sheet_name(). If a name is already taken,
+/// ignoring case like excel does, "Sheet" followed by the 1-based index of the
+/// Data is used instead, counting up from there until a free name is found.
+pub fn get_file_sheet_names(files: &[Data]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let is_taken = |names: &Vec<String>, name: &str| names.iter().any(|taken| taken.to_lowercase() == name.to_lowercase());
+    for (idx, data_file) in files.iter().enumerate() {
+        let base = match (data_file.test_name.trim(), &data_file.source_file) {
+            ("", Some(source_file)) => source_file.as_str(),
+            (test_name, _) => test_name,
+        };//end matching which name to base the sheet name on
+        let mut name = sanitize_sheet_name(base, idx + 1);
+        let mut fallback_index = idx + 1;
+        while is_taken(&names, &name) {
+            name = format!("Sheet{}", fallback_index);
+            fallback_index += 1;
+        }//end finding a name that isn't taken
+        names.push(name);
+    }//end naming each file's sheet
+    names
+}//end get_file_sheet_names()
+
+/// Writes each Data to its own sheet in the workbook, named by get_file_sheet_names(),
+/// using the layout from config.
+pub fn write_all_files(workbook: &mut Workbook, files: &[Data], config: &ConfigStore) -> Result<(),XlsxError> {
+    for (sheet_name, data_file) in get_file_sheet_names(files).iter().zip(files.iter()) {
+        write_sheet_for_layout(workbook, &vec![data_file.clone()], sheet_name, config)?;
+    }//end writing each file to a sheet
+    Ok(())
+}//end write_all_files()
+
+/// Runs the whole processing pipeline, reading and parsing each of the inputs,
+/// then writing the parsed data to the output. Problems along the way don't stop
+/// processing, but are instead recorded in the returned report.
+pub fn run(inputs: &[PathBuf], output: &OutputSpec, config: &ConfigStore) -> RunReport {
+    run_with_progress(inputs, output, config, &mut |_,_| true)
+}//end run()
+
+/// Works the same as process::run(), but calls on_progress with the number of steps
+/// done and the total number of steps as it goes. Parsing each input file is one step,
+/// and writing the output is the last.  
+/// If on_progress returns false after an input file, no more input files are read,
+/// and the output is written with just the files read so far.
+pub fn run_with_progress(inputs: &[PathBuf], output: &OutputSpec, config: &ConfigStore, on_progress: &mut dyn FnMut(usize,usize) -> bool) -> RunReport {
+    let parsed = parse_inputs_with_progress(inputs, config, on_progress);
+    export_parsed(parsed, output, config, &mut |_| false, on_progress)
+}//end run_with_progress()
+
+/// Decides whether to number the test names used by more than one input, given each
+/// repeated name and how many inputs use it. Used by process::export_parsed().
+pub type RenameDuplicates<'a> = dyn FnMut(&[(String,usize)]) -> bool + 'a;
+
+/// Writes parsed inputs to the output like write_parsed(), after looking for test names
+/// used by more than one input. If there are any, rename_duplicates is called with each
+/// repeated name and how many inputs use it, and if it returns true, the repeated names
+/// are numbered with data::disambiguate_test_names().  
+/// This is the step shared by run_with_progress(), run_export(), and the gui, so that
+/// they all write the same output from the same inputs.
+pub fn export_parsed(mut parsed: ParsedInputs, output: &OutputSpec, config: &ConfigStore, rename_duplicates: &mut RenameDuplicates, on_progress: &mut dyn FnMut(usize,usize) -> bool) -> RunReport {
+    let duplicates = data::find_duplicate_test_names(&parsed.data_files);
+    let duplicates_renamed = !duplicates.is_empty() && rename_duplicates(&duplicates);
+    if duplicates_renamed {data::disambiguate_test_names(&mut parsed.data_files);}
+    let report = write_parsed(parsed, output, config, on_progress);
+    RunReport { duplicates, duplicates_renamed, ..report }
+}//end export_parsed()
+
+/// The data read from the input files by parse_inputs_with_progress(),
+/// which can be looked over or changed before passing it on to write_parsed().
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct ParsedInputs {
+    /// The data from each input file that could be parsed, in the order they were given.
+    pub data_files: Vec<Data>,
+    /// The outcome of each input file that was read, in the order they were given.
+    pub files: Vec<FileOutcome>,
+    /// True if reading was stopped before every input file was read.
+    pub canceled: bool,
+    /// The number of steps in the whole run, for on_progress, including writing the output.
+    pub total_steps: usize,
+}//end struct ParsedInputs
+
+/// Reads and parses the input files as the first part of process::run_with_progress(),
+/// calling on_progress after each file, and stopping early if it returns false.
+pub fn parse_inputs_with_progress(inputs: &[PathBuf], config: &ConfigStore, on_progress: &mut dyn FnMut(usize,usize) -> bool) -> ParsedInputs {
+    let mut parsed = ParsedInputs { total_steps: inputs.len() + 1, ..ParsedInputs::default() };
+    for (idx, input_path) in inputs.iter().enumerate() {
+        let (data, outcome) = parse_input(input_path, config);
+        if let Some(data) = data {parsed.data_files.push(data);}
+        parsed.files.push(outcome);
+        if !on_progress(idx + 1, parsed.total_steps) && idx + 1 < inputs.len() {parsed.canceled = true; break;}
+    }//end parsing each input file
+    parsed
+}//end parse_inputs_with_progress()
+
+/// The outcome of checking the input files without writing any output, from validate_parsed().
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct ValidationSummary {
+    /// A line of text for each file, followed by any problems found across files
+    /// and a total, ready to show to the user.
+    pub lines: Vec<String>,
+    /// True if every file could be used and no test name was repeated.
+    pub passed: bool,
+}//end struct ValidationSummary
+
+/// Runs the same checks on parsed inputs that processing does, without writing anything.
+/// Each file gets a line starting with PASS or FAIL, with the rows parsed from it
+/// or why it couldn't be used, followed by any non-fatal issues in that file.
+/// After that come any repeated test names and values outside config value_ranges,
+/// and then the totals.
+pub fn validate_parsed(parsed: &ParsedInputs, config: &ConfigStore) -> ValidationSummary {
+    let mut lines = Vec::new();
+    let mut data_files = parsed.data_files.iter();
+    let mut rows_parsed = 0;
+    for file in parsed.files.iter() {
+        let path = file.path.to_string_lossy();
+        match &file.result {
+            Err(msg) => lines.push(format!("FAIL {}: {}", path, msg.replace("\n", " "))),
+            Ok(issues) => {
+                let rows = data_files.next().map(|data_file| data_file.row_data.len()).unwrap_or(0);
+                rows_parsed += rows;
+                lines.push(format!("PASS {}: {} row(s), {} issue(s)", path, rows, issues.len()));
+                lines.extend(issues.iter().map(|issue| format!("    {}", issue.replace("\n", " "))));
+            },
+        }//end matching whether the file could be used
+    }//end adding a line for each file
+    let duplicates = data::find_duplicate_test_names(&parsed.data_files);
+    for (name, count) in duplicates.iter() {
+        lines.push(format!("Repeated test name \"{}\" is used by {} files", name, count));
+    }//end adding a line for each repeated test name
+    lines.extend(get_out_of_range_messages(&parsed.data_files, config).into_iter().map(|msg| format!("Out of range: {}", msg)));
+    let files_passed = parsed.files.iter().filter(|file| file.result.is_ok()).count();
+    lines.push(format!("{} of {} file(s) passed, with {} row(s) parsed.", files_passed, parsed.files.len(), rows_parsed));
+    if parsed.canceled {lines.push("Checking was canceled before every file was read.".to_string());}
+    ValidationSummary {
+        lines,
+        passed: files_passed == parsed.files.len() && duplicates.is_empty() && !parsed.canceled,
+    }
+}//end validate_parsed()
+
+/// Parses inputs which have already been read, given as pairs of name and contents,
+/// such as a file name and the text of that file. The name is used as the path of
+/// each FileOutcome, and as the source file of each Data.
+pub fn parse_named_inputs(inputs: &[(String, String)], config: &ConfigStore) -> ParsedInputs {
+    let mut parsed = ParsedInputs { total_steps: inputs.len() + 1, ..ParsedInputs::default() };
+    for (name, contents) in inputs {
+        let (data, result) = parse_contents(name, contents, "UTF-8", config);
+        if let Some(data) = data {parsed.data_files.push(data);}
+        parsed.files.push(FileOutcome { path: PathBuf::from(name), result });
+    }//end parsing each input
+    parsed
+}//end parse_named_inputs()
+
+/// Parses the given inputs and writes them to output in one call, for use by
+/// programs that embed this library. Each input is a pair of name and contents,
+/// such as a file name and the text of that file.  
+/// The extension for config output_format is added to output if it doesn't
+/// have a recognized one, like resolve_output_path(). This writes through
+/// process::export_parsed(), like run() and the gui, so the output is the same as
+/// from the gui or command line, except that repeated test names are reported but never renamed.
+pub fn run_export(inputs: &[(String, String)], output: &PathBuf, config: &ConfigStore) -> Result<ExportReport,ExportError> {
+    config.validate().map_err(ExportError::InvalidConfig)?;
+    let (output_path, output_format) = resolve_output_path(output.clone(), config.output_format);
+    let config = &ConfigStore { output_format, ..config.clone() };
+    let parsed = parse_named_inputs(inputs, config);
+    let output_spec = OutputSpec::new(output_path, &get_sheet_name(config, 1));
+    let mut run_report = export_parsed(parsed, &output_spec, config, &mut |_| false, &mut |_,_| true);
+    if run_report.files_parsed() == 0 {return Err(ExportError::NoUsableInputs(run_report.into_export_report().skipped));}
+    if let Some(msg) = run_report.write_error.take() {return Err(ExportError::Write(msg));}
+    Ok(run_report.into_export_report())
+}//end run_export()
+
+/// Writes the data from parse_inputs_with_progress() to the output, as the last part
+/// of process::run_with_progress(), calling on_progress once the output is written.  
+/// Problems along the way are recorded in the returned report, along with the outcome of each file.
+pub fn write_parsed(parsed: ParsedInputs, output: &OutputSpec, config: &ConfigStore, on_progress: &mut dyn FnMut(usize,usize) -> bool) -> RunReport {
+    let ParsedInputs { mut data_files, files, canceled, total_steps } = parsed;
+    let mut report = RunReport {
+        output_path: output.path.clone(),
+        files,
+        canceled,
+        ..RunReport::default()
+    };
+    if data_files.is_empty() {
+        report.write_error = Some("None of the input files could be used, so no output was written.".to_string());
+        return report;
+    }//end if there's nothing to write
+    let parsed_count = data_files.len();
+    data_files = data::filter_by_test_name(data_files, config);
+    if data_files.is_empty() {
+        report.write_error = Some("All of the input files were left out by test_name_include or test_name_exclude in the config, so no output was written.".to_string());
+        return report;
+    } else if data_files.len() < parsed_count {
+        report.warnings.push(format!("{} file(s) were left out of the output by test_name_include or test_name_exclude in the config.", parsed_count - data_files.len()));
+    }//end else if only some files were filtered out
+    report.out_of_range = get_out_of_range_messages(&data_files, config);
+    // summary statistics come from each file, before any averaging
+    let summaries = data::summarize_data(&get_summary_data(&data_files, config));
+    report.zero_values_skipped = data_files.iter()
+        .flat_map(|data_file| data_file.row_data.iter())
+        .filter(|row| config.is_skipped_zero(row.value))
+        .count();
+    // sample dates come from each file too, since averaging drops metadata
+    let sample_dates: Vec<(String,String)> = data_files.iter()
+        .filter_map(|data_file| data_file.get_sample_date().map(|date| (data_file.test_name.clone(), date.to_string())))
+        .collect();
+    data_files = combine_data(data_files, config);
+    report.rows_written = data_files.len();
+
+    if config.output_format == OutputFormat::Csv {
+        // a csv file can only hold a single sheet, so it isn't split
+        if let Err(err) = write_csv_for_layout(&data_files, &output.path, config) {
+            report.write_error = Some(format!("There was an issue writing the csv file \"{}\". \nIs it open? \n{}", output.path.to_string_lossy(), err));
+        } else {on_progress(total_steps, total_steps);}
+        return report;
+    }//end if output should be a csv file instead of a workbook
+    if config.output_format == OutputFormat::Json {
+        if let Err(err) = write_output_to_json(&data_files, &output.path) {
+            report.write_error = Some(format!("There was an issue writing the json file \"{}\". \nIs it open? \n{}", output.path.to_string_lossy(), err));
+        } else {on_progress(total_steps, total_steps);}
+        return report;
+    }//end if output should be a json file instead of a workbook
+
+    let mut wb = match open_or_create_workbook(&output.append_to) {
+        Ok(wb) => wb,
+        Err(err) => {
+            report.write_error = Some(format!("There was an issue writing the sheets already in the workbook:\n{}", err));
+            return report;
+        },
+    };//end matching whether we could write the sheets being appended to
+    let mut taken_names: Vec<String> = output.append_to.iter().map(|record| record.sheet_name.clone()).collect();
+    let sheets: Vec<(String, Vec<Data>)> = split_into_sheets(&output.sheet_name, data_files, config).into_iter()
+        .map(|(sheet_name, sheet_data)| {
+            let taken: Vec<&str> = taken_names.iter().map(|name| name.as_str()).collect();
+            let sheet_name = get_unique_sheet_name(&sheet_name, &taken);
+            taken_names.push(sheet_name.clone());
+            (sheet_name, sheet_data)
+        })
+        .collect();
+    let mut records = output.append_to.clone();
+    records.extend(sheets.iter().map(|(sheet_name, sheet_data)| SheetRecord {
+        sheet_name: sheet_name.clone(),
+        contents: SheetContents::Data(sheet_data.clone()),
+        config: config.clone(),
+    }));
+    let mut sheet_ranges = Vec::new();
+    for (sheet_name, sheet_data) in sheets.iter() {
+        match write_sheet_for_layout(&mut wb, sheet_data, sheet_name, config) {
+            Ok(sheet_range) => sheet_ranges.extend(sheet_range),
+            Err(err) => {
+                report.write_error = Some(format!("There was an issue writing output data to the sheet:\n{}", err));
+                return report;
+            },
+        }//end matching whether there was an error writing to the sheet
+    }//end writing each sheet
+    if config.include_summary_sheet {
+        let sheet_names: Vec<&str> = taken_names.iter().map(|sheet_name| sheet_name.as_str()).collect();
+        let summary_name = get_summary_sheet_name(&sheet_names);
+        // formulas over averaged rows wouldn't match the statistics of each file
+        if !config.summary_use_formulas || config.average_all_inputs {sheet_ranges.clear();}
+        if let Err(err) = write_summary_sheet(&mut wb, &summaries, &sheet_ranges, &summary_name, config) {
+            report.write_error = Some(format!("There was an issue writing the summary sheet:\n{}", err));
+            return report;
+        }//end if there was an error writing the summary sheet
+        taken_names.push(summary_name.clone());
+        records.push(SheetRecord { sheet_name: summary_name, contents: SheetContents::Summary(summaries, sheet_ranges), config: config.clone() });
+    }//end if we should write a summary sheet
+    if config.include_metadata_sheet {
+        let sheet_names: Vec<&str> = taken_names.iter().map(|sheet_name| sheet_name.as_str()).collect();
+        let metadata_name = get_unique_sheet_name(METADATA_SHEET_NAME, &sheet_names);
+        let inputs: Vec<String> = report.files.iter()
+            .filter(|file| file.result.is_ok())
+            .map(|file| file.path.file_name().unwrap_or(file.path.as_os_str()).to_string_lossy().to_string())
+            .collect();
+        let metadata = get_metadata(config, &inputs, &sample_dates, output.profile_name.as_deref(), OffsetDateTime::now_utc());
+        if let Err(err) = write_metadata_sheet(&mut wb, &metadata, &metadata_name) {
+            report.write_error = Some(format!("There was an issue writing the metadata sheet:\n{}", err));
+            return report;
+        }//end if there was an error writing the metadata sheet
+        records.push(SheetRecord { sheet_name: metadata_name, contents: SheetContents::Metadata(metadata), config: config.clone() });
+    }//end if we should write a metadata sheet
+    if let Err(err) = close_workbook(&mut wb, &output.path) {
+        report.write_error = Some(format!("There was an issue closing the workbook \"{}\". \nIs it open? \n{}", output.path.to_string_lossy(), err));
+        return report;
+    }//end if there was an error closing the workbook
+    report.sheets = records;
+    // the output is already written, so there's nothing left to cancel
+    on_progress(total_steps, total_steps);
+
+    if config.export_sheets_as_csv {
+        let csv_dir = get_csv_sheet_dir(&output.path);
+        if let Err(err) = fs::create_dir_all(&csv_dir) {
+            report.warnings.push(format!("Couldn't create the folder \"{}\" for csv copies of each sheet:\n{}", csv_dir.to_string_lossy(), err));
+        } else {
+            for (sheet_name, sheet_data) in sheets.iter() {
+                let csv_path = csv_dir.join(format!("{}.csv", sanitize_file_name(sheet_name)));
+                if let Err(err) = write_csv_for_layout(sheet_data, &csv_path, config) {
+                    report.warnings.push(format!("Couldn't write csv copy of sheet \"{}\" to \"{}\":\n{}", sheet_name, csv_path.to_string_lossy(), err));
+                }//end if we couldn't write the csv file
+            }//end writing each sheet as a csv file
+        }//end else we can write csv files into the folder
+    }//end if we should also write each sheet as a csv
+    report
+}//end write_parsed()
+
+/// Gets the text used to represent a value that isn't a finite number,
+/// such as the result of dividing by zero.  
+/// Finite values are simply converted to a string.
+pub fn non_finite_label(value: f64) -> String {
+    if value.is_nan() {"NaN".to_string()}
+    else if value == f64::INFINITY {"Infinity".to_string()}
+    else if value == f64::NEG_INFINITY {"-Infinity".to_string()}
+    else {value.to_string()}
+}//end non_finite_label()
+
+/// Fills in the tokens in a name template, such as the sheet name template.  
+/// {date} is replaced with the date as YYYY-MM-DD, {time} with the time as
+/// HH-MM-SS, and {index} with the given index.
+pub fn fill_name_template(template: &str, index: usize, now: OffsetDateTime) -> String {
+    let date = format!("{:04}-{:02}-{:02}", now.year(), now.month() as u8, now.day());
+    let time = format!("{:02}-{:02}-{:02}", now.hour(), now.minute(), now.second());
+    template
+        .replace("{date}", &date)
+        .replace("{time}", &time)
+        .replace("{index}", &index.to_string())
+}//end fill_name_template()
+
+/// Makes a name safe to use as an excel sheet name, by replacing
+/// characters excel doesn't allow with underscores, removing apostrophes
+/// from the start and end, and cutting it down to 31 characters.  
+/// If nothing is left, gives "Sheet" followed by index.
+pub fn sanitize_sheet_name(name: &str, index: usize) -> String {
+    let sanitized: String = name.trim()
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) {'_'} else {c})
+        .collect::<String>()
+        .trim_matches('\'')
+        .chars()
+        .take(SHEET_NAME_MAX_LEN)
+        .collect();
+    if sanitized.trim().is_empty() {format!("Sheet{}", index)} else {sanitized}
+}//end sanitize_sheet_name()
+
+/// Gets the name for the sheet at the given 1-based index, using the
+/// sheet name template from the config and the current time.
+pub fn get_sheet_name(config: &ConfigStore, index: usize) -> String {
+    let filled = fill_name_template(&config.sheet_name_template, index, OffsetDateTime::now_utc());
+    sanitize_sheet_name(&filled, index)
+}//end get_sheet_name()
+
+/// Gets the name of the first sheet for a run, from the name typed by the user.
+/// If typed is blank, config sheet_name_template is used, or the stem of
+/// output_path if that's blank too.  
+/// The name is made safe with sanitize_sheet_name(). Also returns true if a typed
+/// name had to be changed to be used, such as by being shortened, so the user can be told.
+pub fn resolve_sheet_name(typed: &str, output_path: &Path, config: &ConfigStore) -> (String, bool) {
+    let typed = typed.trim();
+    if !typed.is_empty() {
+        let sanitized = sanitize_sheet_name(typed, 1);
+        let changed = sanitized != typed;
+        return (sanitized, changed);
+    }//end if the user typed a name
+    if !config.sheet_name_template.trim().is_empty() {return (get_sheet_name(config, 1), false);}
+    let stem = output_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    (sanitize_sheet_name(&stem, 1), false)
+}//end resolve_sheet_name()
+
+/// Gets the output name to fill in by default, from config output_name_template.  
+/// Returns None if there's no template.
+pub fn get_default_output_name(config: &ConfigStore, now: OffsetDateTime) -> Option<String> {
+    if config.output_name_template.trim().is_empty() {return None;}
+    Some(sanitize_file_name(&fill_name_template(&config.output_name_template, 1, now)))
+}//end get_default_output_name()
+
+/// Gets the folder that a typed output name should be saved into. This is
+/// config default_output_dir if set, otherwise the folder of the first input.
+pub fn get_output_dir(config: &ConfigStore, inputs: &[PathBuf]) -> Option<PathBuf> {
+    if !config.default_output_dir.trim().is_empty() {
+        return Some(PathBuf::from(config.default_output_dir.trim()));
+    }//end if there's a folder set in the config
+    inputs.first()
+        .and_then(|input| input.parent())
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .map(|parent| parent.to_path_buf())
+}//end get_output_dir()
+
+/// Gets the folder that csv copies of each sheet are written to, which sits
+/// next to the output file and is named after it.
+pub fn get_csv_sheet_dir(output_path: &Path) -> PathBuf {
+    let stem = output_path.file_stem().unwrap_or_default().to_string_lossy();
+    output_path.with_file_name(format!("{}_csv", stem))
+}//end get_csv_sheet_dir()
+
+/// Replaces any characters which aren't allowed in file names on common
+/// platforms with underscores. If nothing is left, gives "output".
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name.trim().chars()
+        .map(|c| if c.is_control() || "<>:\"/\\|?*".contains(c) {'_'} else {c})
+        .collect();
+    if sanitized.is_empty() {"output".to_string()} else {sanitized}
+}//end sanitize_file_name()
+
+/// Surrounds a csv field in config.csv_quote if it contains anything that would
+/// break the csv format, doubling any quotes inside it.
+fn csv_escape(field: &str, config: &ConfigStore) -> String {
+    let quote = config.csv_quote;
+    if field.contains([config.csv_delimiter, quote, '\n', '\r']) {
+        let doubled_quote = format!("{quote}{quote}");
+        format!("{quote}{}{quote}", field.replace(quote, &doubled_quote))
+    } else {field.to_string()}
+}//end csv_escape()
+
+/// Builds a single line of csv from the given cells, escaping each one
+/// and separating them with config.csv_delimiter.
+pub fn get_csv_line(cells: &[String], config: &ConfigStore) -> String {
+    cells.iter()
+        .map(|cell| csv_escape(cell, config))
+        .collect::<Vec<String>>()
+        .join(&config.csv_delimiter.to_string())
+}//end get_csv_line()
+
+/// Writes output to a csv file at output_path, with the same header row
+/// and rows for each test as write_output_to_sheet().
+pub fn write_output_to_csv(data: &Vec<Data>, output_path: &PathBuf, config: &ConfigStore) -> Result<(),io::Error> {
+    let mut file = File::create(output_path)?;
+    for table_row in get_wide_table(data, config) {
+        writeln!(file, "{}", get_csv_line(&table_row, config))?;
+    }//end writing each row of the table
+    Ok(())
+}//end write_output_to_csv()
+
+/// Writes the parsed data to a pretty-printed json file at output_path, as a list
+/// with the test_name, source_file, metadata, and row_data of each Data.  
+/// This is meant for other programs, so none of the layout or display settings apply.
+/// Values that aren't finite, such as NaN, are written as null, since json can't hold them.
+pub fn write_output_to_json(data: &Vec<Data>, output_path: &PathBuf) -> Result<(),io::Error> {
+    let json = serde_json::to_string_pretty(data).map_err(io::Error::other)?;
+    fs::write(output_path, json)
+}//end write_output_to_json()
+
+/// Builds the wide layout as a table of text, with a header row followed by
+/// a row for each data file. This is shared by the plain-text writers.  
+/// If data is empty, so is the table.
+pub fn get_wide_table(data: &Vec<Data>, config: &ConfigStore) -> Vec<Vec<String>> {
+    let mut table = Vec::new();
+    if data.len() < 1 {return table;}
+    let data = &order_columns(data, config);
+
+    let constant_columns = get_constant_columns(config, OffsetDateTime::now_utc());
+    let metadata_keys = get_metadata_columns(data, config);
+    let mut header_row = vec!["Test Name".to_string()];
+    header_row.extend(metadata_keys.iter().cloned());
+    for row in data.first().expect("already checked").row_data.iter() {
+        let display_header = get_display_header(&row.header, config);
+        header_row.push(display_header.clone());
+        if config.parse_row_flags {header_row.push(get_flag_column_header(&display_header));}
+    }//end adding each row header
+    let data_width = header_row.len();
+    if config.include_source_file_column {header_row.push(SOURCE_FILE_HEADER.to_string());}
+    header_row.extend(constant_columns.iter().map(|(header,_)| header.clone()));
+    table.push(header_row);
+
+    for data_file in data {
+        let mut table_row = vec![data_file.test_name.clone()];
+        table_row.extend(metadata_keys.iter().map(|key| data_file.get_metadata_value(key).unwrap_or_default().to_string()));
+        for row in data_file.row_data.iter() {
+            table_row.push(get_csv_value(row.value, &row.header, config));
+            if config.parse_row_flags {table_row.push(row.flag.clone().unwrap_or_default());}
+        }//end adding each value in this test
+        // keep constant columns lined up under their headers
+        if table_row.len() < data_width {table_row.resize(data_width, "".to_string());}
+        if config.include_source_file_column {table_row.push(data_file.source_file.clone().unwrap_or_default());}
+        table_row.extend(constant_columns.iter().map(|(_,value)| value.clone()));
+        table.push(table_row);
+    }//end adding a row for each data file
+    table
+}//end get_wide_table()
+
+/// Gets a width for each column of a table from get_wide_table(), fitting the longest
+/// cell in that column, clamped between MIN_COLUMN_WIDTH and MAX_COLUMN_WIDTH.  
+/// The first row is taken as the header row, which is written in a larger font.
+/// Numbers are measured as if shown with two decimal places, as a 0.00 format would.
+pub fn get_column_widths(table: &[Vec<String>]) -> Vec<f64> {
+    let header_scale = FONT_SIZE_HEADER as f64 / FONT_SIZE_DATA as f64;
+    let mut widths: Vec<f64> = Vec::new();
+    for (row_idx, table_row) in table.iter().enumerate() {
+        for (col, cell) in table_row.iter().enumerate() {
+            let chars = match cell.parse::<f64>() {
+                Ok(value) if value.is_finite() => format!("{:.2}", value).len().max(cell.chars().count()),
+                _ => cell.chars().count(),
+            } as f64;
+            let width = if row_idx == 0 {chars * header_scale} else {chars} + COLUMN_WIDTH_PADDING;
+            if col >= widths.len() {widths.resize(col + 1, MIN_COLUMN_WIDTH);}
+            widths[col] = widths[col].max(width);
+        }//end measuring each cell in the row
+    }//end measuring each row of the table
+    widths.iter().map(|width| width.min(MAX_COLUMN_WIDTH)).collect()
+}//end get_column_widths()
+
+/// Gets the metadata keys to write as columns after the test name, in the order
+/// they were first found in data. Returns nothing unless config
+/// include_file_metadata_columns is set.
+pub fn get_metadata_columns(data: &[Data], config: &ConfigStore) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    if !config.include_file_metadata_columns {return keys;}
+    for (key, _) in data.iter().flat_map(|data_file| data_file.metadata.iter()) {
+        if !keys.contains(key) {keys.push(key.clone());}
+    }//end adding each key we haven't seen yet
+    keys
+}//end get_metadata_columns()
+
+/// Gets config constant_columns, with any tokens in the values filled in
+/// using the given time.
+pub fn get_constant_columns(config: &ConfigStore, now: OffsetDateTime) -> Vec<(String,String)> {
+    config.constant_columns.iter()
+        .map(|(header, value)| (header.clone(), fill_name_template(value, 1, now)))
+        .collect()
+}//end get_constant_columns()
+
+/// Gets the wide layout as tab-separated text, such as for pasting into
+/// another spreadsheet. Tabs and line breaks within cells are replaced with spaces.
+pub fn get_wide_tsv(data: &Vec<Data>, config: &ConfigStore) -> String {
+    get_wide_table(data, config).iter()
+        .map(|table_row| table_row.iter()
+            .map(|cell| cell.replace(['\t','\r','\n'], " "))
+            .collect::<Vec<String>>()
+            .join("\t"))
+        .map(|line| line + "\n")
+        .collect()
+}//end get_wide_tsv()
+
+/// Gets a plain text listing of the rows in data, for previewing what was parsed
+/// before writing any output. Each row is on its own line, with its header, value,
+/// and flag if it has one, separated by tabs.
+pub fn get_preview_text(data: &Data, config: &ConfigStore) -> String {
+    let mut text = format!("Preview of \"{}\"", data.test_name);
+    if let Some(source_file) = &data.source_file {text.push_str(&format!(" from {}", source_file));}
+    text.push_str(&format!(", with {} row(s):\n", data.row_data.len()));
+    for row in data.row_data.iter() {
+        text.push_str(&format!("\n{}\t{}", row.header, get_csv_value(row.value, &row.header, config)));
+        if let Some(flag) = &row.flag {text.push_str(&format!("\t{}", flag));}
+    }//end adding a line for each row
+    text
+}//end get_preview_text()
+
+/// Gets the text to write to a csv file for a value of header, rounded to the
+/// decimal places from config, and following config non_finite_format for
+/// values that aren't finite numbers.
+/// Values skipped by config skip_zero_rows are left blank.
+fn get_csv_value(value: f64, header: &str, config: &ConfigStore) -> String {
+    if config.is_skipped_zero(value) {return "".to_string();}
+    if value.is_finite() {return format!("{:.*}", config.get_decimal_places(header) as usize, value);}
+    match config.non_finite_format {
+        NonFiniteFormat::Blank => "".to_string(),
+        NonFiniteFormat::Text | NonFiniteFormat::Flagged => non_finite_label(value),
+    }//end matching how to write value that isn't a number
+}//end get_csv_value()
+
+/// Writes data to a csv file, using whichever writer matches config output_layout.
+pub fn write_csv_for_layout(data: &Vec<Data>, output_path: &PathBuf, config: &ConfigStore) -> Result<(),io::Error> {
+    match config.output_layout {
+        OutputLayout::Wide => write_output_to_csv(data, output_path, config),
+        OutputLayout::Pivot => write_pivot_to_csv(data, output_path, config),
+        OutputLayout::Merged => write_merged_to_csv(data, output_path, config),
+    }//end matching the layout to write
+}//end write_csv_for_layout()
+
+/// Writes data to a csv file in the merged layout, from get_merged_table().
+pub fn write_merged_to_csv(data: &Vec<Data>, output_path: &PathBuf, config: &ConfigStore) -> Result<(),io::Error> {
+    let mut file = File::create(output_path)?;
+    for table_row in get_merged_table(data, config) {
+        writeln!(file, "{}", get_csv_line(&table_row, config))?;
+    }//end writing each row of the table
+    Ok(())
+}//end write_merged_to_csv()
+
+/// Builds the merged layout as a table of text, with a header row followed by a row
+/// for each data file. Each row starts with the source file and test name, followed
+/// by a column for every header found in any of the files, in the order each is first
+/// seen, and a flag column after each if config parse_row_flags is set.  
+/// Headers missing from a file are left blank. If data is empty, so is the table.
+pub fn get_merged_table(data: &Vec<Data>, config: &ConfigStore) -> Vec<Vec<String>> {
+    let mut table = Vec::new();
+    if data.len() < 1 {return table;}
+    let data = &order_columns(data, config);
+    let headers = get_pivot_row_headers(data);
+
+    let mut header_row = vec![SOURCE_FILE_HEADER.to_string(), "Test Name".to_string()];
+    for header in headers.iter() {
+        let display_header = get_display_header(header, config);
+        header_row.push(display_header.clone());
+        if config.parse_row_flags {header_row.push(get_flag_column_header(&display_header));}
+    }//end adding each header
+    table.push(header_row);
+
+    for data_file in data {
+        let mut table_row = vec![data_file.source_file.clone().unwrap_or_default(), data_file.test_name.clone()];
+        for header in headers.iter() {
+            let row = data_file.row_data.iter().find(|row| row.header.eq(header));
+            table_row.push(row.map(|row| get_csv_value(row.value, &row.header, config)).unwrap_or_default());
+            if config.parse_row_flags {table_row.push(row.and_then(|row| row.flag.clone()).unwrap_or_default());}
+        }//end adding the value for each header
+        table.push(table_row);
+    }//end adding a row for each data file
+    table
+}//end get_merged_table()
+
+/// Writes data to a csv file in the pivot layout, from get_pivot_table().
+pub fn write_pivot_to_csv(data: &Vec<Data>, output_path: &PathBuf, config: &ConfigStore) -> Result<(),io::Error> {
+    let mut file = File::create(output_path)?;
+    for table_row in get_pivot_table(data, config) {
+        writeln!(file, "{}", get_csv_line(&table_row, config))?;
+    }//end writing each row of the table
+    Ok(())
+}//end write_pivot_to_csv()
+
+/// Builds the pivot layout as a table of text, following the same shape as
+/// process::write_pivot_to_sheet(), but without merged cells. The first row has
+/// "Measurement" followed by each test name, then there's a row for each measurement,
+/// with its value for each file.  
+/// If config parse_row_flags is set, each test name is followed by a blank cell, and a
+/// second header row labels each pair of columns "Value" and "Flag".  
+/// Measurements missing from a file are left blank. If data is empty, so is the table.
+pub fn get_pivot_table(data: &Vec<Data>, config: &ConfigStore) -> Vec<Vec<String>> {
+    let mut table = Vec::new();
+    if data.len() < 1 {return table;}
+
+    let mut group_row = vec!["Measurement".to_string()];
+    let mut sub_row = vec!["".to_string()];
+    for data_file in data {
+        group_row.push(data_file.test_name.clone());
+        if config.parse_row_flags {
+            group_row.push("".to_string());
+            sub_row.push("Value".to_string());
+            sub_row.push("Flag".to_string());
+        }//end if each group has a flag column
+    }//end adding headers for each file's group
+    table.push(group_row);
+    if config.parse_row_flags {table.push(sub_row);}
+
+    for header in get_pivot_row_headers(data) {
+        let mut table_row = vec![header.clone()];
+        for data_file in data {
+            let row = data_file.row_data.iter().find(|row| row.header.eq(&header));
+            table_row.push(row.map(|row| get_csv_value(row.value, &row.header, config)).unwrap_or_default());
+            if config.parse_row_flags {
+                table_row.push(row.and_then(|row| row.flag.clone()).unwrap_or_default());
+            }//end if we need to write the flag next to the value
+        }//end adding each file's value for this measurement
+        table.push(table_row);
+    }//end adding a row for each measurement
+
+    // constant columns become rows at the bottom, with the value under each file
+    for (header, value) in get_constant_columns(config, OffsetDateTime::now_utc()) {
+        let mut table_row = vec![header];
+        for _ in data {
+            table_row.push(value.clone());
+            if config.parse_row_flags {table_row.push("".to_string());}
+        }//end adding the value for each file
+        table.push(table_row);
+    }//end adding a row for each constant column
+    table
+}//end get_pivot_table()
+
+/// Gets the header for the column holding the quality flags of the given row header.
+pub fn get_flag_column_header(row_header: &str) -> String {
+    format!("{} Flag", row_header)
+}//end get_flag_column_header()
+
+/// Gets the name a row header is shown as in the wide layout, from config
+/// column_display, or the header itself if it isn't given a name there.
+pub fn get_display_header(row_header: &str, config: &ConfigStore) -> String {
+    match config.column_display.iter().find(|(header,_)| header == row_header) {
+        Some((_, name)) if !name.is_empty() => name.clone(),
+        _ => row_header.to_string(),
+    }//end matching whether the header has a name to show
+}//end get_display_header()
+
+/// Reorders the rows of each Data to follow config column_display, with the headers
+/// listed there first, in that order, and any other headers after, in their original order.  
+/// If column_display is empty, the data is unchanged.
+pub fn order_columns(data: &[Data], config: &ConfigStore) -> Vec<Data> {
+    let mut ordered = data.to_vec();
+    if config.column_display.is_empty() {return ordered;}
+    for data_file in ordered.iter_mut() {
+        // sorting is stable, so unlisted headers keep their order
+        data_file.row_data.sort_by_key(|row| config.column_display.iter().position(|(header,_)| *header == row.header).unwrap_or(usize::MAX));
+    }//end reordering the rows of each file
+    ordered
+}//end order_columns()
+
+/// Gets a message for each value in data outside its range in config value_ranges,
+/// such as "24-PCF4001: W is 450, outside 150 to 400".
+pub fn get_out_of_range_messages(data: &[Data], config: &ConfigStore) -> Vec<String> {
+    let mut messages = Vec::new();
+    for data_file in data {
+        for row in data_file.out_of_range(config) {
+            let range = config.value_ranges[&row.header];
+            messages.push(format!("{}: {} is {}, outside {}", data_file.test_name, row.header, row.value, range.to_string()));
+        }//end adding a message for each value out of range
+    }//end checking each file
+    messages
+}//end get_out_of_range_messages()
+
+/// Gets the format to use for values outside their range in config value_ranges,
+/// which stands out from the normal format for that cell in the same way as other
+/// flagged cells, following config flag_color and flag_style.
+pub fn get_out_of_range_format(base_format: &Format, config: &ConfigStore) -> Format {
+    get_flagged_format(base_format, config)
+}//end get_out_of_range_format()
+
+/// Gets the 0-based row that the first row of data goes in, in the wide layout of
+/// xlsx output. This is just after the header row, or after the units row if there
+/// are any config header_units.
+pub fn get_first_data_row(config: &ConfigStore) -> u32 {
+    if config.header_units.is_empty() {HEADER_START_ROW + 1}
+    else {HEADER_START_ROW + 2}
+}//end get_first_data_row()
+
+/// Gets the cells to put an autofilter on in the wide layout, as the first row,
+/// first column, last row, and last column. This goes from the header row to the
+/// last row of data, across every column written. If there's a units row, the
+/// filter starts from it instead, so it's never sorted in with the data.  
+/// Returns None if there's no data, or no rows of data to make headers from.
+pub fn get_autofilter_range(data: &[Data], config: &ConfigStore) -> Option<(u32,u16,u32,u16)> {
+    let widest_row_data = data.iter().map(|d| d.row_data.len()).max().unwrap_or(0);
+    if widest_row_data == 0 {return None;}
+    let cols_per_row: u16 = if config.parse_row_flags {2} else {1};
+    let mut last_col = widest_row_data as u16 * cols_per_row + get_metadata_columns(data, config).len() as u16;
+    if config.include_source_file_column {last_col += 1;}
+    last_col += config.constant_columns.len() as u16;
+    let filter_row = get_first_data_row(config) - 1;
+    Some((filter_row, 0, filter_row + data.len() as u32, last_col))
+}//end get_autofilter_range()
+
+/// Gets the format to use for flagged cells, based on the flag color and
+/// style in the config, building on top of the normal format for that cell.
+pub fn get_flagged_format(base_format: &Format, config: &ConfigStore) -> Format {
+    let format = base_format.clone();
+    match config.flag_style {
+        FlagStyle::Fill => format
+            .set_background_color(Color::RGB(config.flag_color.fill_rgb()))
+            .set_font_color(Color::RGB(config.flag_color.font_rgb())),
+        FlagStyle::Font => format.set_font_color(Color::RGB(config.flag_color.font_rgb())),
+        FlagStyle::Bold => format.set_bold(),
+        FlagStyle::Italic => format.set_italic(),
+    }//end matching the style of flagging
+}//end get_flagged_format()
 
 /// Creates an excel workbook, which can then be used in
 /// further funtions.
@@ -22,17 +1226,485 @@ pub fn get_workbook() -> Workbook {
 
 /// Should be called after done working with a workbook, for performance reasons.
 pub fn close_workbook(workbook: &mut Workbook, output_path: &PathBuf) -> Result<(),XlsxError> {
-    workbook.save(output_path)?;
+    let bytes = workbook.save_to_buffer()?;
+    fs::write(output_path, bytes).map_err(XlsxError::IoError)?;
     Ok(())
 }//end close_workbook(workbook)
 
+/// Writes the data to a new workbook held in memory, and gives back the bytes
+/// of the xlsx file instead of saving it to disk.  
+/// The data is written to a single sheet, named from config sheet_name_template.
+/// This is useful for tests or for handing the file to something other than the filesystem.
+pub fn write_output_to_bytes(data: &Vec<Data>, config: &ConfigStore) -> Result<Vec<u8>,XlsxError> {
+    let mut workbook = get_workbook();
+    write_sheet_for_layout(&mut workbook, data, &get_sheet_name(config, 1), config)?;
+    workbook.save_to_buffer()
+}//end write_output_to_bytes()
+
+/// Writes data to a new sheet in the workbook, using whichever writer
+/// matches config output_layout.  
+/// For the wide layout, gives back where the values were written, if there were any.
+pub fn write_sheet_for_layout(workbook: &mut Workbook, data: &Vec<Data>, sheet_name: &str, config: &ConfigStore) -> Result<Option<SheetRange>,XlsxError> {
+    match config.output_layout {
+        OutputLayout::Wide => {
+            let sheet_range = write_output_to_sheet(workbook, data, sheet_name, config)?;
+            if let (Some(header), Some(sheet_range)) = (&config.chart_metric, &sheet_range) {
+                add_metric_chart(workbook, data, header, sheet_range)?;
+            }//end if we should chart a metric from this sheet
+            Ok(sheet_range)
+        },
+        OutputLayout::Pivot => write_pivot_to_sheet(workbook, data, sheet_name, config).map(|_| None),
+        OutputLayout::Merged => write_merged_sheet(workbook, data, sheet_name, config).map(|_| None),
+    }//end matching the layout to write
+}//end write_sheet_for_layout()
+
+/// Adds a bar chart of the value of header for each test in data, with the test names
+/// as categories, to a sheet already written by write_output_to_sheet(), which sheet_range
+/// describes. The chart is placed below the data, leaving a blank row after it.  
+/// If no file in data has header, no chart is added, and this still returns Ok.
+pub fn add_metric_chart(workbook: &mut Workbook, data: &[Data], header: &str, sheet_range: &SheetRange) -> Result<(),XlsxError> {
+    if !data.iter().any(|data_file| data_file.row_data.iter().any(|row| row.header == header)) {return Ok(());}
+    let Some((_, col)) = sheet_range.header_cols.iter().find(|(range_header,_)| range_header == header) else {return Ok(());};
+    let sheet_name = sheet_range.sheet_name.as_str();
+    let mut chart = Chart::new(ChartType::Column);
+    chart.add_series()
+        .set_name(header)
+        .set_categories((sheet_name, sheet_range.first_row, 0, sheet_range.last_row, 0))
+        .set_values((sheet_name, sheet_range.first_row, *col, sheet_range.last_row, *col));
+    chart.title().set_name(&format!("{} by Test", header));
+    chart.x_axis().set_name("Test Name");
+    chart.y_axis().set_name(header);
+    chart.legend().set_hidden();
+    workbook.worksheet_from_name(sheet_name)?
+        .insert_chart(sheet_range.last_row + 2, 0, &chart)
+        .map_err(|e| add_write_context(e, format!("chart of \"{}\"", shorten_for_message(header))))?;
+    Ok(())
+}//end add_metric_chart()
+
+/// Gets every measurement header found in the data, in the order each
+/// is first seen. These are the rows of the pivot layout.
+pub fn get_pivot_row_headers(data: &Vec<Data>) -> Vec<String> {
+    let mut headers: Vec<String> = Vec::new();
+    for data_file in data {
+        for row in data_file.row_data.iter() {
+            if !headers.contains(&row.header) {headers.push(row.header.clone());}
+        }//end checking each row header in this file
+    }//end going through each file
+    headers
+}//end get_pivot_row_headers()
+
+/// Wraps an error from writing to a sheet with a description of what was
+/// being written, so the message points at the data which caused it, like
+/// "failed writing test \"Sample001\" header \"P\": ...".
+fn add_write_context(err: XlsxError, context: String) -> XlsxError {
+    XlsxError::ParameterError(format!("failed writing {}: {}", context, err))
+}//end add_write_context()
+
+/// Cuts long text down to a length that fits comfortably in an error message.
+fn shorten_for_message(text: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    if text.chars().count() <= MAX_CHARS {text.to_string()}
+    else {format!("{}...", text.chars().take(MAX_CHARS).collect::<String>())}
+}//end shorten_for_message()
+
+/// Gets a name for the summary sheet which doesn't clash with any of the
+/// given data sheet names, ignoring case like excel does.
+pub fn get_summary_sheet_name(sheet_names: &[&str]) -> String {
+    get_unique_sheet_name(SUMMARY_SHEET_NAME, sheet_names)
+}//end get_summary_sheet_name()
+
+/// Gets a sheet name based on name which doesn't clash with any of the given
+/// sheet names, ignoring case like excel does. If name is taken, a number is added
+/// to the end, like "name 2", shortening name if needed to stay within excel's limit.
+pub fn get_unique_sheet_name(name: &str, sheet_names: &[&str]) -> String {
+    let is_taken = |name: &str| sheet_names.iter().any(|sheet_name| sheet_name.to_lowercase() == name.to_lowercase());
+    let mut unique_name = name.to_string();
+    let mut index = 2;
+    while is_taken(&unique_name) {
+        let suffix = format!(" {}", index);
+        let base: String = name.chars().take(SHEET_NAME_MAX_LEN.saturating_sub(suffix.len())).collect();
+        unique_name = format!("{}{}", base, suffix);
+        index += 1;
+    }//end trying names until one is free
+    unique_name
+}//end get_unique_sheet_name()
+
+/// Gets a new workbook holding the given sheets, written again from what was kept of them.
+/// Since rust_xlsxwriter can't read xlsx files, this is how a workbook written earlier
+/// is added to: its sheets are written again, and new sheets go after them. This only
+/// works for workbooks written by this program, such as earlier in the same session.
+/// If existing_sheets is empty, this is the same as get_workbook().
+pub fn open_or_create_workbook(existing_sheets: &[SheetRecord]) -> Result<Workbook,XlsxError> {
+    let mut workbook = get_workbook();
+    for record in existing_sheets {
+        match &record.contents {
+            SheetContents::Data(data) => {write_sheet_for_layout(&mut workbook, data, &record.sheet_name, &record.config)?;},
+            SheetContents::Summary(summaries, sheet_ranges) => write_summary_sheet(&mut workbook, summaries, sheet_ranges, &record.sheet_name, &record.config)?,
+            SheetContents::Metadata(metadata) => write_metadata_sheet(&mut workbook, metadata, &record.sheet_name)?,
+        }//end matching the kind of sheet to write
+    }//end writing each existing sheet
+    Ok(workbook)
+}//end open_or_create_workbook()
+
+/// Gets the excel formula for a statistic over the given cell references, such as
+/// from SheetRange::get_header_ref(), like =IFERROR(AVERAGE('Sheet1'!B4:B40),"").
+/// Statistics that can't be calculated are left blank, except counts which can't fail.  
+/// Returns None if there aren't any references.
+pub fn get_summary_formula(stat: SummaryStat, refs: &[String]) -> Option<String> {
+    if refs.is_empty() {return None;}
+    let refs = refs.join(",");
+    let formula = match stat {
+        SummaryStat::Count => return Some(format!("=COUNT({})", refs)),
+        SummaryStat::Mean => format!("AVERAGE({})", refs),
+        SummaryStat::Min => format!("MIN({})", refs),
+        SummaryStat::Max => format!("MAX({})", refs),
+        SummaryStat::StdDev => format!("STDEV({})", refs),
+        SummaryStat::StdErr => format!("STDEV({0})/SQRT(COUNT({0}))", refs),
+        SummaryStat::Cv => format!("STDEV({0})/AVERAGE({0})", refs),
+    };//end matching the stat to a formula
+    Some(format!("=IFERROR({},\"\")", formula))
+}//end get_summary_formula()
+
+/// Writes a sheet with a row for each header, and a column for each
+/// statistic in config summary_stats, in that order.  
+/// If sheet_ranges isn't empty, each statistic is written as a formula over the values
+/// in those ranges, so it updates when they're changed. Headers not found in any range,
+/// or any header when sheet_ranges is empty, get the values calculated in summaries instead.  
+/// Statistics that can't be calculated for a header, such as a standard
+/// deviation with fewer than two values, are left blank. Statistics other than counts
+/// are shown with two decimal places. If there aren't any headers, only the header row is written.
+pub fn write_summary_sheet(workbook: &mut Workbook, summaries: &[HeaderSummary], sheet_ranges: &[SheetRange], sheet_name: &str, config: &ConfigStore) -> Result<(),XlsxError> {
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(sheet_name).map_err(|e| add_write_context(e, format!("sheet name \"{}\"", shorten_for_message(sheet_name))))?;
+
+    let bold = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_HEADER);
+    sheet.write_with_format(HEADER_START_ROW, 0, "Measurement", &bold)?;
+    for (stat_idx, stat) in config.summary_stats.iter().enumerate() {
+        sheet.write_with_format(HEADER_START_ROW, 1 + stat_idx as u16, stat.to_string(), &bold)
+            .map_err(|e| add_write_context(e, format!("summary header \"{}\"", stat.to_string())))?;
+    }//end writing a header for each stat
+
+    let header_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_TEST_NAME);
+    let count_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_DATA);
+    for (summary_idx, summary) in summaries.iter().enumerate() {
+        let row_num = HEADER_START_ROW + 1 + summary_idx as u32;
+        let stat_format = count_format.clone().set_num_format(get_num_format(config.get_decimal_places(&summary.header), config.use_thousands_separator));
+        sheet.write_with_format(row_num, 0, summary.header.clone(), &header_format)
+            .map_err(|e| add_write_context(e, format!("header \"{}\"", shorten_for_message(&summary.header))))?;
+        let refs: Vec<String> = sheet_ranges.iter().filter_map(|sheet_range| sheet_range.get_header_ref(&summary.header)).collect();
+        for (stat_idx, stat) in config.summary_stats.iter().enumerate() {
+            let col = 1 + stat_idx as u16;
+            let format = if *stat == SummaryStat::Count {&count_format} else {&stat_format};
+            match (get_summary_formula(*stat, &refs), summary.get_stat(*stat)) {
+                (Some(formula), _) => sheet.write_formula_with_format(row_num, col, Formula::new(formula), format),
+                (None, Some(value)) => sheet.write_number_with_format(row_num, col, value, format),
+                (None, None) => sheet.write_blank(row_num, col, format),
+            }.map_err(|e| add_write_context(e, format!("{} for header \"{}\"", stat.to_string(), shorten_for_message(&summary.header))))?;
+        }//end writing each stat for this header
+    }//end writing a row for each header
+    Ok(())
+}//end write_summary_sheet()
+
+/// Gets the metadata recorded about an export as pairs of name and value, for
+/// write_metadata_sheet(). This has the time of the export in UTC, the version of
+/// this program, the config profile used, if known, each input file used, the date each
+/// sample was tested, as pairs of test name and date from Data::get_sample_date(), and the
+/// settings that decide how files are read and written, named as in the config file.
+pub fn get_metadata(config: &ConfigStore, inputs: &[String], sample_dates: &[(String,String)], profile_name: Option<&str>, now: OffsetDateTime) -> Vec<(String,String)> {
+    let mut metadata = vec![
+        ("Exported".to_string(), format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC", now.year(), now.month() as u8, now.day(), now.hour(), now.minute(), now.second())),
+        ("Program Version".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+    ];
+    if let Some(profile_name) = profile_name {metadata.push(("Config Profile".to_string(), profile_name.to_string()));}
+    metadata.extend(inputs.iter().map(|input| ("Input File".to_string(), input.clone())));
+    metadata.extend(sample_dates.iter().map(|(test_name, date)| ("Sample Date".to_string(), format!("{}: {}", test_name, date))));
+    let settings = [
+        ("read_start_mode", config.read_start_mode.to_string()),
+        ("read_start_header", config.read_start_header.clone()),
+        ("read_start_idx", config.read_start_idx.to_string()),
+        ("read_end_header", config.read_end_header.clone()),
+        ("read_row_mode", config.read_row_mode.to_string()),
+        ("read_row_headers", config.read_row_headers.join(", ")),
+        ("read_max_rows", config.read_max_rows.to_string()),
+        ("include_headers", config.include_headers.join(", ")),
+        ("output_format", config.output_format.to_string()),
+        ("output_layout", config.output_layout.to_string()),
+        ("non_finite_format", config.non_finite_format.to_string()),
+        ("parse_row_flags", config.parse_row_flags.to_string()),
+        ("decimal_comma", config.decimal_comma.to_string()),
+        ("strip_thousands_separators", config.strip_thousands_separators.to_string()),
+        ("average_all_inputs", config.average_all_inputs.to_string()),
+        ("skip_zero_rows", config.skip_zero_rows.to_string()),
+        ("sheet_per_file", config.sheet_per_file.to_string()),
+        ("include_summary_sheet", config.include_summary_sheet.to_string()),
+        ("include_file_metadata_columns", config.include_file_metadata_columns.to_string()),
+    ];
+    metadata.extend(settings.into_iter().map(|(name, value)| (name.to_string(), value)));
+    metadata
+}//end get_metadata()
+
+/// Writes metadata from get_metadata() to a new sheet, as a table with a
+/// column of names and a column of values.
+pub fn write_metadata_sheet(workbook: &mut Workbook, metadata: &[(String,String)], sheet_name: &str) -> Result<(),XlsxError> {
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(sheet_name).map_err(|e| add_write_context(e, format!("sheet name \"{}\"", shorten_for_message(sheet_name))))?;
+
+    let bold = Format::new()
+        .set_bold()
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_HEADER);
+    sheet.write_with_format(HEADER_START_ROW, 0, "Name", &bold)?;
+    sheet.write_with_format(HEADER_START_ROW, 1, "Value", &bold)?;
+    let default_format = Format::new()
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_DATA);
+    for (idx, (name, value)) in metadata.iter().enumerate() {
+        let row_num = HEADER_START_ROW + 1 + idx as u32;
+        sheet.write_string_with_format(row_num, 0, name, &default_format)
+            .map_err(|e| add_write_context(e, format!("metadata \"{}\"", shorten_for_message(name))))?;
+        sheet.write_string_with_format(row_num, 1, value, &default_format)
+            .map_err(|e| add_write_context(e, format!("metadata \"{}\"", shorten_for_message(name))))?;
+    }//end writing a row for each pair
+    let mut table = vec![vec!["Name".to_string(), "Value".to_string()]];
+    table.extend(metadata.iter().map(|(name, value)| vec![name.clone(), value.clone()]));
+    for (col, width) in get_column_widths(&table).into_iter().enumerate() {
+        sheet.set_column_width(col as u16, width)?;
+    }//end fitting each column to its contents
+    Ok(())
+}//end write_metadata_sheet()
+
+/// Gets the excel number format for showing values with the given number of
+/// decimal places, such as "0.000" for 3, clamped to MAX_DECIMAL_PLACES.  
+/// If thousands_separator is true, the whole part is grouped, such as "#,##0.000" for 3.
+pub fn get_num_format(decimal_places: u8, thousands_separator: bool) -> String {
+    let whole_part = if thousands_separator {"#,##0"} else {"0"};
+    match decimal_places.min(MAX_DECIMAL_PLACES) {
+        0 => whole_part.to_string(),
+        places => format!("{}.{}", whole_part, "0".repeat(places as usize)),
+    }//end matching whether there's a decimal point
+}//end get_num_format()
+
+/// Gets base_format with the number format for values of header, which shows
+/// the decimal places from ConfigStore::get_decimal_places().
+fn get_value_num_format(base_format: &Format, header: &str, config: &ConfigStore) -> Format {
+    base_format.clone().set_num_format(get_num_format(config.get_decimal_places(header), config.use_thousands_separator))
+}//end get_value_num_format()
+
+/// Writes a single value to a cell, following config non_finite_format for
+/// values that excel can't represent as numbers. Numbers are written with
+/// default_format, which should already have the number format for their
+/// header from get_value_num_format().  
+/// Values skipped by config skip_zero_rows are left blank.
+fn write_value_cell(sheet: &mut Worksheet, row: u32, col: u16, value: f64, default_format: &Format, flagged_format: &Format, config: &ConfigStore) -> Result<(),XlsxError> {
+    if config.is_skipped_zero(value) {
+        sheet.write_blank(row, col, default_format)?;
+    } else if value.is_finite() {
+        sheet.write_number_with_format(row, col, value, default_format)?;
+    } else {
+        match config.non_finite_format {
+            NonFiniteFormat::Blank => sheet.write_blank(row, col, default_format)?,
+            NonFiniteFormat::Text => sheet.write_string_with_format(row, col, non_finite_label(value), default_format)?,
+            NonFiniteFormat::Flagged => sheet.write_string_with_format(row, col, non_finite_label(value), flagged_format)?,
+        };
+    }//end else we need to handle a value that excel can't represent
+    Ok(())
+}//end write_value_cell()
+
+/// Writes data to a new sheet in the pivot layout, with a row for each measurement
+/// and a group of columns for each input file. The shape looks like this:
+/// ```text
+/// | Measurement | Sample001        | Sample002        |
+/// |             | Value   | Flag   | Value   | Flag   |
+/// | P           | 1       | OK     | 4       |        |
+/// | L           | 2       | LOW    |         |        |
+/// ```
+/// The "Measurement" header is merged down over both header rows, and each test name
+/// is merged across its group. The Flag columns and the second header row are only written
+/// if config parse_row_flags is set. Otherwise, it's a plain grid, with the test names across
+/// the first row and a single column of values under each. Measurements missing from a file are left blank.
+pub fn write_pivot_to_sheet(workbook: &mut Workbook, data: &Vec<Data>, sheet_name: &str, config: &ConfigStore) -> Result<(),XlsxError> {
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(sheet_name).map_err(|e| add_write_context(e, format!("sheet name \"{}\"", shorten_for_message(sheet_name))))?;
+    if data.len() < 1 {return Ok(());}
+
+    let bold = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_HEADER);
+    let cols_per_file: u16 = if config.parse_row_flags {2} else {1};
+    // the Value and Flag labels are only needed when there's a flag column to tell apart
+    let header_rows: u32 = if config.parse_row_flags {2} else {1};
+    if header_rows > 1 {sheet.merge_range(HEADER_START_ROW, 0, HEADER_START_ROW + 1, 0, "Measurement", &bold)?;}
+    else {sheet.write_with_format(HEADER_START_ROW, 0, "Measurement", &bold)?;}
+    for (file_idx, data_file) in data.iter().enumerate() {
+        let first_col = 1 + file_idx as u16 * cols_per_file;
+        // excel doesn't allow merging a single cell
+        let test_name_context = || format!("test name \"{}\"", shorten_for_message(&data_file.test_name));
+        if cols_per_file > 1 {sheet.merge_range(HEADER_START_ROW, first_col, HEADER_START_ROW, first_col + cols_per_file - 1, &data_file.test_name, &bold).map_err(|e| add_write_context(e, test_name_context()))?;}
+        else {sheet.write_with_format(HEADER_START_ROW, first_col, data_file.test_name.clone(), &bold).map_err(|e| add_write_context(e, test_name_context()))?;}
+        if config.parse_row_flags {
+            sheet.write_with_format(HEADER_START_ROW + 1, first_col, "Value", &bold)?;
+            sheet.write_with_format(HEADER_START_ROW + 1, first_col + 1, "Flag", &bold)?;
+        }//end if each group has a flag column
+    }//end writing the header for each file's group
+
+    let measurement_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_TEST_NAME);
+    let default_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_DATA);
+    let flagged_format = get_flagged_format(&default_format, config);
+    let out_of_range_format = get_out_of_range_format(&default_format, config);
+    for (header_idx, header) in get_pivot_row_headers(data).iter().enumerate() {
+        let range = config.value_ranges.get(header);
+        let row_num = HEADER_START_ROW + header_rows + header_idx as u32;
+        sheet.write_with_format(row_num, 0, header.clone(), &measurement_format)
+            .map_err(|e| add_write_context(e, format!("header \"{}\"", shorten_for_message(header))))?;
+        for (file_idx, data_file) in data.iter().enumerate() {
+            let col = 1 + file_idx as u16 * cols_per_file;
+            let row = data_file.row_data.iter().find(|row| row.header.eq(header));
+            let cell_context = || format!("test \"{}\" header \"{}\"", shorten_for_message(&data_file.test_name), shorten_for_message(header));
+            match row {
+                Some(row) => {
+                    let out_of_range = !config.is_skipped_zero(row.value) && range.is_some_and(|range| range.is_out_of_range(row.value));
+                    let value_format = get_value_num_format(if out_of_range {&out_of_range_format} else {&default_format}, header, config);
+                    write_value_cell(sheet, row_num, col, row.value, &value_format, &flagged_format, config)
+                },
+                None => sheet.write_blank(row_num, col, &default_format).map(|_| ()),
+            }.map_err(|e| add_write_context(e, cell_context()))?;
+            if config.parse_row_flags {
+                match row.and_then(|row| row.flag.as_ref()) {
+                    Some(flag) => sheet.write_string_with_format(row_num, col + 1, flag, &default_format),
+                    None => sheet.write_blank(row_num, col + 1, &default_format),
+                }.map_err(|e| add_write_context(e, format!("flag for {}", cell_context())))?;
+            }//end if we need to write the flag next to the value
+        }//end writing each file's value for this measurement
+    }//end writing a row for each measurement
+
+    // constant columns become rows at the bottom, with the value under each file
+    let first_constant_row = HEADER_START_ROW + header_rows + get_pivot_row_headers(data).len() as u32;
+    for (constant_idx, (header, value)) in get_constant_columns(config, OffsetDateTime::now_utc()).iter().enumerate() {
+        let row_num = first_constant_row + constant_idx as u32;
+        let constant_context = || format!("constant column \"{}\"", shorten_for_message(header));
+        sheet.write_with_format(row_num, 0, header.clone(), &measurement_format).map_err(|e| add_write_context(e, constant_context()))?;
+        for file_idx in 0..data.len() {
+            let col = 1 + file_idx as u16 * cols_per_file;
+            sheet.write_with_format(row_num, col, value.clone(), &default_format).map_err(|e| add_write_context(e, constant_context()))?;
+        }//end writing the value under each file
+    }//end writing a row for each constant column
+
+    sheet.set_column_width(0, 14.5)?;
+
+    Ok(())
+}//end write_pivot_to_sheet()
+
+/// Writes data to a new sheet in the merged layout, with every file stacked in one
+/// table that works well with excel pivot tables. The shape looks like this:
+/// ```text
+/// | Source File | Test Name | P  | L  | G  |
+/// | a.txt       | Sample001 | 1  | 2  |    |
+/// | b.txt       | Sample002 | 4  |    | 3  |
+/// ```
+/// There's a column for every header in any of the files, and headers missing from a
+/// file are left blank. Flag columns follow each value if config parse_row_flags is set.
+pub fn write_merged_sheet(workbook: &mut Workbook, data: &Vec<Data>, sheet_name: &str, config: &ConfigStore) -> Result<(),XlsxError> {
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(sheet_name).map_err(|e| add_write_context(e, format!("sheet name \"{}\"", shorten_for_message(sheet_name))))?;
+    if data.len() < 1 {return Ok(());}
+    let data = &order_columns(data, config);
+    let headers = get_pivot_row_headers(data);
+
+    let bold = Format::new()
+        .set_bold()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_HEADER);
+    let cols_per_header: u16 = if config.parse_row_flags {2} else {1};
+    sheet.write_with_format(HEADER_START_ROW, 0, SOURCE_FILE_HEADER, &bold)?;
+    sheet.write_with_format(HEADER_START_ROW, 1, "Test Name", &bold)?;
+    for (header_idx, header) in headers.iter().enumerate() {
+        let col = 2 + header_idx as u16 * cols_per_header;
+        let header_context = || format!("header \"{}\"", shorten_for_message(header));
+        let display_header = get_display_header(header, config);
+        sheet.write_with_format(HEADER_START_ROW, col, display_header.clone(), &bold).map_err(|e| add_write_context(e, header_context()))?;
+        if config.parse_row_flags {
+            sheet.write_with_format(HEADER_START_ROW, col + 1, get_flag_column_header(&display_header), &bold).map_err(|e| add_write_context(e, header_context()))?;
+        }//end if we need a header for the flag column
+    }//end writing each header
+
+    let test_name_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_TEST_NAME);
+    let default_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_border(BORDER_FORMAT)
+        .set_font_size(FONT_SIZE_DATA);
+    let flagged_format = get_flagged_format(&default_format, config);
+    let out_of_range_format = get_out_of_range_format(&default_format, config);
+    for (data_idx, data_file) in data.iter().enumerate() {
+        let row_num = HEADER_START_ROW + 1 + data_idx as u32;
+        let test_context = || format!("test name \"{}\"", shorten_for_message(&data_file.test_name));
+        match &data_file.source_file {
+            Some(source_file) => sheet.write_string_with_format(row_num, 0, source_file, &default_format),
+            None => sheet.write_blank(row_num, 0, &default_format),
+        }.map_err(|e| add_write_context(e, format!("source file for {}", test_context())))?;
+        sheet.write_with_format(row_num, 1, data_file.test_name.clone(), &test_name_format).map_err(|e| add_write_context(e, test_context()))?;
+        for (header_idx, header) in headers.iter().enumerate() {
+            let col = 2 + header_idx as u16 * cols_per_header;
+            let row = data_file.row_data.iter().find(|row| row.header.eq(header));
+            let cell_context = || format!("test \"{}\" header \"{}\"", shorten_for_message(&data_file.test_name), shorten_for_message(header));
+            match row {
+                Some(row) => {
+                    let out_of_range = !config.is_skipped_zero(row.value) && config.value_ranges.get(header).is_some_and(|range| range.is_out_of_range(row.value));
+                    let value_format = get_value_num_format(if out_of_range {&out_of_range_format} else {&default_format}, header, config);
+                    write_value_cell(sheet, row_num, col, row.value, &value_format, &flagged_format, config)
+                },
+                None => sheet.write_blank(row_num, col, &default_format).map(|_| ()),
+            }.map_err(|e| add_write_context(e, cell_context()))?;
+            if config.parse_row_flags {
+                match row.and_then(|row| row.flag.as_ref()) {
+                    Some(flag) => sheet.write_string_with_format(row_num, col + 1, flag, &default_format),
+                    None => sheet.write_blank(row_num, col + 1, &default_format),
+                }.map_err(|e| add_write_context(e, format!("flag for {}", cell_context())))?;
+            }//end if we need to write the flag next to the value
+        }//end writing the value for each header
+    }//end writing a row for each data file
+
+    if config.excel_freeze_and_filter && !headers.is_empty() {
+        sheet.set_freeze_panes(HEADER_START_ROW + 1, 0)?;
+        sheet.autofilter(HEADER_START_ROW, 0, HEADER_START_ROW + data.len() as u32, 1 + headers.len() as u16 * cols_per_header)?;
+    }//end if the header row should be frozen and filtered
+
+    for (col, width) in get_column_widths(&get_merged_table(data, config)).iter().enumerate() {
+        sheet.set_column_width(col as u16, *width)?;
+    }//end fitting each column to its contents
+    Ok(())
+}//end write_merged_sheet()
+
 /// Writes output from another function to a workbook that has already
 /// been created. After you're done calling this function (however many times),  
-/// make sure to call process::close_workbook().
-pub fn write_output_to_sheet(workbook: &mut Workbook, data: &Vec<Data>, sheet_name: &str) -> Result<(),XlsxError> {
+/// make sure to call process::close_workbook().  
+/// Gives back where the values were written, or None if there wasn't any data.
+pub fn write_output_to_sheet(workbook: &mut Workbook, data: &Vec<Data>, sheet_name: &str, config: &ConfigStore) -> Result<Option<SheetRange>,XlsxError> {
     let sheet = workbook.add_worksheet();//workbook.create_sheet(sheet_name);
-    sheet.set_name(sheet_name)?;
-    if data.len() < 1 {return Ok(());}
+    sheet.set_name(sheet_name).map_err(|e| add_write_context(e, format!("sheet name \"{}\"", shorten_for_message(sheet_name))))?;
+    if data.len() < 1 {return Ok(None);}
+    let data = &order_columns(data, config);
 
     // write the header row
     let bold = Format::new()
@@ -40,10 +1712,26 @@ pub fn write_output_to_sheet(workbook: &mut Workbook, data: &Vec<Data>, sheet_na
         .set_align(FormatAlign::Center)
         .set_border(BORDER_FORMAT)
         .set_font_size(FONT_SIZE_HEADER);
+    // when flags are parsed, each value column is followed by a flag column
+    let cols_per_row: u16 = if config.parse_row_flags {2} else {1};
     sheet.write_with_format(HEADER_START_ROW,0,"Test Name", &bold)?;
+    // any metadata columns go between the test name and the values
+    let metadata_keys = get_metadata_columns(data, config);
+    let first_value_col = 1 + metadata_keys.len() as u16;
+    for (key_idx, key) in metadata_keys.iter().enumerate() {
+        sheet.write_with_format(HEADER_START_ROW, 1 + key_idx as u16, key.clone(), &bold)
+            .map_err(|e| add_write_context(e, format!("metadata column \"{}\"", shorten_for_message(key))))?;
+    }//end writing each metadata header
+    let mut header_cols = Vec::new();
     for (index, row) in data.first().expect("already checked").row_data.iter().enumerate() {
-        let index = index as u16;
-        sheet.write_with_format(HEADER_START_ROW,index + 1, row.header.clone(),&bold)?;
+        let col = first_value_col + index as u16 * cols_per_row;
+        header_cols.push((row.header.clone(), col));
+        let header_context = || format!("header \"{}\"", shorten_for_message(&row.header));
+        let display_header = get_display_header(&row.header, config);
+        sheet.write_with_format(HEADER_START_ROW,col, display_header.clone(),&bold).map_err(|e| add_write_context(e, header_context()))?;
+        if config.parse_row_flags {
+            sheet.write_with_format(HEADER_START_ROW,col + 1, get_flag_column_header(&display_header),&bold).map_err(|e| add_write_context(e, header_context()))?;
+        }//end if we need a header for the flag column
     }//end writing each row header
 
     let test_name_format = Format::new()
@@ -54,17 +1742,89 @@ pub fn write_output_to_sheet(workbook: &mut Workbook, data: &Vec<Data>, sheet_na
         .set_align(FormatAlign::Center)
         .set_border(BORDER_FORMAT)
         .set_font_size(FONT_SIZE_DATA);
-    let mut row_num = HEADER_START_ROW + 1;
+    let flagged_format = get_flagged_format(&default_format, config);
+    let out_of_range_format = get_out_of_range_format(&default_format, config);
+    let first_data_row = get_first_data_row(config);
+    // write the units row, with a blank cell for each column without a unit
+    if !config.header_units.is_empty() {
+        let units_format = default_format.clone().set_italic();
+        let units_row = HEADER_START_ROW + 1;
+        let last_col = get_autofilter_range(data, config).map(|(_,_,_,last_col)| last_col).unwrap_or(0);
+        for col in 0..=last_col {sheet.write_blank(units_row, col, &units_format)?;}
+        for (header, col) in header_cols.iter() {
+            if let Some(unit) = config.header_units.get(header) {
+                sheet.write_string_with_format(units_row, *col, unit, &units_format)
+                    .map_err(|e| add_write_context(e, format!("unit for header \"{}\"", shorten_for_message(header))))?;
+            }//end if there's a unit for this header
+        }//end writing the unit of each header
+    }//end if there should be a units row
+    let mut row_num = first_data_row;
     for data_file in data {
-        sheet.write_with_format(row_num,0,data_file.test_name.clone(), &test_name_format)?;
+        sheet.write_with_format(row_num,0,data_file.test_name.clone(), &test_name_format)
+            .map_err(|e| add_write_context(e, format!("test name \"{}\"", shorten_for_message(&data_file.test_name))))?;
+        for (key_idx, key) in metadata_keys.iter().enumerate() {
+            let metadata_context = || format!("test \"{}\" metadata \"{}\"", shorten_for_message(&data_file.test_name), shorten_for_message(key));
+            match data_file.get_metadata_value(key) {
+                Some(value) => sheet.write_string_with_format(row_num, 1 + key_idx as u16, value, &default_format),
+                None => sheet.write_blank(row_num, 1 + key_idx as u16, &default_format),
+            }.map_err(|e| add_write_context(e, metadata_context()))?;
+        }//end writing each metadata value for this test
         for (col_offset,row) in data_file.row_data.iter().enumerate() {
-            let col_offset = col_offset as u16;
-            sheet.write_number_with_format(row_num,1+col_offset,row.value, &default_format)?;
+            let col_offset = first_value_col - 1 + col_offset as u16 * cols_per_row;
+            let cell_context = || format!("test \"{}\" header \"{}\"", shorten_for_message(&data_file.test_name), shorten_for_message(&row.header));
+            let out_of_range = !config.is_skipped_zero(row.value) && config.value_ranges.get(&row.header).is_some_and(|range| range.is_out_of_range(row.value));
+            let value_format = get_value_num_format(if out_of_range {&out_of_range_format} else {&default_format}, &row.header, config);
+            write_value_cell(sheet, row_num, 1+col_offset, row.value, &value_format, &flagged_format, config)
+                .map_err(|e| add_write_context(e, cell_context()))?;
+            if config.parse_row_flags {
+                match &row.flag {
+                    Some(flag) => sheet.write_string_with_format(row_num, 2+col_offset, flag, &default_format),
+                    None => sheet.write_blank(row_num, 2+col_offset, &default_format),
+                }.map_err(|e| add_write_context(e, format!("flag for {}", cell_context())))?;
+            }//end if we need to write the flag next to the value
         }//end looping over each row of data to place in a column
         row_num += 1;
     }//end looping over each data file
 
-    sheet.set_column_width(0, 14.5)?;
+    // the source file and constant columns go after the widest row of data
+    let mut first_constant_col = first_value_col + data.iter().map(|d| d.row_data.len()).max().unwrap_or(0) as u16 * cols_per_row;
+    if config.include_source_file_column {
+        let source_context = || "source file column".to_string();
+        sheet.write_with_format(HEADER_START_ROW, first_constant_col, SOURCE_FILE_HEADER, &bold).map_err(|e| add_write_context(e, source_context()))?;
+        for (data_idx, data_file) in data.iter().enumerate() {
+            let data_row_num = first_data_row + data_idx as u32;
+            match &data_file.source_file {
+                Some(source_file) => sheet.write_string_with_format(data_row_num, first_constant_col, source_file, &default_format),
+                None => sheet.write_blank(data_row_num, first_constant_col, &default_format),
+            }.map_err(|e| add_write_context(e, source_context()))?;
+        }//end writing the source file for each row of data
+        first_constant_col += 1;
+    }//end if we should write where each row came from
+    let constant_columns = get_constant_columns(config, OffsetDateTime::now_utc());
+    for (constant_idx, (header, value)) in constant_columns.iter().enumerate() {
+        let col = first_constant_col + constant_idx as u16;
+        let constant_context = || format!("constant column \"{}\"", shorten_for_message(header));
+        sheet.write_with_format(HEADER_START_ROW, col, header.clone(), &bold).map_err(|e| add_write_context(e, constant_context()))?;
+        for data_row_num in first_data_row..row_num {
+            sheet.write_with_format(data_row_num, col, value.clone(), &default_format).map_err(|e| add_write_context(e, constant_context()))?;
+        }//end writing the value for each row of data
+    }//end writing each constant column
 
-    Ok(())
+    if config.excel_freeze_and_filter {
+        sheet.set_freeze_panes(first_data_row, 0)?;
+        if let Some((first_row, first_col, last_row, last_col)) = get_autofilter_range(data, config) {
+            sheet.autofilter(first_row, first_col, last_row, last_col)?;
+        }//end if there are headers to filter on
+    }//end if the header row should be frozen and filtered
+
+    for (col, width) in get_column_widths(&get_wide_table(data, config)).iter().enumerate() {
+        sheet.set_column_width(col as u16, *width)?;
+    }//end fitting each column to its contents
+
+    Ok(Some(SheetRange {
+        sheet_name: sheet_name.to_string(),
+        first_row: first_data_row,
+        last_row: row_num - 1,
+        header_cols,
+    }))
 }//end write_output_to_sheet()
@@ -1,5 +1,8 @@
 use crate::config_store::ConfigStore;
 
+/// The tolerance for comparing parsed or converted values in tests.
+const TEST_EPSILON: f64 = 1e-9;
+
 /// Sample lines from a made-up file, to be used for testing.
 pub fn sample_file_lines() -> Vec<String> {
     vec![
@@ -20,6 +23,45 @@ pub fn sample_file_lines() -> Vec<String> {
     ].iter().map(|s| s.to_string()).collect()
 }//end sample_file_lines
 
+/// Sample lines from a made-up file with junk lines at the start and end
+/// of the data block, to be used for testing skip_rows settings.
+pub fn sample_file_lines_with_junk() -> Vec<String> {
+    vec![
+        "Test name\t:\tSample002-7654321",
+        "wiuuebwfw fwefwef",
+        "Standard\t : \tAverage",
+        "Summary\tof curves",
+        "---------",
+        "P\t1",
+        "L\t2",
+        "G\t3",
+        "Dmax\t-----",
+    ].iter().map(|s| s.to_string()).collect()
+}//end sample_file_lines_with_junk()
+
+/// Sample contents of a made-up file that starts with a UTF-8 byte order
+/// mark, immediately followed by the start header.
+pub fn sample_file_contents_with_bom() -> String {
+    vec![
+        "\u{FEFF}Standard\t : \tAverage",
+        "P\t1",
+        "L\t2",
+        "G\t3",
+        "Test name\t:\tSample003-1111111",
+    ].join("\n")
+}//end sample_file_contents_with_bom()
+
+/// Sample lines from a made-up file with no start header, where every
+/// line is a data row.
+pub fn sample_file_lines_markerless() -> Vec<String> {
+    vec![
+        "Dmax\t-----",
+        "P\t1",
+        "L\t2",
+        "G\t3",
+    ].iter().map(|s| s.to_string()).collect()
+}//end sample_file_lines_markerless()
+
 /// Sample configuration settings, to be used with
 /// sample file from crate::test::sample_file_lines().
 pub fn sample_config() -> ConfigStore {
@@ -33,6 +75,7 @@ pub fn sample_config() -> ConfigStore {
         row_order_preference: vec!["G","L","P"].iter().map(|s| s.to_string()).collect(),
         read_row_split_char: "\t".to_string(),
         read_test_name_prefix: "Test name\t:\t".to_string(),
+        ..ConfigStore::default()
     }//end struct construction
 }//end sample_config()
 
@@ -54,13 +97,89 @@ pub fn data_get_header_idx_from_lines1() {
     let file_lines = sample_file_lines();
     let config = sample_config();
     let header_idx = crate::data::get_header_idx_from_lines(
-        "sample-filename",
         &file_lines,
         &config
     ).unwrap();
     assert_eq!(header_idx, 7);
 }//end data_get_header_idx_from_lines1
 
+/// Test for crate::data::get_header_idx_from_lines() and crate::data::get_end_idx_from_lines()
+/// with normalize_header_match, using markers with and without trailing colons.
+#[test]
+pub fn data_get_header_idx_from_lines_normalized() {
+    let mut file_lines = sample_file_lines();
+    file_lines[7] = "Standard  Average:".to_string();
+    file_lines[11] = "End of results :\t".to_string();
+    let mut config = sample_config();
+    config.read_start_header = "Standard\tAverage".to_string();
+    config.read_end_header = "End of results:".to_string();
+
+    // exact matching doesn't find either marker
+    assert!(crate::data::get_header_idx_from_lines(&file_lines, &config).is_err());
+    assert_eq!(crate::data::get_end_idx_from_lines(7, &file_lines, &config), None);
+
+    config.normalize_header_match = true;
+    let header_idx = crate::data::get_header_idx_from_lines(
+        &file_lines,
+        &config
+    ).unwrap();
+    assert_eq!(header_idx, 7);
+    assert_eq!(crate::data::get_end_idx_from_lines(header_idx, &file_lines, &config), Some(11));
+
+    // normalizing doesn't ignore differences in the words themselves
+    config.read_start_header = "Standard Averages".to_string();
+    assert!(crate::data::get_header_idx_from_lines(&file_lines, &config).is_err());
+}//end data_get_header_idx_from_lines_normalized()
+
+/// Test for crate::data::read_data_from_file() with header_match_case_insensitive,
+/// with start and end headers that differ in case and surrounding whitespace.
+#[test]
+pub fn data_read_data_from_file_case_insensitive() {
+    let mut file_lines = sample_file_lines();
+    file_lines[7] = "  STANDARD\t : \taverage ".to_string();
+    file_lines[10] = "end of data".to_string();
+    let mut config = sample_config();
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 10;
+    config.read_end_header = "End Of Data".to_string();
+
+    // exact matching keeps the old behavior, so the start header isn't found
+    assert!(crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).is_err());
+
+    // rows stop at the end header, instead of reading junk until the end of the file
+    config.header_match_case_insensitive = true;
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    assert!(errs.len() == 0);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("L".to_string(),2.),
+        crate::data::Row::new("P".to_string(),1.),
+    ]);
+}//end data_read_data_from_file_case_insensitive()
+
+/// Test for crate::data::normalize_header(), and reading with config normalize_headers
+#[test]
+pub fn data_normalize_header1() {
+    assert_eq!(crate::data::normalize_header("  P  "), "P");
+    assert_eq!(crate::data::normalize_header("P\u{A0}/L"), "P /L");
+    assert_eq!(crate::data::normalize_header("Dmax \t\u{A0} 2"), "Dmax 2");
+    assert_eq!(crate::data::normalize_header("\u{FEFF}W:"), "W:");
+    assert_eq!(crate::data::normalize_header("\u{A0}"), "");
+
+    let mut config = sample_config();
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.row_order_preference = Vec::new();
+    let mut file_lines = sample_file_lines();
+    file_lines[8] = "P\u{A0}\u{A0}/L\t1".to_string();
+    file_lines[9] = "P  /L \t2".to_string();
+    let (data, _) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    let headers: Vec<&str> = data.row_data.iter().map(|row| row.header.as_str()).collect();
+    assert_ne!(headers[0], headers[1]);
+    config.normalize_headers = true;
+    let (data, _) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    let headers: Vec<&str> = data.row_data.iter().map(|row| row.header.as_str()).collect();
+    assert_eq!(headers, vec!["P /L", "P /L", "G"]);
+}//end data_normalize_header1()
+
 /// Test 1 for crate::data::read_data_from_file()
 #[test]
 pub fn data_read_data_from_file() {
@@ -72,17 +191,143 @@ pub fn data_read_data_from_file() {
         &config
     ).unwrap();
     assert!(errs.len() == 0);
-    let correct_data = crate::data::Data::new1(
+    let correct_data = crate::data::Data::new2(
         "Sample001-1234567".to_string(),
         vec![
             crate::data::Row::new("G".to_string(),3.),
             crate::data::Row::new("L".to_string(),2.),
             crate::data::Row::new("P".to_string(),1.),
         ],
+        Some("sample-filename".to_string()),
     );
-    assert_eq!(data, correct_data);
+    assert!(data.approx_eq(&correct_data, TEST_EPSILON), "{:?}", data);
 }//end data_read_data_from_file()
 
+/// Test for crate::data::read_data_from_file() with a file that has no test name line,
+/// so the test name should come from the file name.
+#[test]
+pub fn data_read_data_from_file_no_test_name() {
+    let config = sample_config();
+    let contents = include_str!("../tests/fixtures/no_test_name.txt");
+    let (data, errs) = crate::data::read_data_from_file("24-PCF4001.txt", contents, &config).unwrap();
+    assert_eq!(errs, vec![crate::data::DataParseError::NoTestName]);
+    assert_eq!(data.test_name, "24-PCF4001");
+    assert!(data.test_name_derived);
+    assert_eq!(data.row_data.len(), 3);
+
+    // a prefix with nothing after it shouldn't count as a test name
+    let contents = contents.replacen("awferajaegkagn", "Test name\t:\t", 1);
+    let (data, errs) = crate::data::read_data_from_file("24-PCF4002", &contents, &config).unwrap();
+    assert_eq!(errs, vec![crate::data::DataParseError::NoTestName]);
+    assert_eq!(data.test_name, "24-PCF4002");
+    assert!(data.test_name_derived);
+
+    let (data, _) = crate::data::read_data_from_file("sample-filename", &sample_file_lines().join("\n"), &config).unwrap();
+    assert!(!data.test_name_derived);
+    assert_eq!(crate::data::get_test_name_from_filename("dir/.txt"), ".txt");
+}//end data_read_data_from_file_no_test_name()
+
+/// Test for crate::data::get_test_name_from_lines() with config read_test_name_alt_prefixes
+#[test]
+pub fn data_get_test_name_from_lines_alt_prefixes() {
+    let mut config = sample_config();
+    let lines: Vec<String> = vec!["Operator: Jane", "Sample: 24-PCF4001 ", "Test ID = 24-PCF4002"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(crate::data::get_test_name_from_lines(&lines, &config), None);
+    config.read_test_name_alt_prefixes = vec!["".to_string(), "Test ID =".to_string(), "Sample:".to_string()];
+    assert_eq!(config.get_test_name_prefixes(), vec!["Test name\t:\t", "Test ID =", "Sample:"]);
+    // the first line with any prefix is used, whichever prefix it has
+    assert_eq!(crate::data::get_test_name_from_lines(&lines, &config), Some("24-PCF4001".to_string()));
+    assert_eq!(crate::data::get_test_name_from_lines(&lines[2..].to_vec(), &config), Some("24-PCF4002".to_string()));
+    // lines with a test name aren't metadata
+    assert_eq!(crate::data::get_metadata_from_lines(&lines, &config), vec![("Operator".to_string(), "Jane".to_string())]);
+}//end data_get_test_name_from_lines_alt_prefixes()
+
+/// Test for crate::data::read_data_from_file() with no start header, reading
+/// data from the first line, along with skip_rows settings.
+#[test]
+pub fn data_read_data_from_file_markerless() {
+    let file_contents = sample_file_lines_markerless().join("\n");
+    let mut config = sample_config();
+    config.read_start_header = "".to_string();
+    config.read_test_name_prefix = "".to_string();
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 10;
+
+    // an empty start header is still an error unless the setting is on
+    assert!(config.validate().is_err());
+    config.empty_start_header_reads_from_start = true;
+    assert!(config.validate().is_ok());
+
+    // the junk first line can't be parsed, but everything else is read
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_contents, &config).unwrap();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("G".to_string(),3.),
+        crate::data::Row::new("L".to_string(),2.),
+        crate::data::Row::new("P".to_string(),1.),
+    ]);
+
+    // skipping rows counts from the first line of the file
+    config.skip_rows_after_start = 1;
+    config.skip_rows_before_end = 1;
+    config.read_row_mode = crate::config_store::ReadRowMode::Header;
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_contents, &config).unwrap();
+    assert!(errs.len() == 0);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("L".to_string(),2.),
+        crate::data::Row::new("P".to_string(),1.),
+    ]);
+}//end data_read_data_from_file_markerless()
+
+/// Test for the crate::data::DataParseError variants given by crate::data::read_data_from_file()
+#[test]
+pub fn data_read_data_from_file_errors() {
+    use crate::data::DataParseError;
+    let mut config = sample_config();
+    assert_eq!(crate::data::read_data_from_file("sample-filename", " \n\n", &config), Err(DataParseError::EmptyFile));
+
+    let mut file_lines = sample_file_lines();
+    file_lines[7] = "Standard Average".to_string();
+    let err = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap_err();
+    assert_eq!(err, DataParseError::StartHeaderNotFound{header: "Standard\t : \tAverage".to_string()});
+    assert_eq!(err.to_string(), "Could not find the header str \"Standard\t : \tAverage\".");
+
+    // the value for "L" is on the 10th line of the file
+    let mut file_lines = sample_file_lines();
+    file_lines[1] = "Test id\tSample001-1234567".to_string();
+    file_lines[9] = "L\t2.x".to_string();
+    config.read_end_header = "not in the file".to_string();
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    assert_eq!(errs, vec![
+        DataParseError::NoTestName,
+        DataParseError::EndHeaderNotFound{header: "not in the file".to_string()},
+        DataParseError::InvalidNumber{line: 10, raw: "2.x".to_string()},
+    ]);
+    assert_eq!(data.row_data.len(), 2);
+
+    config.read_end_header = "".to_string();
+    file_lines[9] = "G\t3".to_string();
+    let (_, errs) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    assert_eq!(errs[1], DataParseError::UnexpectedRow{line: 10, expected: "L".to_string(), text: "G\t3".to_string()});
+}//end data_read_data_from_file_errors()
+
+/// Test for crate::data::read_data_from_files(), with one file that can't be read
+#[test]
+pub fn data_read_data_from_files1() {
+    let config = sample_config();
+    let files = vec![
+        ("first.txt".to_string(), sample_file_lines().join("\n")),
+        ("empty.txt".to_string(), "".to_string()),
+        ("junk.txt".to_string(), sample_file_lines_with_junk().join("\n")),
+    ];
+    let (data, errs) = crate::data::read_data_from_files(&files, &config);
+    assert_eq!(data.iter().map(|d| d.test_name.as_str()).collect::<Vec<&str>>(), vec!["Sample001-1234567", "Sample002-7654321"]);
+    assert_eq!(errs[0], ("empty.txt".to_string(), crate::data::DataParseError::EmptyFile));
+    // the junk rows break the row pattern, but the file is still read
+    assert!(errs[1..].iter().all(|(filename,_)| filename == "junk.txt"));
+    assert_eq!(errs.len(), 2);
+}//end data_read_data_from_files1()
+
 /// Test 1 for crate::data::sort_row_data()
 #[test]
 pub fn data_sort_row_data1() {
@@ -104,3 +349,2308 @@ pub fn data_sort_row_data1() {
     );
     assert_eq!(correct_sorted_row_data,sorted_row_data);
 }//end data_sort_row_data1
+
+/// Test 1 for crate::data::get_data_block_range()
+#[test]
+pub fn data_get_data_block_range1() {
+    let mut config = sample_config();
+    assert_eq!(crate::data::get_data_block_range(2, 9, &config), 3..9);
+    config.skip_rows_after_start = 2;
+    config.skip_rows_before_end = 1;
+    assert_eq!(crate::data::get_data_block_range(2, 9, &config), 5..8);
+    config.skip_rows_before_end = 20;
+    assert_eq!(crate::data::get_data_block_range(2, 9, &config), 5..5);
+    config.skip_rows_after_start = 0;
+    config.skip_rows_before_end = 0;
+    config.start_header_is_data = true;
+    assert_eq!(crate::data::get_data_block_range(2, 9, &config), 2..9);
+}//end data_get_data_block_range1()
+
+/// Test 2 for crate::data::read_data_from_file(), skipping junk rows in the data block
+#[test]
+pub fn data_read_data_from_file_skip_rows() {
+    let file_lines = sample_file_lines_with_junk();
+    let mut config = sample_config();
+    config.read_row_headers = vec!["P","L","G","Dmax"].iter().map(|s| s.to_string()).collect();
+    config.skip_rows_after_start = 2;
+    config.skip_rows_before_end = 1;
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &(file_lines.join("\n") + "\n"),
+        &config
+    ).unwrap();
+    assert!(errs.len() == 0);
+    let correct_data = crate::data::Data::new2(
+        "Sample002-7654321".to_string(),
+        vec![
+            crate::data::Row::new("G".to_string(),3.),
+            crate::data::Row::new("L".to_string(),2.),
+            crate::data::Row::new("P".to_string(),1.),
+        ],
+        Some("sample-filename".to_string()),
+    );
+    assert!(data.approx_eq(&correct_data, TEST_EPSILON), "{:?}", data);
+
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 3;
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &file_lines.join("\n"),
+        &config
+    ).unwrap();
+    assert!(errs.len() == 0);
+    assert!(data.approx_eq(&correct_data, TEST_EPSILON), "{:?}", data);
+}//end data_read_data_from_file_skip_rows()
+
+/// Test for crate::data::read_data_from_file() with a fixture ending in two footer lines
+/// and no end header, skipped with skip_rows_before_end.
+#[test]
+pub fn data_read_data_from_file_footer_lines() {
+    let contents = include_str!("../tests/fixtures/footer_lines.txt");
+    let mut config = sample_config();
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 10;
+    config.row_order_preference = Vec::new();
+    // without skipping, the footer is read as data
+    let (data, errs) = crate::data::read_data_from_file("footer_lines.txt", contents, &config).unwrap();
+    assert_eq!(data.row_data.len(), 4);
+    assert_eq!(errs, vec![crate::data::DataParseError::InvalidNumber{line: 9, raw: "JS".to_string()}]);
+
+    config.skip_rows_before_end = 2;
+    let (data, errs) = crate::data::read_data_from_file("footer_lines.txt", contents, &config).unwrap();
+    assert!(errs.is_empty(), "{:?}", errs);
+    let correct_data = crate::data::Data {
+        metadata: vec![("Instrument".to_string(), "Alveograph".to_string())],
+        ..crate::data::Data::new2(
+            "Sample003-1112223".to_string(),
+            vec![
+                crate::data::Row::new("P".to_string(), 1.),
+                crate::data::Row::new("L".to_string(), 2.),
+                crate::data::Row::new("G".to_string(), 3.),
+            ],
+            Some("footer_lines.txt".to_string()),
+        )
+    };
+    assert!(data.approx_eq(&correct_data, TEST_EPSILON), "{:?}", data);
+}//end data_read_data_from_file_footer_lines()
+
+/// Test 1 for crate::process::run(), with one good and one missing input file
+#[test]
+pub fn process_run1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let good_input = dir.join("good.txt");
+    let missing_input = dir.join("missing.txt");
+    let _ = std::fs::remove_file(&missing_input);
+    std::fs::write(&good_input, sample_file_lines().join("\n")).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "test-sheet");
+    let report = crate::process::run(
+        &vec![good_input.clone(), missing_input.clone()],
+        &output,
+        &sample_config()
+    );
+    assert!(report.succeeded());
+    assert_eq!(report.output_path, output.path);
+    assert_eq!(report.files_parsed(), 1);
+    assert_eq!(report.files[0].path, good_input);
+    assert!(report.files[1].result.is_err());
+    assert_eq!(report.issue_messages().len(), 1);
+    assert!(output.path.exists());
+}//end process_run1()
+
+/// Test 1 for crate::process::non_finite_label()
+#[test]
+pub fn process_non_finite_label1() {
+    let p = 3.;
+    let l = 0.;
+    assert_eq!(crate::process::non_finite_label(p / l), "Infinity");
+    assert_eq!(crate::process::non_finite_label(-p / l), "-Infinity");
+    assert_eq!(crate::process::non_finite_label(l / l), "NaN");
+    assert_eq!(crate::process::non_finite_label(1.5), "1.5");
+}//end process_non_finite_label1()
+
+/// Test 1 for crate::process::write_output_to_sheet(), with a ratio dividing by zero
+#[test]
+pub fn process_write_output_to_sheet_non_finite1() {
+    let p = 3.;
+    let l = 0.;
+    let data = vec![crate::data::Data::new1(
+        "Sample001-1234567".to_string(),
+        vec![
+            crate::data::Row::new("P".to_string(),p),
+            crate::data::Row::new("L".to_string(),l),
+            crate::data::Row::new("P/L".to_string(),p / l),
+        ],
+    )];
+    let mut config = sample_config();
+    for non_finite_format in [
+        crate::config_store::NonFiniteFormat::Blank,
+        crate::config_store::NonFiniteFormat::Text,
+        crate::config_store::NonFiniteFormat::Flagged,
+    ] {
+        config.non_finite_format = non_finite_format;
+        let mut wb = crate::process::get_workbook();
+        crate::process::write_output_to_sheet(&mut wb, &data, "test-sheet", &config).unwrap();
+        assert!(wb.save_to_buffer().unwrap().len() > 0);
+    }//end trying out each way to write non-finite values
+}//end process_write_output_to_sheet_non_finite1()
+
+/// Test 1 for crate::data::strip_grouping_separators()
+#[test]
+pub fn data_strip_grouping_separators1() {
+    for text in ["1,234.56", "1.234,56", "1 234.56"] {
+        let stripped = crate::data::strip_grouping_separators(text, '.');
+        assert_eq!(stripped.parse::<f64>(), Ok(1234.56), "input was {text}");
+    }//end checking each grouped number
+    assert_eq!(crate::data::strip_grouping_separators("1\u{A0}234,56", ','), "1234.56");
+    assert_eq!(crate::data::strip_grouping_separators("1,234", '.'), "1234");
+    assert_eq!(crate::data::strip_grouping_separators("1.234", '.'), "1.234");
+    assert_eq!(crate::data::strip_grouping_separators("1.234.567", '.'), "1234567");
+    assert_eq!(crate::data::strip_grouping_separators("-283.4", '.'), "-283.4");
+}//end data_strip_grouping_separators1()
+
+/// Test 3 for crate::data::read_data_from_file(), with thousands separators in values
+#[test]
+pub fn data_read_data_from_file_thousands() {
+    let mut file_lines = sample_file_lines();
+    file_lines[8] = "P\t1,234.5".to_string();
+    let mut config = sample_config();
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(data.row_data.len(), 2);
+    config.strip_thousands_separators = true;
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    assert_eq!(errs.len(), 0);
+    assert_eq!(data.row_data[2], crate::data::Row::new("P".to_string(), 1234.5));
+}//end data_read_data_from_file_thousands()
+
+/// Test 1 for crate::process::sanitize_file_name()
+#[test]
+pub fn process_sanitize_file_name1() {
+    assert_eq!(crate::process::sanitize_file_name("alveograph-exporter-output"), "alveograph-exporter-output");
+    assert_eq!(crate::process::sanitize_file_name("run: 1/2*"), "run_ 1_2_");
+    assert_eq!(crate::process::sanitize_file_name("  "), "output");
+}//end process_sanitize_file_name1()
+
+/// Test 2 for crate::process::run(), writing csv copies of each sheet
+#[test]
+pub fn process_run_csv_sheets() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run_csv_sheets");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.txt");
+    std::fs::write(&input, sample_file_lines().join("\n")).unwrap();
+    let mut config = sample_config();
+    config.export_sheets_as_csv = true;
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "Sheet 1");
+    let report = crate::process::run(&vec![input], &output, &config);
+    assert!(report.succeeded());
+    assert_eq!(report.warnings.len(), 0);
+    let csv_path = dir.join("output_csv").join("Sheet 1.csv");
+    assert_eq!(crate::process::get_csv_sheet_dir(&output.path), dir.join("output_csv"));
+    let csv_contents = std::fs::read_to_string(csv_path).unwrap();
+    assert_eq!(csv_contents, "Test Name,G,L,P\nSample001-1234567,3.00,2.00,1.00\n");
+}//end process_run_csv_sheets()
+
+/// Test 1 for crate::process::get_flagged_format()
+#[test]
+pub fn process_get_flagged_format1() {
+    use rust_xlsxwriter::{Color, Format};
+    let base = Format::new().set_font_size(11);
+    let mut config = sample_config();
+    config.flag_color = crate::config_store::FlagColor::Blue;
+    config.flag_style = crate::config_store::FlagStyle::Font;
+    assert_eq!(
+        crate::process::get_flagged_format(&base, &config),
+        Format::new().set_font_size(11).set_font_color(Color::RGB(0x1F4E79))
+    );
+    config.flag_style = crate::config_store::FlagStyle::Italic;
+    assert_eq!(
+        crate::process::get_flagged_format(&base, &config),
+        Format::new().set_font_size(11).set_italic()
+    );
+}//end process_get_flagged_format1()
+
+/// Test 1 for crate::process::get_out_of_range_format(), following config flag_color and flag_style
+#[test]
+pub fn process_get_out_of_range_format1() {
+    use rust_xlsxwriter::{Color, Format};
+    let base = Format::new().set_font_size(11);
+    let mut config = sample_config();
+    let default_format = crate::process::get_out_of_range_format(&base, &config);
+    assert_eq!(default_format, Format::new().set_font_size(11)
+        .set_background_color(Color::RGB(crate::config_store::FlagColor::Red.fill_rgb()))
+        .set_font_color(Color::RGB(crate::config_store::FlagColor::Red.font_rgb())));
+    config.flag_color = crate::config_store::FlagColor::Blue;
+    let blue_format = crate::process::get_out_of_range_format(&base, &config);
+    assert_ne!(blue_format, default_format);
+    assert_eq!(blue_format, Format::new().set_font_size(11)
+        .set_background_color(Color::RGB(crate::config_store::FlagColor::Blue.fill_rgb()))
+        .set_font_color(Color::RGB(crate::config_store::FlagColor::Blue.font_rgb())));
+    config.flag_style = crate::config_store::FlagStyle::Bold;
+    assert_eq!(crate::process::get_out_of_range_format(&base, &config), Format::new().set_font_size(11).set_bold());
+}//end process_get_out_of_range_format1()
+
+/// Test 1 for crate::process::fill_name_template()
+#[test]
+pub fn process_fill_name_template1() {
+    let now = time::macros::datetime!(2024-09-18 13:05:09 UTC);
+    assert_eq!(
+        crate::process::fill_name_template("run {date} {time} #{index}", 2, now),
+        "run 2024-09-18 13-05-09 #2"
+    );
+    assert_eq!(
+        crate::process::fill_name_template("alveograph-exporter-output", 1, now),
+        "alveograph-exporter-output"
+    );
+}//end process_fill_name_template1()
+
+/// Test 1 for crate::process::sanitize_sheet_name()
+#[test]
+pub fn process_sanitize_sheet_name1() {
+    assert_eq!(crate::process::sanitize_sheet_name("Results [1/2]", 1), "Results _1_2_");
+    assert_eq!(crate::process::sanitize_sheet_name("'quoted'", 1), "quoted");
+    assert_eq!(crate::process::sanitize_sheet_name("", 3), "Sheet3");
+    assert_eq!(crate::process::sanitize_sheet_name(&"a".repeat(40), 1).len(), 31);
+}//end process_sanitize_sheet_name1()
+
+/// Test 1 for crate::process::resolve_sheet_name()
+#[test]
+pub fn process_resolve_sheet_name1() {
+    let mut config = sample_config();
+    config.sheet_name_template = String::from("Template");
+    let output = std::path::PathBuf::from("results/run-1.xlsx");
+    assert_eq!(crate::process::resolve_sheet_name(" Flour ", &output, &config), (String::from("Flour"), false));
+    assert_eq!(crate::process::resolve_sheet_name("Flour [1]", &output, &config), (String::from("Flour _1_"), true));
+    assert_eq!(crate::process::resolve_sheet_name(&"a".repeat(40), &output, &config), ("a".repeat(31), true));
+    assert_eq!(crate::process::resolve_sheet_name("", &output, &config), (String::from("Template"), false));
+    config.sheet_name_template = String::new();
+    assert_eq!(crate::process::resolve_sheet_name("  ", &output, &config), (String::from("run-1"), false));
+}//end process_resolve_sheet_name1()
+
+/// Test for crate::data::read_data_from_file() with a byte order mark
+/// before the start header.
+#[test]
+pub fn data_read_data_from_file_bom() {
+    let mut config = sample_config();
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &sample_file_contents_with_bom(),
+        &config
+    ).unwrap();
+    assert!(errs.len() == 0);
+    let correct_data = crate::data::Data::new2(
+        "Sample003-1111111".to_string(),
+        vec![
+            crate::data::Row::new("G".to_string(),3.),
+            crate::data::Row::new("L".to_string(),2.),
+            crate::data::Row::new("P".to_string(),1.),
+        ],
+        Some("sample-filename".to_string()),
+    );
+    assert!(data.approx_eq(&correct_data, TEST_EPSILON), "{:?}", data);
+}//end data_read_data_from_file_bom()
+
+/// Test 1 for crate::data::average_data()
+#[test]
+pub fn data_average_data1() {
+    let data = vec![
+        crate::data::Data::new1("A".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 10.),
+            crate::data::Row::new("L".to_string(), 4.),
+        ]),
+        crate::data::Data::new1("B".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 20.),
+            crate::data::Row::new("G".to_string(), 7.),
+        ]),
+        crate::data::Data::new1("C".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 30.),
+            crate::data::Row::new("L".to_string(), 8.),
+        ]),
+    ];
+    let averaged = crate::data::average_data(&data, "Average");
+    let correct_data = crate::data::Data::new1("Average".to_string(), vec![
+        crate::data::Row::new("P".to_string(), 20.),
+        crate::data::Row::new("L".to_string(), 6.),
+        crate::data::Row::new("G".to_string(), 7.),
+    ]);
+    assert_eq!(averaged, correct_data);
+
+    let empty = crate::data::average_data(&[], "Nothing");
+    assert_eq!(empty, crate::data::Data::new("Nothing".to_string()));
+}//end data_average_data1()
+
+/// Test for crate::data::summarize_data() and crate::process::write_summary_sheet()
+#[test]
+pub fn data_summarize_data1() {
+    use crate::config_store::SummaryStat;
+    let data = vec![
+        crate::data::Data::new1("A".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 10.),
+            crate::data::Row::new("L".to_string(), 4.),
+        ]),
+        crate::data::Data::new1("B".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 20.),
+            crate::data::Row::new("L".to_string(), f64::NAN),
+        ]),
+        crate::data::Data::new1("C".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 30.),
+            crate::data::Row::new("G".to_string(), 0.),
+        ]),
+    ];
+    let summaries = crate::data::summarize_data(&data);
+    assert_eq!(summaries.iter().map(|s| s.header.as_str()).collect::<Vec<&str>>(), vec!["P","L","G"]);
+
+    let p = &summaries[0];
+    assert_eq!(p.get_stat(SummaryStat::Count), Some(3.));
+    assert_eq!(p.get_stat(SummaryStat::Mean), Some(20.));
+    assert_eq!(p.get_stat(SummaryStat::StdDev), Some(10.));
+    assert_eq!(p.get_stat(SummaryStat::StdErr), Some(10. / 3f64.sqrt()));
+    assert_eq!(p.get_stat(SummaryStat::Cv), Some(0.5));
+    assert_eq!(p.get_stat(SummaryStat::Min), Some(10.));
+    assert_eq!(p.get_stat(SummaryStat::Max), Some(30.));
+
+    // the NaN is left out, so there's only one value, and variance-based stats are blank
+    let l = &summaries[1];
+    assert_eq!(l.get_stat(SummaryStat::Count), Some(1.));
+    assert_eq!(l.get_stat(SummaryStat::Mean), Some(4.));
+    assert_eq!(l.get_stat(SummaryStat::StdDev), None);
+    assert_eq!(l.get_stat(SummaryStat::StdErr), None);
+    assert_eq!(l.get_stat(SummaryStat::Cv), None);
+    assert_eq!(l.get_stat(SummaryStat::Min), Some(4.));
+    assert_eq!(l.get_stat(SummaryStat::Max), Some(4.));
+
+    let mut config = sample_config();
+    config.summary_stats = vec![SummaryStat::Count, SummaryStat::Mean, SummaryStat::StdDev, SummaryStat::StdErr, SummaryStat::Cv];
+    let mut wb = crate::process::get_workbook();
+    let summary_name = crate::process::get_summary_sheet_name(&["summary", "Summary 2", "Data"]);
+    assert_eq!(summary_name, "Summary 3");
+    crate::process::write_summary_sheet(&mut wb, &summaries, &[], &summary_name, &config).unwrap();
+    // with no data, just the header row is written
+    config.summary_stats = crate::config_store::ConfigStore::default().summary_stats;
+    crate::process::write_summary_sheet(&mut wb, &crate::data::summarize_data(&[]), &[], "Empty", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end data_summarize_data1()
+
+/// Test 1 for crate::data::filter_row_data()
+#[test]
+pub fn data_filter_row_data1() {
+    let row_data = vec![
+        crate::data::Row::new("P".to_string(), 1.),
+        crate::data::Row::new("L".to_string(), 2.),
+        crate::data::Row::new("G".to_string(), 3.),
+    ];
+    let mut config = sample_config();
+    assert_eq!(crate::data::filter_row_data(row_data.clone(), &config), row_data);
+
+    config.include_headers = vec!["G","P"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(
+        crate::data::filter_row_data(row_data, &config),
+        vec![
+            crate::data::Row::new("P".to_string(), 1.),
+            crate::data::Row::new("G".to_string(), 3.),
+        ]
+    );
+}//end data_filter_row_data1()
+
+/// Test 1 for crate::data::test_name_matches()
+#[test]
+pub fn data_test_name_matches1() {
+    // plain patterns only need to be found somewhere in the name
+    assert!(crate::data::test_name_matches("Calibration-01", "cal"));
+    assert!(crate::data::test_name_matches("Sample001", "001"));
+    assert!(!crate::data::test_name_matches("Sample001", "002"));
+    // wildcards are matched against the whole name
+    assert!(crate::data::test_name_matches("Sample001-1234567", "sample*"));
+    assert!(!crate::data::test_name_matches("MySample001", "sample*"));
+    assert!(crate::data::test_name_matches("Sample001", "Sample00?"));
+    assert!(!crate::data::test_name_matches("Sample0011", "Sample00?"));
+    assert!(crate::data::test_name_matches("Run-7-CAL-b", "*cal*"));
+    assert!(crate::data::test_name_matches("abcabd", "*ab?"));
+    assert!(crate::data::test_name_matches("", "*"));
+}//end data_test_name_matches1()
+
+/// Test 1 for crate::data::test_name_passes_filter(), with overlapping and empty lists
+#[test]
+pub fn data_test_name_passes_filter1() {
+    let to_strings = |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<String>>();
+    // empty lists let everything through
+    assert!(crate::data::test_name_passes_filter("Junk", &[], &[]));
+    // only includes
+    let include = to_strings(&["Sample*", "Wheat"]);
+    assert!(crate::data::test_name_passes_filter("Sample001", &include, &[]));
+    assert!(crate::data::test_name_passes_filter("HardWheat-3", &include, &[]));
+    assert!(!crate::data::test_name_passes_filter("Calibration", &include, &[]));
+    // only excludes
+    let exclude = to_strings(&["cal", "junk"]);
+    assert!(!crate::data::test_name_passes_filter("Calibration", &[], &exclude));
+    assert!(crate::data::test_name_passes_filter("Sample001", &[], &exclude));
+    // overlapping, where the exclude wins
+    assert!(!crate::data::test_name_passes_filter("Sample-CAL", &include, &exclude));
+    assert!(crate::data::test_name_passes_filter("Sample002", &include, &exclude));
+    assert!(!crate::data::test_name_passes_filter("Sample002", &include, &to_strings(&["Sample*"])));
+}//end data_test_name_passes_filter1()
+
+/// Test 1 for crate::data::filter_by_test_name()
+#[test]
+pub fn data_filter_by_test_name1() {
+    let data = vec![
+        crate::data::Data::new("Sample001".to_string()),
+        crate::data::Data::new("Calibration".to_string()),
+        crate::data::Data::new("Sample002-cal".to_string()),
+    ];
+    let mut config = sample_config();
+    assert_eq!(crate::data::filter_by_test_name(data.clone(), &config), data);
+    config.test_name_include = vec!["sample*".to_string()];
+    config.test_name_exclude = vec!["cal".to_string()];
+    assert_eq!(crate::data::filter_by_test_name(data, &config), vec![crate::data::Data::new("Sample001".to_string())]);
+    assert!(config.validate().is_ok());
+    config.test_name_exclude = vec![" ".to_string()];
+    assert!(config.validate().is_err());
+}//end data_filter_by_test_name1()
+
+/// Test 1 for crate::process::write_output_to_bytes()
+#[test]
+pub fn process_write_output_to_bytes1() {
+    let config = sample_config();
+    let (data, _) = crate::data::read_data_from_file(
+        "sample-filename",
+        &sample_file_lines().join("\n"),
+        &config
+    ).unwrap();
+    let bytes = crate::process::write_output_to_bytes(&vec![data], &config).unwrap();
+    // xlsx files are zip archives, which start with "PK"
+    assert!(bytes.starts_with(b"PK"));
+
+    let empty_bytes = crate::process::write_output_to_bytes(&Vec::new(), &config).unwrap();
+    assert!(empty_bytes.starts_with(b"PK"));
+    assert!(empty_bytes.len() < bytes.len());
+}//end process_write_output_to_bytes1()
+
+/// Test for crate::data::read_data_from_file() with the start index on the
+/// first data row, both with and without start_header_is_data.
+#[test]
+pub fn data_read_data_from_file_start_header_is_data() {
+    let mut config = sample_config();
+    config.read_start_mode = crate::config_store::ReadStartMode::Index;
+    config.read_start_idx = 8;
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 3;
+
+    // by default, the "P" line is treated as a marker and skipped
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &sample_file_lines().join("\n"),
+        &config
+    ).unwrap();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("G".to_string(),3.),
+        crate::data::Row::new("L".to_string(),2.),
+    ]);
+
+    config.start_header_is_data = true;
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &sample_file_lines().join("\n"),
+        &config
+    ).unwrap();
+    assert!(errs.len() == 0);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("G".to_string(),3.),
+        crate::data::Row::new("L".to_string(),2.),
+        crate::data::Row::new("P".to_string(),1.),
+    ]);
+}//end data_read_data_from_file_start_header_is_data()
+
+/// Test 1 for crate::process::resolve_output_path()
+#[test]
+pub fn process_resolve_output_path1() {
+    use crate::config_store::OutputFormat;
+    use std::path::PathBuf;
+    let dir = PathBuf::from("some").join("dir");
+    // no extension, so the format's extension is added
+    assert_eq!(
+        crate::process::resolve_output_path(dir.join("run"), OutputFormat::Xlsx),
+        (dir.join("run.xlsx"), OutputFormat::Xlsx)
+    );
+    assert_eq!(
+        crate::process::resolve_output_path(dir.join("run"), OutputFormat::Csv),
+        (dir.join("run.csv"), OutputFormat::Csv)
+    );
+    // a recognized extension is kept, and switches the format
+    assert_eq!(
+        crate::process::resolve_output_path(dir.join("run.csv"), OutputFormat::Xlsx),
+        (dir.join("run.csv"), OutputFormat::Csv)
+    );
+    assert_eq!(
+        crate::process::resolve_output_path(dir.join("run.XLSX"), OutputFormat::Csv),
+        (dir.join("run.XLSX"), OutputFormat::Xlsx)
+    );
+    // an unrelated dot isn't treated as an extension
+    assert_eq!(
+        crate::process::resolve_output_path(dir.join("run.v2"), OutputFormat::Xlsx),
+        (dir.join("run.v2.xlsx"), OutputFormat::Xlsx)
+    );
+}//end process_resolve_output_path1()
+
+/// Test 1 for crate::process::get_unsupported_extension()
+#[test]
+pub fn process_get_unsupported_extension1() {
+    use std::path::PathBuf;
+    let dir = PathBuf::from("some").join("dir");
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run")), None);
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run.csv")), None);
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run.JSON")), None);
+    // endings with digits are part of the name, not an extension
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run.v2")), None);
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run.2024")), None);
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run.ods")), Some("ods".to_string()));
+    assert_eq!(crate::process::get_unsupported_extension(&dir.join("run.XLS")), Some("XLS".to_string()));
+    assert_eq!(crate::config_store::OutputFormat::supported_extensions(), ".xlsx, .csv, .json");
+}//end process_get_unsupported_extension1()
+
+/// Test 1 for crate::process::find_input_files(), with and without recursion
+#[test]
+pub fn process_find_input_files1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_find_input_files1");
+    let _ = std::fs::remove_dir_all(&dir);
+    let sub_dir = dir.join("sub");
+    std::fs::create_dir_all(&sub_dir).unwrap();
+    for path in [dir.join("b.txt"), dir.join("a.TXT"), dir.join("notes.csv"), sub_dir.join("c.txt")] {
+        std::fs::write(path, "").unwrap();
+    }//end making each file
+    assert_eq!(crate::process::find_input_files(&dir, false).unwrap(), vec![dir.join("a.TXT"), dir.join("b.txt")]);
+    assert_eq!(crate::process::find_input_files(&dir, true).unwrap(), vec![dir.join("a.TXT"), dir.join("b.txt"), sub_dir.join("c.txt")]);
+    assert!(crate::process::find_input_files(&dir.join("missing"), false).is_err());
+}//end process_find_input_files1()
+
+/// Test for crate::process::run() with csv as the output format
+#[test]
+pub fn process_run_csv_format() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run_csv_format");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.txt");
+    std::fs::write(&input, sample_file_lines().join("\n")).unwrap();
+    let mut config = sample_config();
+    config.output_format = crate::config_store::OutputFormat::Csv;
+    let output = crate::process::OutputSpec::new(dir.join("output.csv"), "Sheet 1");
+    let report = crate::process::run(&vec![input], &output, &config);
+    assert!(report.succeeded());
+    let csv_contents = std::fs::read_to_string(&output.path).unwrap();
+    assert_eq!(csv_contents, "Test Name,G,L,P\nSample001-1234567,3.00,2.00,1.00\n");
+}//end process_run_csv_format()
+
+/// Test for crate::data::read_data_from_file() and crate::process::write_output_to_csv()
+/// with quality flags after each value.
+#[test]
+pub fn data_read_data_from_file_row_flags() {
+    let contents = vec![
+        "Test name\t:\tSample004-2222222",
+        "Standard\t : \tAverage",
+        "P\t1\tOK",
+        "L\t2\tLOW",
+        "G\t3",
+    ].join("\n");
+    let mut config = sample_config();
+    let (data, _) = crate::data::read_data_from_file("sample-filename", &contents, &config).unwrap();
+    assert!(data.row_data.iter().all(|row| row.flag.is_none()));
+
+    config.parse_row_flags = true;
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &contents, &config).unwrap();
+    assert!(errs.len() == 0);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("G".to_string(),3.),
+        crate::data::Row::new1("L".to_string(),2.,Some("LOW".to_string())),
+        crate::data::Row::new1("P".to_string(),1.,Some("OK".to_string())),
+    ]);
+
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-data_read_data_from_file_row_flags");
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("output.csv");
+    crate::process::write_output_to_csv(&vec![data], &csv_path, &config).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(csv_path).unwrap(),
+        "Test Name,G,G Flag,L,L Flag,P,P Flag\nSample004-2222222,3.00,,2.00,LOW,1.00,OK\n"
+    );
+}//end data_read_data_from_file_row_flags()
+
+/// Test for crate::process::write_pivot_to_csv() and crate::process::write_pivot_to_sheet()
+#[test]
+pub fn process_write_pivot1() {
+    let data = vec![
+        crate::data::Data::new1("Sample001".to_string(), vec![
+            crate::data::Row::new1("P".to_string(), 1., Some("OK".to_string())),
+            crate::data::Row::new1("L".to_string(), 2., Some("LOW".to_string())),
+        ]),
+        crate::data::Data::new1("Sample002".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 4.),
+            crate::data::Row::new("G".to_string(), 5.),
+        ]),
+    ];
+    assert_eq!(crate::process::get_pivot_row_headers(&data), vec!["P","L","G"]);
+
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_write_pivot1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("pivot.csv");
+    let mut config = sample_config();
+    config.output_layout = crate::config_store::OutputLayout::Pivot;
+    crate::process::write_csv_for_layout(&data, &csv_path, &config).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&csv_path).unwrap(),
+        "Measurement,Sample001,Sample002\nP,1.00,4.00\nL,2.00,\nG,,5.00\n"
+    );
+    // without flags, the table is just the grid of measurements by test
+    assert_eq!(crate::process::get_pivot_table(&data, &config), vec![
+        vec!["Measurement", "Sample001", "Sample002"],
+        vec!["P", "1.00", "4.00"],
+        vec!["L", "2.00", ""],
+        vec!["G", "", "5.00"],
+    ]);
+
+    config.parse_row_flags = true;
+    crate::process::write_csv_for_layout(&data, &csv_path, &config).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&csv_path).unwrap(),
+        "Measurement,Sample001,,Sample002,\n,Value,Flag,Value,Flag\nP,1.00,OK,4.00,\nL,2.00,LOW,,\nG,,,5.00,\n"
+    );
+
+    // merged headers should be accepted by the workbook in both shapes
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_pivot_to_sheet(&mut wb, &data, "with flags", &config).unwrap();
+    config.parse_row_flags = false;
+    crate::process::write_pivot_to_sheet(&mut wb, &data, "without flags", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_write_pivot1()
+
+/// Test for crate::process::write_merged_to_csv() and crate::process::write_merged_sheet()
+#[test]
+pub fn process_write_merged1() {
+    let data = vec![
+        crate::data::Data::new2("Sample001".to_string(), vec![
+            crate::data::Row::new1("P".to_string(), 1., Some("OK".to_string())),
+            crate::data::Row::new("L".to_string(), 2.),
+        ], Some("a.txt".to_string())),
+        crate::data::Data::new2("Sample002".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 4.),
+            crate::data::Row::new("G".to_string(), 5.),
+        ], None),
+    ];
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_write_merged1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("merged.csv");
+    let mut config = sample_config();
+    config.output_layout = crate::config_store::OutputLayout::Merged;
+    crate::process::write_csv_for_layout(&data, &csv_path, &config).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&csv_path).unwrap(),
+        "Source File,Test Name,P,L,G\na.txt,Sample001,1.00,2.00,\n,Sample002,4.00,,5.00\n"
+    );
+    config.parse_row_flags = true;
+    assert_eq!(crate::process::get_merged_table(&data, &config)[1], vec!["a.txt", "Sample001", "1.00", "OK", "2.00", "", "", ""]);
+
+    // the merged layout stays on one sheet, even if each file would get its own
+    config.sheet_per_file = true;
+    config.max_rows_per_sheet = Some(1);
+    let sheets = crate::process::split_into_sheets("Merged", data.clone(), &config);
+    assert_eq!(sheets.len(), 1);
+    assert_eq!(sheets[0].1.len(), 2);
+    config.excel_freeze_and_filter = true;
+    let mut wb = crate::process::get_workbook();
+    assert_eq!(crate::process::write_sheet_for_layout(&mut wb, &data, "Merged", &config).unwrap(), None);
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_write_merged1()
+
+/// Test for crate::process::write_output_to_json(), reading the json back in
+#[test]
+pub fn process_write_output_to_json1() {
+    let data = vec![
+        crate::data::Data {
+            metadata: vec![("Operator".to_string(), "Jane".to_string())],
+            ..crate::data::Data::new2("Sample001".to_string(), vec![
+                crate::data::Row::new1("P".to_string(), 1.25, Some("OK".to_string())),
+                crate::data::Row {raw_value: Some(20.), ..crate::data::Row::new("L".to_string(), 2. / 3.)},
+            ], Some("a.txt".to_string()))
+        },
+        crate::data::Data::new1("Sample002".to_string(), vec![crate::data::Row::new("G".to_string(), 5.)]),
+    ];
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_write_output_to_json1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let (json_path, format) = crate::process::resolve_output_path(dir.join("run.json"), crate::config_store::OutputFormat::Xlsx);
+    assert_eq!(format, crate::config_store::OutputFormat::Json);
+    crate::process::write_output_to_json(&data, &json_path).unwrap();
+    let json = std::fs::read_to_string(&json_path).unwrap();
+    assert!(json.contains("\"test_name\": \"Sample001\""));
+    let read_back: Vec<crate::data::Data> = serde_json::from_str(&json).unwrap();
+    assert_eq!(read_back.len(), data.len());
+    for (read_data, data) in read_back.iter().zip(data.iter()) {
+        assert!(read_data.approx_eq(data, TEST_EPSILON), "{:?}", read_data);
+    }//end checking each data read back
+}//end process_write_output_to_json1()
+
+/// Test 1 for crate::process::add_metric_chart()
+#[test]
+pub fn process_add_metric_chart1() {
+    let has_chart = |buffer: &[u8]| buffer.windows(b"xl/charts/chart1.xml".len()).any(|name| name == b"xl/charts/chart1.xml");
+    let data = vec![
+        crate::data::Data::new1("Sample001".to_string(), vec![crate::data::Row::new("P".to_string(), 1.), crate::data::Row::new("W".to_string(), 250.)]),
+        crate::data::Data::new1("Sample002".to_string(), vec![crate::data::Row::new("P".to_string(), 4.), crate::data::Row::new("W".to_string(), 310.)]),
+    ];
+    let mut config = sample_config();
+    let mut wb = crate::process::get_workbook();
+    let sheet_range = crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 1", &config).unwrap().unwrap();
+    // a header that isn't in any file doesn't get a chart
+    crate::process::add_metric_chart(&mut wb, &data, "G", &sheet_range).unwrap();
+    assert!(!has_chart(&wb.save_to_buffer().unwrap()));
+    crate::process::add_metric_chart(&mut wb, &data, "W", &sheet_range).unwrap();
+    assert!(has_chart(&wb.save_to_buffer().unwrap()));
+
+    // config chart_metric adds the chart when writing the wide layout
+    config.chart_metric = Some("W".to_string());
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_sheet_for_layout(&mut wb, &data, "Sheet 1", &config).unwrap();
+    assert!(has_chart(&wb.save_to_buffer().unwrap()));
+}//end process_add_metric_chart1()
+
+/// Test 1 for crate::process::get_num_format() and crate::config_store::ConfigStore::get_decimal_places()
+#[test]
+pub fn process_get_num_format1() {
+    let mut config = sample_config();
+    assert_eq!(config.get_decimal_places("P"), 2);
+    // headers with their own places keep them whatever decimal_places is
+    config.header_decimal_places.insert("W".to_string(), 3);
+    for (places, format) in [(0, "0"), (2, "0.00"), (4, "0.0000")] {
+        config.decimal_places = places;
+        assert_eq!(crate::process::get_num_format(config.get_decimal_places("P"), false), format);
+        assert_eq!(crate::process::get_num_format(config.get_decimal_places("W"), false), "0.000");
+    }//end checking each number of places
+    // silly values are clamped, and headers can have their own places
+    config.decimal_places = 200;
+    assert_eq!(config.get_decimal_places("P"), crate::config_store::MAX_DECIMAL_PLACES);
+    assert_eq!(crate::process::get_num_format(200, false), "0.00000000");
+    assert_eq!(config.get_decimal_places("W"), 3);
+    let data = vec![crate::data::Data::new1("Sample001".to_string(), vec![crate::data::Row::new("W".to_string(), 1.23456)])];
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 1", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_get_num_format1()
+
+/// Test for crate::process::get_num_format() with config use_thousands_separator,
+/// to make sure it works with each number of decimal places.
+#[test]
+pub fn process_get_num_format_thousands() {
+    let mut config = sample_config();
+    assert!(!config.use_thousands_separator);
+    for (places, format) in [(0, "#,##0"), (2, "#,##0.00"), (3, "#,##0.000")] {
+        assert_eq!(crate::process::get_num_format(places, true), format);
+    }//end checking each number of places
+    assert_eq!(crate::process::get_num_format(200, true), "#,##0.00000000");
+
+    config.use_thousands_separator = true;
+    config.header_decimal_places.insert("W".to_string(), 0);
+    assert_eq!(crate::process::get_num_format(config.get_decimal_places("W"), config.use_thousands_separator), "#,##0");
+    assert_eq!(crate::process::get_num_format(config.get_decimal_places("P"), config.use_thousands_separator), "#,##0.00");
+    let data = vec![crate::data::Data::new1("Sample001".to_string(), vec![
+        crate::data::Row::new("P".to_string(), 1234.5),
+        crate::data::Row::new("W".to_string(), 123456.),
+    ])];
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 1", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_get_num_format_thousands()
+
+/// Test for crate::process::get_csv_line() and crate::process::write_output_to_csv()
+/// with a custom csv_delimiter and csv_quote, quoting fields with embedded delimiters.
+#[test]
+pub fn process_get_csv_line_delimiter() {
+    let mut config = sample_config();
+    let cells: Vec<String> = vec!["a,b", "c;d", "say \"hi\"", "it's"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(crate::process::get_csv_line(&cells, &config), "\"a,b\",c;d,\"say \"\"hi\"\"\",it's");
+
+    config.csv_delimiter = ';';
+    config.csv_quote = '\'';
+    assert_eq!(crate::process::get_csv_line(&cells, &config), "a,b;'c;d';say \"hi\";'it''s'");
+
+    config.csv_delimiter = '\t';
+    config.csv_quote = '"';
+    let data = vec![crate::data::Data::new1("Sample\t001".to_string(), vec![
+        crate::data::Row::new("P".to_string(), 1.5),
+        crate::data::Row::new("L".to_string(), 2.),
+    ])];
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_get_csv_line_delimiter");
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("output.csv");
+    crate::process::write_output_to_csv(&data, &csv_path, &config).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(csv_path).unwrap(),
+        "Test Name\tP\tL\n\"Sample\t001\"\t1.50\t2.00\n"
+    );
+
+    config.csv_quote = '\t';
+    assert!(config.validate().is_err());
+}//end process_get_csv_line_delimiter()
+
+/// Test 1 for crate::config_store::ConfigStore::validate()
+#[test]
+pub fn config_store_validate1() {
+    let mut config = sample_config();
+    assert!(config.validate().is_ok());
+
+    config.read_start_header = " ".to_string();
+    assert_eq!(config.validate().unwrap_err().len(), 1);
+
+    config.read_start_header = "Standard\t : \tAverage".to_string();
+    config.read_end_header = "Standard\t : \tAverage".to_string();
+    assert_eq!(config.validate().unwrap_err().len(), 1);
+
+    // the start header isn't used when reading from an index
+    config.read_start_mode = crate::config_store::ReadStartMode::Index;
+    config.read_start_header = "".to_string();
+    assert!(config.validate().is_ok());
+}//end config_store_validate1()
+
+/// Test for crate::data::read_data_from_file() stopping at read_end_header
+#[test]
+pub fn data_read_data_from_file_end_header() {
+    let mut config = sample_config();
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 10;
+    config.read_end_header = "G\t3".to_string();
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &sample_file_lines().join("\n"),
+        &config
+    ).unwrap();
+    assert!(errs.len() == 0);
+    assert_eq!(data.row_data, vec![
+        crate::data::Row::new("L".to_string(),2.),
+        crate::data::Row::new("P".to_string(),1.),
+    ]);
+
+    // a missing end header reads to the end of the file, with a warning
+    config.read_end_header = "not in the file".to_string();
+    config.read_max_rows = 3;
+    let (data, errs) = crate::data::read_data_from_file(
+        "sample-filename",
+        &sample_file_lines().join("\n"),
+        &config
+    ).unwrap();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(data.row_data.len(), 3);
+}//end data_read_data_from_file_end_header()
+
+/// Test 1 for crate::process::get_wide_tsv()
+#[test]
+pub fn process_get_wide_tsv1() {
+    let data = vec![
+        crate::data::Data::new1("Sample\t001".to_string(), vec![
+            crate::data::Row::new1("P".to_string(), 1.5, Some("OK".to_string())),
+            crate::data::Row::new("L".to_string(), 2.),
+        ]),
+    ];
+    let mut config = sample_config();
+    assert_eq!(crate::process::get_wide_tsv(&data, &config), "Test Name\tP\tL\nSample 001\t1.50\t2.00\n");
+    config.parse_row_flags = true;
+    assert_eq!(
+        crate::process::get_wide_tsv(&data, &config),
+        "Test Name\tP\tP Flag\tL\tL Flag\nSample 001\t1.50\tOK\t2.00\t\n"
+    );
+    assert_eq!(crate::process::get_wide_tsv(&Vec::new(), &config), "");
+}//end process_get_wide_tsv1()
+
+/// Test for crate::process::get_default_output_name() and crate::process::get_output_dir(),
+/// checking that switching between two configs switches where output goes.
+#[test]
+pub fn process_output_defaults1() {
+    use std::path::PathBuf;
+    let now = time::macros::datetime!(2024-09-18 13:05:09 UTC);
+    let inputs = vec![PathBuf::from("inputs").join("file1.txt")];
+    let plain = sample_config();
+    let mut lab_b = sample_config();
+    lab_b.default_output_dir = "shared/lab-b".to_string();
+    lab_b.output_name_template = "lab-b {date}".to_string();
+
+    assert_eq!(crate::process::get_default_output_name(&plain, now), None);
+    assert_eq!(crate::process::get_output_dir(&plain, &inputs), Some(PathBuf::from("inputs")));
+    assert_eq!(crate::process::get_default_output_name(&lab_b, now), Some("lab-b 2024-09-18".to_string()));
+    assert_eq!(crate::process::get_output_dir(&lab_b, &inputs), Some(PathBuf::from("shared/lab-b")));
+    // switching back gives the original behavior again
+    assert_eq!(crate::process::get_output_dir(&plain, &Vec::new()), None);
+}//end process_output_defaults1()
+
+/// Test for crate::process::write_output_to_sheet() giving context when
+/// a value can't be written.
+#[test]
+pub fn process_write_output_to_sheet_error_context() {
+    let too_long = "x".repeat(40_000);
+    let data = vec![crate::data::Data::new1(
+        "Sample001".to_string(),
+        vec![crate::data::Row::new1("P".to_string(), 1., Some(too_long))],
+    )];
+    let mut config = sample_config();
+    config.parse_row_flags = true;
+    let mut wb = crate::process::get_workbook();
+    let err = crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 1", &config).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("failed writing flag for test \"Sample001\" header \"P\""), "{}", msg);
+}//end process_write_output_to_sheet_error_context()
+
+/// Test 1 for crate::process::parse_manifest()
+#[test]
+pub fn process_parse_manifest1() {
+    use std::path::{Path, PathBuf};
+    let base_dir = Path::new("manifests");
+    let contents = "# morning batch\nfile1.txt\n\n  sub/file2.txt  \r\n";
+    assert_eq!(
+        crate::process::parse_manifest(contents, base_dir),
+        vec![base_dir.join("file1.txt"), base_dir.join("sub/file2.txt")]
+    );
+    assert_eq!(crate::process::parse_manifest("# nothing here\n", base_dir), Vec::<PathBuf>::new());
+}//end process_parse_manifest1()
+
+/// Test matrix for crate::data::parse_value(), with each combination of settings
+#[test]
+pub fn data_parse_value1() {
+    let mut config = sample_config();
+    // (text, expected without stripping, expected with stripping)
+    let cases: Vec<(&str, Option<f64>, Option<f64>)> = vec![
+        ("42", Some(42.), Some(42.)),
+        ("  -3.5 ", Some(-3.5), Some(-3.5)),
+        ("1,234.5", None, Some(1234.5)),
+        ("1 234", None, Some(1234.)),
+        ("1.234,5", None, Some(1234.5)),
+        ("NaN", Some(f64::NAN), Some(f64::NAN)),
+        ("", None, None),
+        ("abc", None, None),
+    ];
+    for strip in [false, true] {
+        config.strip_thousands_separators = strip;
+        for (text, plain, stripped) in cases.iter() {
+            let expected = if strip {stripped} else {plain};
+            let actual = crate::data::parse_value(text, &config).ok();
+            match (expected, actual) {
+                (Some(e), Some(a)) if e.is_nan() => assert!(a.is_nan(), "{:?} strip={}", text, strip),
+                (e, a) => assert_eq!(*e, a, "{:?} strip={}", text, strip),
+            }//end matching expected and actual value
+        }//end checking each case
+    }//end checking each setting
+}//end data_parse_value1()
+
+/// Test for crate::data::parse_value() with decimal_comma, with and without
+/// strip_thousands_separators.
+#[test]
+pub fn data_parse_value_decimal_comma() {
+    let mut config = sample_config();
+    assert!(crate::data::parse_value("1,5", &config).is_err());
+
+    config.decimal_comma = true;
+    for strip in [false, true] {
+        config.strip_thousands_separators = strip;
+        assert_eq!(crate::data::parse_value("1,5", &config), Ok(1.5), "strip={}", strip);
+        assert_eq!(crate::data::parse_value("283,4", &config), Ok(283.4), "strip={}", strip);
+        assert_eq!(crate::data::parse_value("1.234,56", &config), Ok(1234.56), "strip={}", strip);
+        assert_eq!(crate::data::parse_value("1,234.56", &config), Ok(1234.56), "strip={}", strip);
+        assert_eq!(crate::data::parse_value("-0,25", &config), Ok(-0.25), "strip={}", strip);
+    }//end checking each setting
+
+    // without stripping, a value already using a period isn't changed
+    config.strip_thousands_separators = false;
+    assert_eq!(crate::data::parse_value("283.4", &config), Ok(283.4));
+    assert!(crate::data::parse_value("1,234,567", &config).is_err());
+}//end data_parse_value_decimal_comma()
+
+/// Test for crate::process::get_constant_columns() and constant columns in csv output
+#[test]
+pub fn process_constant_columns1() {
+    let now = time::macros::datetime!(2024-09-18 13:05:09 UTC);
+    let mut config = sample_config();
+    config.constant_columns = vec![
+        ("Project".to_string(), "WX-12".to_string()),
+        ("Exported".to_string(), "{date}".to_string()),
+    ];
+    assert_eq!(
+        crate::process::get_constant_columns(&config, now),
+        vec![
+            ("Project".to_string(), "WX-12".to_string()),
+            ("Exported".to_string(), "2024-09-18".to_string()),
+        ]
+    );
+
+    config.constant_columns.pop();
+    let data = vec![
+        crate::data::Data::new1("Sample001".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 1.),
+            crate::data::Row::new("L".to_string(), 2.),
+        ]),
+        crate::data::Data::new1("Sample002".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 3.),
+        ]),
+    ];
+    assert_eq!(
+        crate::process::get_wide_tsv(&data, &config),
+        "Test Name\tP\tL\tProject\nSample001\t1.00\t2.00\tWX-12\nSample002\t3.00\t\tWX-12\n"
+    );
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 1", &config).unwrap();
+    config.output_layout = crate::config_store::OutputLayout::Pivot;
+    crate::process::write_sheet_for_layout(&mut wb, &data, "Sheet 2", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_constant_columns1()
+
+/// Test 1 for crate::process::split_into_sheets(), with rows straddling two sheets
+#[test]
+pub fn process_split_into_sheets1() {
+    let data: Vec<crate::data::Data> = (1..=5)
+        .map(|i| crate::data::Data::new1(format!("Sample{:03}", i), vec![crate::data::Row::new("P".to_string(), i as f64)]))
+        .collect();
+    let mut config = sample_config();
+    assert_eq!(crate::process::split_into_sheets("Sheet", data.clone(), &config).len(), 1);
+
+    config.max_rows_per_sheet = Some(3);
+    let sheets = crate::process::split_into_sheets("Sheet", data.clone(), &config);
+    assert_eq!(sheets.len(), 2);
+    assert_eq!(sheets[0].0, "Sheet_1");
+    assert_eq!(sheets[1].0, "Sheet_2");
+    assert_eq!(sheets[0].1, data[0..3].to_vec());
+    assert_eq!(sheets[1].1, data[3..5].to_vec());
+
+    // long names are shortened so the suffix still fits
+    let long_name = "x".repeat(31);
+    let sheets = crate::process::split_into_sheets(&long_name, data.clone(), &config);
+    assert_eq!(sheets[1].0, format!("{}_2", "x".repeat(29)));
+
+    // the header row is repeated on each sheet
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_split_into_sheets1");
+    std::fs::create_dir_all(&dir).unwrap();
+    for (sheet_name, sheet_data) in crate::process::split_into_sheets("Sheet", data, &config) {
+        let csv_path = dir.join(format!("{}.csv", sheet_name));
+        crate::process::write_output_to_csv(&sheet_data, &csv_path, &config).unwrap();
+        assert!(std::fs::read_to_string(csv_path).unwrap().starts_with("Test Name,P\n"));
+    }//end checking each sheet
+}//end process_split_into_sheets1()
+
+/// Test for crate::logging::LogBuffer and crate::logging::LogEntry::to_line()
+#[test]
+pub fn logging_log_buffer1() {
+    use crate::logging::{LogBuffer, LogEntry, LogLevel};
+    let entry = |i: usize| LogEntry {
+        time: time::macros::datetime!(2024-05-01 13:04:59 UTC),
+        level: if i % 2 == 0 {LogLevel::Info} else {LogLevel::Warn},
+        message: format!("message {}", i),
+    };
+    assert_eq!(entry(1).to_line(), "2024-05-01 13:04:59 [WARN] message 1");
+
+    // once full, the oldest entries are dropped
+    let mut buffer = LogBuffer::new(3);
+    for i in 0..5 {buffer.push(entry(i));}
+    assert_eq!(buffer.entries(), vec![entry(2), entry(3), entry(4)]);
+
+    let mut empty = LogBuffer::new(0);
+    empty.push(entry(0));
+    assert!(empty.entries().is_empty());
+
+    crate::logging::log_error("logging_log_buffer1 test error");
+    assert!(crate::logging::get_log_entries().iter().any(|e| e.level == LogLevel::Error && e.message == "logging_log_buffer1 test error"));
+}//end logging_log_buffer1()
+
+/// Test for the source file column from crate::process::get_wide_table() and
+/// crate::process::write_output_to_sheet()
+#[test]
+pub fn process_source_file_column1() {
+    let data = vec![
+        crate::data::Data::new2("Sample001".to_string(), vec![crate::data::Row::new("P".to_string(), 1.)], Some("first.txt".to_string())),
+        crate::data::Data::new1("Average".to_string(), vec![crate::data::Row::new("P".to_string(), 2.)]),
+    ];
+    let mut config = sample_config();
+    config.constant_columns = vec![("Operator".to_string(), "NB".to_string())];
+    assert_eq!(crate::process::get_wide_table(&data, &config)[0], vec!["Test Name", "P", "Operator"]);
+
+    config.include_source_file_column = true;
+    assert_eq!(crate::process::get_wide_table(&data, &config), vec![
+        vec!["Test Name", "P", "Source File", "Operator"],
+        vec!["Sample001", "1.00", "first.txt", "NB"],
+        vec!["Average", "2.00", "", "NB"],
+    ]);
+
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_output_to_sheet(&mut wb, &data, "sources", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_source_file_column1()
+
+/// Test for crate::process::write_output_to_csv(), reading the csv back in
+/// to make sure the values match what was written, to the configured decimal places.
+#[test]
+pub fn process_write_output_to_csv_round_trip() {
+    let data = vec![
+        crate::data::Data::new1("Sample, 001".to_string(), vec![
+            crate::data::Row::new("P".to_string(), 85.25),
+            crate::data::Row::new("L".to_string(), 0.1),
+        ]),
+        crate::data::Data::new1("Sample002".to_string(), vec![
+            crate::data::Row::new("P".to_string(), -3.),
+            crate::data::Row::new("L".to_string(), 1234.5678),
+        ]),
+    ];
+    let mut config = sample_config();
+    config.header_decimal_places.insert("L".to_string(), 3);
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_write_output_to_csv_round_trip");
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("output.csv");
+    crate::process::write_output_to_csv(&data, &csv_path, &config).unwrap();
+
+    let contents = std::fs::read_to_string(&csv_path).unwrap();
+    let mut lines = contents.lines();
+    assert_eq!(lines.next(), Some("Test Name,P,L"));
+    for data_file in data.iter() {
+        let line = lines.next().unwrap();
+        // the test name is quoted because of its comma, so take it off the front first
+        let quoted_name = format!("\"{}\",", data_file.test_name);
+        let unquoted_name = format!("{},", data_file.test_name);
+        let values = line.strip_prefix(&quoted_name).or(line.strip_prefix(&unquoted_name)).unwrap();
+        let values: Vec<&str> = values.split(',').collect();
+        assert_eq!(values.len(), data_file.row_data.len());
+        for (value, row) in values.iter().zip(data_file.row_data.iter()) {
+            let places = config.get_decimal_places(&row.header) as i32;
+            assert_eq!(value.split('.').nth(1).map(|decimals| decimals.len() as i32), Some(places));
+            assert!((value.parse::<f64>().unwrap() - row.value).abs() <= 0.5 * 10f64.powi(-places));
+        }//end checking each value in the row
+    }//end checking each row read back in
+    // values are rounded rather than written with full precision
+    assert!(contents.contains("-3.00,1234.568\n") && contents.contains("85.25,0.100\n"));
+    assert_eq!(lines.next(), None);
+}//end process_write_output_to_csv_round_trip()
+
+/// Test for crate::process::get_file_sheet_names() and crate::process::write_all_files()
+#[test]
+pub fn process_get_file_sheet_names1() {
+    let file = |test_name: &str, source_file: Option<&str>| crate::data::Data::new2(
+        test_name.to_string(),
+        vec![crate::data::Row::new("P".to_string(), 1.)],
+        source_file.map(|s| s.to_string()),
+    );
+    let files = vec![
+        file("24-PCF4001", Some("a.txt")),
+        file("Run: 2/3 [retest]", None),
+        file("", Some("c.txt")),
+        file("24-pcf4001", None),
+        file("", None),
+        file("A very long test name that goes past the limit", None),
+    ];
+    assert_eq!(crate::process::get_file_sheet_names(&files), vec![
+        "24-PCF4001",
+        "Run_ 2_3 _retest_",
+        "c.txt",
+        "Sheet4",
+        "Sheet5",
+        "A very long test name that goes",
+    ]);
+
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_all_files(&mut wb, &files, &sample_config()).unwrap();
+    assert_eq!(wb.worksheets().len(), files.len());
+    assert!(wb.save_to_buffer().is_ok());
+
+    let mut config = sample_config();
+    config.sheet_per_file = true;
+    config.max_rows_per_sheet = Some(1);
+    let sheets = crate::process::split_into_sheets("ignored", files[0..2].to_vec(), &config);
+    assert_eq!(sheets.iter().map(|(name,_)| name.as_str()).collect::<Vec<&str>>(), vec!["24-PCF4001", "Run_ 2_3 _retest_"]);
+}//end process_get_file_sheet_names1()
+
+#[test]
+/// Test 1 for crate::cli::parse_args()
+pub fn cli_parse_args1() {
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+    assert_eq!(crate::cli::parse_args(&[]), Ok(None));
+    assert_eq!(crate::cli::parse_args(&args(&["--input", "a.txt", "b.txt", "--output", "report.xlsx"])), Ok(Some(crate::cli::CliArgs {
+        inputs: vec![std::path::PathBuf::from("a.txt"), std::path::PathBuf::from("b.txt")],
+        output: std::path::PathBuf::from("report.xlsx"),
+        config: None,
+        dry_run: false,
+    })));
+    assert_eq!(crate::cli::parse_args(&args(&["-i", "a.txt", "-o", "out", "--input", "b.txt"])).unwrap().unwrap().inputs.len(), 2);
+    assert!(crate::cli::parse_args(&args(&["--input", "a.txt"])).is_err());
+    assert!(crate::cli::parse_args(&args(&["--output", "report.xlsx"])).is_err());
+    assert!(crate::cli::parse_args(&args(&["--input", "--output", "report.xlsx"])).is_err());
+    assert!(crate::cli::parse_args(&args(&["--input", "a.txt", "--output"])).is_err());
+    assert!(crate::cli::parse_args(&args(&["--input", "a.txt", "--output", "a", "--output", "b"])).is_err());
+    assert!(crate::cli::parse_args(&args(&["--verbose"])).is_err());
+}//end cli_parse_args1()
+
+#[test]
+/// Test 2 for crate::cli::parse_args(), for --config
+pub fn cli_parse_args2() {
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+    let parsed = crate::cli::parse_args(&args(&["--config", "profiles/mixolab.json", "--input", "a.txt", "--output", "out.csv"])).unwrap().unwrap();
+    assert_eq!(parsed.config, Some(std::path::PathBuf::from("profiles/mixolab.json")));
+    assert_eq!(parsed.inputs, vec![std::path::PathBuf::from("a.txt")]);
+    assert!(crate::cli::parse_args(&args(&["--input", "a.txt", "--output", "out.csv", "--config"])).is_err());
+    assert!(crate::cli::parse_args(&args(&["-c", "a.json", "-c", "b.json", "-i", "a.txt", "-o", "out.csv"])).is_err());
+}//end cli_parse_args2()
+
+#[test]
+/// Test 1 for crate::process::parse_dropped_paths()
+pub fn process_parse_dropped_paths1() {
+    let dropped = "/home/user/runs/a.txt\nfile:///home/user/runs/run%202.TXT\r\n\nfile:///C:/runs/c.txt\n/home/user/runs/report.xlsx\n/home/user/runs/notes";
+    assert_eq!(crate::process::parse_dropped_paths(dropped), vec![
+        std::path::PathBuf::from("/home/user/runs/a.txt"),
+        std::path::PathBuf::from("/home/user/runs/run 2.TXT"),
+        std::path::PathBuf::from("C:/runs/c.txt"),
+    ]);
+    assert!(crate::process::parse_dropped_paths("report.xlsx\nfile:///tmp/%zz.csv").is_empty());
+    assert!(crate::process::parse_dropped_paths("").is_empty());
+}//end process_parse_dropped_paths1()
+
+/// Test 1 for crate::process::run_with_progress()
+#[test]
+pub fn process_run_with_progress1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run_with_progress1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let good_input = dir.join("good.txt");
+    let missing_input = dir.join("missing.txt");
+    let _ = std::fs::remove_file(&missing_input);
+    std::fs::write(&good_input, sample_file_lines().join("\n")).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "test-sheet");
+    let mut steps = Vec::new();
+    let report = crate::process::run_with_progress(
+        &vec![good_input.clone(), missing_input.clone(), good_input.clone()],
+        &output,
+        &sample_config(),
+        &mut |done,total| {steps.push((done,total)); true},
+    );
+    assert!(report.succeeded());
+    assert!(!report.canceled);
+    assert_eq!(steps, vec![(1,4),(2,4),(3,4),(4,4)]);
+
+    let mut steps = Vec::new();
+    let report = crate::process::run_with_progress(&vec![missing_input], &output, &sample_config(), &mut |done,total| {steps.push((done,total)); true});
+    assert!(!report.succeeded());
+    assert_eq!(steps, vec![(1,2)]);
+}//end process_run_with_progress1()
+
+/// Test 2 for crate::process::run_with_progress(), canceling partway through
+#[test]
+pub fn process_run_with_progress2() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run_with_progress2");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("good.txt");
+    std::fs::write(&input, sample_file_lines().join("\n")).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "test-sheet");
+    let _ = std::fs::remove_file(&output.path);
+    let mut steps = Vec::new();
+    let report = crate::process::run_with_progress(
+        &vec![input.clone(), input.clone(), input.clone()],
+        &output,
+        &sample_config(),
+        &mut |done,total| {steps.push((done,total)); done < 2},
+    );
+    assert!(report.canceled);
+    assert!(report.succeeded());
+    assert_eq!(report.files.len(), 2);
+    assert_eq!(steps, vec![(1,4),(2,4),(4,4)]);
+    assert!(output.path.exists());
+
+    // canceling after the last file doesn't skip anything
+    let report = crate::process::run_with_progress(&vec![input], &output, &sample_config(), &mut |_,_| false);
+    assert!(!report.canceled);
+    assert!(report.succeeded());
+}//end process_run_with_progress2()
+
+/// Test 1 for crate::process::get_preview_text()
+#[test]
+pub fn process_get_preview_text1() {
+    let data = crate::data::Data::new2(
+        "24-PCF4001".to_string(),
+        vec![
+            crate::data::Row::new("P".to_string(), 85.),
+            crate::data::Row::new1("L".to_string(), f64::NAN, Some("LOW".to_string())),
+        ],
+        Some("run1.txt".to_string()),
+    );
+    assert_eq!(
+        crate::process::get_preview_text(&data, &sample_config()),
+        "Preview of \"24-PCF4001\" from run1.txt, with 2 row(s):\n\nP\t85.00\nL\tNaN\tLOW"
+    );
+}//end process_get_preview_text1()
+
+/// Test 1 for crate::config_store::parse_config()
+#[test]
+pub fn config_store_parse_config1() {
+    // files from before profiles hold a single config
+    let mut old_config = ConfigStore::default();
+    old_config.read_start_idx = 42;
+    let old_file = serde_json::to_string(&old_config).unwrap();
+    let config_profiles = crate::config_store::parse_config(&old_file).unwrap();
+    assert_eq!(config_profiles.get_active_name(), crate::config_store::DEFAULT_PROFILE_NAME);
+    assert_eq!(config_profiles.get_active(), &old_config);
+
+    let mut config_profiles = crate::config_store::ConfigProfiles::new(old_config.clone());
+    assert!(config_profiles.set_active("Lab 2").is_err());
+    let new_file = format!(r#"{{"profiles": {{"Lab 1": {}, "Lab 2": {{"read_start_idx": 7}}}}, "active": "Lab 2"}}"#, old_file);
+    config_profiles = crate::config_store::parse_config(&new_file).unwrap();
+    assert_eq!(config_profiles.get_profile_names(), vec!["Lab 1", "Lab 2"]);
+    assert_eq!(config_profiles.get_active().read_start_idx, 7);
+    assert!(config_profiles.set_active("Lab 1").is_ok());
+    assert_eq!(config_profiles.get_active(), &old_config);
+    let mut changed_config = old_config.clone();
+    changed_config.read_start_idx = 8;
+    config_profiles.update_active(changed_config);
+    assert_eq!(config_profiles.get_active().read_start_idx, 8);
+
+    // an active profile that doesn't exist falls back to the first by name
+    let missing_active = format!(r#"{{"profiles": {{"Lab 2": {}, "Lab 1": {}}}, "active": "Lab 3"}}"#, old_file, old_file);
+    assert_eq!(crate::config_store::parse_config(&missing_active).unwrap().get_active_name(), "Lab 1");
+    assert!(crate::config_store::parse_config("not json").is_err());
+}//end config_store_parse_config1()
+
+/// Test 1 for crate::config_store::migrate(), and how configs are read and written with it
+#[test]
+pub fn config_store_migrate1() {
+    use crate::config_store::CONFIG_SCHEMA_VERSION;
+    // a config from before schema_version, with a setting that can't be read
+    let raw = serde_json::json!({"read_start_idx": 42, "read_max_rows": "ten", "csv_delimiter": ";", "not_a_setting": true});
+    let config = crate::config_store::migrate(raw);
+    assert_eq!(config.schema_version, CONFIG_SCHEMA_VERSION);
+    assert_eq!(config.read_start_idx, 42);
+    assert_eq!(config.csv_delimiter, ';');
+    assert_eq!(config.read_max_rows, ConfigStore::default().read_max_rows);
+    assert_eq!(crate::config_store::migrate(serde_json::json!([1, 2])), ConfigStore::default());
+
+    // one bad setting in a profile doesn't lose the rest of the file
+    let file = r#"{"profiles": {"Lab 1": {"schema_version": 1, "read_start_idx": 7, "output_layout": "Sideways"}}, "active": "Lab 1"}"#;
+    let config_profiles = crate::config_store::parse_config(file).unwrap();
+    assert_eq!(config_profiles.get_active().read_start_idx, 7);
+    assert_eq!(config_profiles.get_active().output_layout, ConfigStore::default().output_layout);
+
+    // the current version is written, whatever version the config was read as
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-config_store_migrate1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("test.config");
+    let old_config = ConfigStore { schema_version: 0, ..ConfigStore::default() };
+    crate::config_store::try_write_config(&config_path, &crate::config_store::ConfigProfiles::new(old_config)).unwrap();
+    let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(written["schema_version"], serde_json::json!(CONFIG_SCHEMA_VERSION));
+}//end config_store_migrate1()
+
+/// Test 1 for crate::config_store::try_write_config()
+#[test]
+pub fn config_store_try_write_config1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-try_write_config1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("config.json");
+
+    // a lone default profile is written the same way as before profiles
+    let config_profiles = crate::config_store::ConfigProfiles::default();
+    crate::config_store::try_write_config(&config_path, &config_profiles).unwrap();
+    let written = std::fs::read_to_string(&config_path).unwrap();
+    assert_eq!(serde_json::from_str::<ConfigStore>(&written).unwrap(), ConfigStore::default());
+    assert_eq!(crate::config_store::try_read_config(&config_path).unwrap(), config_profiles);
+
+    let file = r#"{"profiles": {"Lab 1": {}, "Lab 2": {"read_start_idx": 7}}, "active": "Lab 1"}"#;
+    let config_profiles = crate::config_store::parse_config(file).unwrap();
+    crate::config_store::try_write_config(&config_path, &config_profiles).unwrap();
+    assert_eq!(crate::config_store::try_read_config(&config_path).unwrap(), config_profiles);
+}//end config_store_try_write_config1()
+
+/// Test 1 for crate::config_store::get_config_path_candidates(), with the fallback to the current directory
+#[test]
+pub fn config_store_get_config_path_candidates1() {
+    let config_name = "alveograph-exporter-test-config_path_candidates1";
+    let candidates = crate::config_store::get_config_path_candidates(config_name).unwrap();
+    let exe_path = std::env::current_exe().unwrap().with_file_name(config_name).with_extension("json");
+    let cwd_path = std::env::current_dir().unwrap().join(config_name).with_extension("json");
+    assert_eq!(candidates.first(), Some(&exe_path));
+    assert!(candidates.contains(&cwd_path));
+    assert!(!crate::config_store::is_fallback_config_path(&exe_path, config_name));
+    assert_eq!(crate::config_store::is_fallback_config_path(&cwd_path, config_name), cwd_path != exe_path);
+    // without a file in any of them, the usual place is given back
+    assert_eq!(crate::config_store::try_read_config_path(config_name, false).unwrap(), exe_path);
+    // the log goes wherever the config file is
+    assert_eq!(
+        crate::config_store::try_get_log_path("test-log", Some(&cwd_path)).unwrap(),
+        cwd_path.with_file_name("test-log.log")
+    );
+}//end config_store_get_config_path_candidates1()
+
+/// Test 2 for crate::config_store::ConfigStore::validate(), checking each invariant on its own
+#[test]
+pub fn config_store_validate2() {
+    let problem_count = |change: &dyn Fn(&mut ConfigStore)| {
+        let mut config = sample_config();
+        change(&mut config);
+        config.validate().err().map(|problems| problems.len()).unwrap_or(0)
+    };
+    assert_eq!(problem_count(&|_| {}), 0);
+    assert_eq!(problem_count(&|c| c.read_start_header = "".to_string()), 1);
+    assert_eq!(problem_count(&|c| {c.normalize_header_match = true; c.read_start_header = " : ".to_string();}), 1);
+    assert_eq!(problem_count(&|c| c.read_end_header = c.read_start_header.clone()), 1);
+    assert_eq!(problem_count(&|c| c.csv_quote = c.csv_delimiter), 1);
+    assert_eq!(problem_count(&|c| c.csv_delimiter = '\n'), 1);
+    assert_eq!(problem_count(&|c| {c.read_row_mode = crate::config_store::ReadRowMode::Max; c.read_max_rows = 0;}), 1);
+    assert_eq!(problem_count(&|c| {c.read_row_mode = crate::config_store::ReadRowMode::Max; c.read_row_headers.clear();}), 0);
+    assert_eq!(problem_count(&|c| c.read_row_headers = vec!["".to_string()]), 1);
+    assert_eq!(problem_count(&|c| c.max_rows_per_sheet = Some(0)), 1);
+    assert_eq!(problem_count(&|c| c.max_rows_per_sheet = Some(1)), 0);
+    assert_eq!(problem_count(&|c| {c.include_summary_sheet = true; c.summary_stats.clear();}), 1);
+    assert_eq!(problem_count(&|c| {c.read_start_header = "".to_string(); c.max_rows_per_sheet = Some(0);}), 2);
+}//end config_store_validate2()
+
+/// Test 1 for crate::config_store::try_read_config(), with settings that can't be used
+#[test]
+pub fn config_store_try_read_config1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-try_read_config1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("config.json");
+
+    std::fs::write(&config_path, r#"{"read_start_header": "", "read_start_idx": 3}"#).unwrap();
+    match crate::config_store::try_read_config(&config_path) {
+        Err(crate::config_store::ConfigReadError::Invalid{config_profiles, problems}) => {
+            // the settings that were read aren't thrown away
+            assert_eq!(config_profiles.get_active().read_start_idx, 3);
+            assert_eq!(problems.len(), 1);
+        },
+        other => panic!("Expected invalid settings, got {:?}", other),
+    }//end matching the result of reading the config
+
+    std::fs::write(&config_path, r#"{"profiles": {"Lab 1": {}, "Lab 2": {"max_rows_per_sheet": 0}}, "active": "Lab 1"}"#).unwrap();
+    match crate::config_store::try_read_config(&config_path) {
+        Err(crate::config_store::ConfigReadError::Invalid{problems, ..}) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("In profile \"Lab 2\""));
+        },
+        other => panic!("Expected invalid settings, got {:?}", other),
+    }//end matching the result of reading the config
+
+    std::fs::write(&config_path, "{").unwrap();
+    assert!(matches!(crate::config_store::try_read_config(&config_path), Err(crate::config_store::ConfigReadError::Unreadable(_))));
+}//end config_store_try_read_config1()
+
+/// Test 1 for crate::config_store::export_config_json() and crate::config_store::import_config_json()
+#[test]
+pub fn config_store_export_import_config_json1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-export_import_config_json1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let json_path = dir.join("shared-config.json");
+
+    let mut config = sample_config();
+    config.read_start_idx = 12;
+    config.csv_delimiter = ';';
+    crate::config_store::export_config_json(&config, &json_path).unwrap();
+    assert_eq!(crate::config_store::import_config_json(&json_path).unwrap(), config);
+
+    // a config that can't be used isn't imported
+    config.max_rows_per_sheet = Some(0);
+    crate::config_store::export_config_json(&config, &json_path).unwrap();
+    assert!(crate::config_store::import_config_json(&json_path).unwrap_err().contains("max_rows_per_sheet"));
+
+    std::fs::write(&json_path, "[1, 2, 3]").unwrap();
+    assert!(crate::config_store::import_config_json(&json_path).is_err());
+    assert!(crate::config_store::import_config_json(&dir.join("missing.json")).is_err());
+}//end config_store_export_import_config_json1()
+
+/// Test 1 for crate::data::Data::out_of_range()
+#[test]
+pub fn data_out_of_range1() {
+    let data = crate::data::Data::new2(
+        "24-PCF4001".to_string(),
+        vec![
+            crate::data::Row::new("P".to_string(), 85.),
+            crate::data::Row::new("L".to_string(), f64::NAN),
+            crate::data::Row::new("W".to_string(), 450.),
+            crate::data::Row::new("G".to_string(), 3.),
+        ],
+        None,
+    );
+    let mut config = sample_config();
+    assert!(data.out_of_range(&config).is_empty());
+    config.value_ranges.insert("W".to_string(), crate::config_store::ValueRange { min: Some(150.), max: Some(400.) });
+    config.value_ranges.insert("P".to_string(), crate::config_store::ValueRange { min: Some(85.), max: None });
+    config.value_ranges.insert("L".to_string(), crate::config_store::ValueRange { min: Some(10.), max: Some(20.) });
+    let out = data.out_of_range(&config);
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].header, "W");
+    assert_eq!(
+        crate::process::get_out_of_range_messages(&[data], &config),
+        vec!["24-PCF4001: W is 450, outside 150 to 400".to_string()]
+    );
+}//end data_out_of_range1()
+
+/// Test 1 for crate::config_store::ValueRange
+#[test]
+pub fn config_store_value_range1() {
+    let range = crate::config_store::ValueRange { min: Some(150.), max: None };
+    assert!(range.is_out_of_range(149.));
+    assert!(!range.is_out_of_range(150.));
+    assert!(!range.is_out_of_range(f64::INFINITY));
+    assert_eq!(range.to_string(), "at least 150");
+    assert_eq!(crate::config_store::ValueRange::default().to_string(), "any value");
+
+    let mut config = sample_config();
+    config.value_ranges.insert("W".to_string(), crate::config_store::ValueRange { min: Some(400.), max: Some(150.) });
+    let problems = config.validate().unwrap_err();
+    assert!(problems.iter().any(|p| p.contains("\"W\"")));
+
+    let mut json = serde_json::to_value(sample_config()).unwrap();
+    json["value_ranges"] = serde_json::json!({"W": {"max": 400}});
+    let parsed: crate::config_store::ConfigStore = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.value_ranges["W"], crate::config_store::ValueRange { min: None, max: Some(400.) });
+}//end config_store_value_range1()
+
+/// Test 3 for crate::process::run_with_progress(), with values out of range
+#[test]
+pub fn process_run_with_progress3() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run_with_progress3");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("good.txt");
+    std::fs::write(&input, sample_file_lines().join("\n")).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "test-sheet");
+    let _ = std::fs::remove_file(&output.path);
+    let mut config = sample_config();
+    config.value_ranges.insert("L".to_string(), crate::config_store::ValueRange { min: Some(5.), max: None });
+    let report = crate::process::run_with_progress(&vec![input], &output, &config, &mut |_,_| true);
+    assert!(report.succeeded());
+    assert_eq!(report.out_of_range.len(), 1);
+    assert!(report.out_of_range[0].contains("L is 2"));
+    assert!(output.path.exists());
+}//end process_run_with_progress3()
+
+/// Test 1 for crate::process::get_autofilter_range()
+#[test]
+pub fn process_get_autofilter_range1() {
+    let mut config = sample_config();
+    let data = vec![
+        crate::data::Data::new2("A".to_string(), vec![crate::data::Row::new("P".to_string(), 1.), crate::data::Row::new("L".to_string(), 2.)], None),
+        crate::data::Data::new2("B".to_string(), vec![crate::data::Row::new("P".to_string(), 3.)], None),
+    ];
+    assert_eq!(crate::process::get_autofilter_range(&data, &config), Some((2, 0, 4, 2)));
+    config.parse_row_flags = true;
+    config.include_source_file_column = true;
+    config.constant_columns = vec![("Operator".to_string(), "NS".to_string())];
+    assert_eq!(crate::process::get_autofilter_range(&data, &config), Some((2, 0, 4, 6)));
+
+    // no filter without any headers
+    let empty_rows = vec![crate::data::Data::new2("A".to_string(), Vec::new(), None)];
+    assert_eq!(crate::process::get_autofilter_range(&empty_rows, &config), None);
+    assert_eq!(crate::process::get_autofilter_range(&[], &config), None);
+}//end process_get_autofilter_range1()
+
+/// Test for crate::process::write_output_to_sheet() with and without config header_units,
+/// making sure the data rows and autofilter move down to make room for the units row.
+#[test]
+pub fn process_write_output_to_sheet_units_row() {
+    let mut config = sample_config();
+    config.excel_freeze_and_filter = true;
+    let data = vec![
+        crate::data::Data::new2("A".to_string(), vec![crate::data::Row::new("P".to_string(), 1.), crate::data::Row::new("L".to_string(), 2.)], None),
+        crate::data::Data::new2("B".to_string(), vec![crate::data::Row::new("P".to_string(), 3.), crate::data::Row::new("L".to_string(), 4.)], None),
+    ];
+    assert_eq!(crate::process::get_first_data_row(&config), 3);
+    assert_eq!(crate::process::get_autofilter_range(&data, &config), Some((2, 0, 4, 2)));
+    let mut wb = crate::process::get_workbook();
+    let range = crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 1", &config).unwrap().unwrap();
+    assert_eq!((range.first_row, range.last_row), (3, 4));
+    assert_eq!(range.get_header_ref("P"), Some("'Sheet 1'!B4:B5".to_string()));
+
+    // only L has a unit, so the P column's unit is left blank
+    config.header_units.insert("L".to_string(), "mm".to_string());
+    assert_eq!(crate::process::get_first_data_row(&config), 4);
+    assert_eq!(crate::process::get_autofilter_range(&data, &config), Some((3, 0, 5, 2)));
+    let range = crate::process::write_output_to_sheet(&mut wb, &data, "Sheet 2", &config).unwrap().unwrap();
+    assert_eq!((range.first_row, range.last_row), (4, 5));
+    assert_eq!(range.get_header_ref("P"), Some("'Sheet 2'!B5:B6".to_string()));
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_write_output_to_sheet_units_row()
+
+/// Test 1 for crate::process::get_column_widths()
+#[test]
+pub fn process_get_column_widths1() {
+    let table = vec![
+        vec!["Test Name".to_string(), "P".to_string(), "A very long header that goes on and on and on and on".to_string()],
+        vec!["Sample001-1234567".to_string(), "85".to_string(), "".to_string()],
+        vec!["B".to_string(), "1234.5".to_string()],
+    ];
+    let widths = crate::process::get_column_widths(&table);
+    assert_eq!(widths.len(), 3);
+    // the test name is wider than its header
+    assert_eq!(widths[0], 17. + 2.);
+    // 1234.5 is shown as 1234.50, with two decimals
+    assert_eq!(widths[1], 7. + 2.);
+    // long headers are clamped
+    assert_eq!(widths[2], 50.);
+    assert!(crate::process::get_column_widths(&[]).is_empty());
+}//end process_get_column_widths1()
+
+/// Test 1 for crate::process::get_summary_formula() and crate::process::SheetRange
+#[test]
+pub fn process_get_summary_formula1() {
+    let data = vec![
+        crate::data::Data::new1("A".to_string(), vec![crate::data::Row::new("P".to_string(), 1.), crate::data::Row::new("L".to_string(), 2.)]),
+        crate::data::Data::new1("B".to_string(), vec![crate::data::Row::new("P".to_string(), 3.), crate::data::Row::new("L".to_string(), 4.)]),
+    ];
+    let mut config = sample_config();
+    config.parse_row_flags = true;
+    let mut wb = crate::process::get_workbook();
+    let sheet_range = crate::process::write_output_to_sheet(&mut wb, &data, "Lab's Sheet", &config).unwrap().unwrap();
+    assert_eq!(sheet_range.first_row, 3);
+    assert_eq!(sheet_range.last_row, 4);
+    assert_eq!(sheet_range.get_header_ref("P").unwrap(), "'Lab''s Sheet'!B4:B5");
+    // with flags, L comes after the flag column for P
+    assert_eq!(sheet_range.get_header_ref("L").unwrap(), "'Lab''s Sheet'!D4:D5");
+    assert_eq!(sheet_range.get_header_ref("G"), None);
+    assert_eq!(crate::process::write_output_to_sheet(&mut wb, &Vec::new(), "Empty", &config).unwrap(), None);
+
+    use crate::config_store::SummaryStat;
+    let refs = vec!["'Sheet1'!B4:B40".to_string(), "'Sheet2'!B4:B10".to_string()];
+    assert_eq!(crate::process::get_summary_formula(SummaryStat::Mean, &refs[..1]).unwrap(), "=IFERROR(AVERAGE('Sheet1'!B4:B40),\"\")");
+    assert_eq!(crate::process::get_summary_formula(SummaryStat::Count, &refs).unwrap(), "=COUNT('Sheet1'!B4:B40,'Sheet2'!B4:B10)");
+    assert_eq!(
+        crate::process::get_summary_formula(SummaryStat::StdErr, &refs[..1]).unwrap(),
+        "=IFERROR(STDEV('Sheet1'!B4:B40)/SQRT(COUNT('Sheet1'!B4:B40)),\"\")"
+    );
+    assert_eq!(crate::process::get_summary_formula(SummaryStat::Max, &[]), None);
+
+    // formulas and precomputed values can be mixed in one summary sheet
+    let summaries = crate::data::summarize_data(&data);
+    config.summary_stats = vec![SummaryStat::Count, SummaryStat::Mean, SummaryStat::Cv];
+    crate::process::write_summary_sheet(&mut wb, &summaries, &[sheet_range], "Summary", &config).unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+}//end process_get_summary_formula1()
+
+/// Test 1 for crate::data::read_file_bytes_to_string()
+#[test]
+pub fn data_read_file_bytes_to_string1() {
+    let text = "Test name\t:\tSample001\nStandard\t : \tAverage\nP\t1\n";
+    // utf-16le with a byte order mark, as from some older exports
+    let mut utf16_bytes = vec![0xFF, 0xFE];
+    utf16_bytes.extend(text.encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+    assert_eq!(crate::data::read_file_bytes_to_string(&utf16_bytes), (text.to_string(), "UTF-16LE"));
+
+    let mut utf16be_bytes = vec![0xFE, 0xFF];
+    utf16be_bytes.extend(text.encode_utf16().flat_map(|unit| unit.to_be_bytes()));
+    assert_eq!(crate::data::read_file_bytes_to_string(&utf16be_bytes), (text.to_string(), "UTF-16BE"));
+
+    let mut utf8_bom_bytes = vec![0xEF, 0xBB, 0xBF];
+    utf8_bom_bytes.extend(text.as_bytes());
+    assert_eq!(crate::data::read_file_bytes_to_string(&utf8_bom_bytes), (text.to_string(), "UTF-8"));
+    assert_eq!(crate::data::read_file_bytes_to_string("25°C".as_bytes()), ("25°C".to_string(), "UTF-8"));
+
+    // latin-1 degree sign, and a windows-1252 euro sign
+    assert_eq!(crate::data::read_file_bytes_to_string(&[b'2', b'5', 0xB0, b'C', b' ', 0x80]), ("25°C €".to_string(), "Windows-1252"));
+
+    // a utf-16 file should parse the same as a utf-8 one
+    let (contents, _) = crate::data::read_file_bytes_to_string(&utf16_bytes);
+    let (data, _) = crate::data::read_data_from_file("utf16.txt", &contents, &sample_config()).unwrap();
+    assert_eq!(data.test_name, "Sample001");
+}//end data_read_file_bytes_to_string1()
+
+/// Test 1 for crate::data::get_error_context()
+#[test]
+pub fn data_get_error_context1() {
+    use crate::data::DataParseError;
+    let contents = "Standard\t : \tAverage\r\nP\t1\r\nL\t2.x\r\nG\t3";
+    assert_eq!(
+        crate::data::get_error_context(contents, &DataParseError::InvalidNumber{line: 3, raw: "2.x".to_string()}).unwrap(),
+        "  2 | P   1\n> 3 | L   2.x\n    |     ^^^\n  4 | G   3"
+    );
+    // the first line has nothing above it, and the whole line is pointed at
+    assert_eq!(
+        crate::data::get_error_context(contents, &DataParseError::MissingValue{line: 1, text: "Standard".to_string()}).unwrap(),
+        "> 1 | Standard     :  Average\n    | ^^^^^^^^^^^^^^^^^^^^^^^\n  2 | P   1"
+    );
+    let long_contents = (1..=10).map(|num| format!("row {}", num)).collect::<Vec<_>>().join("\n");
+    assert_eq!(
+        crate::data::get_error_context(&long_contents, &DataParseError::UnexpectedRow{line: 10, expected: "P".to_string(), text: "row 10".to_string()}).unwrap(),
+        "   9 | row 9\n> 10 | row 10\n     | ^^^^^^"
+    );
+    assert_eq!(crate::data::get_error_context(contents, &DataParseError::InvalidNumber{line: 9, raw: "".to_string()}), None);
+    assert_eq!(crate::data::get_error_context(contents, &DataParseError::NoTestName), None);
+}//end data_get_error_context1()
+
+/// Test 1 for crate::config_store::ConfigStore::add_recent_inputs()
+#[test]
+pub fn config_store_add_recent_inputs1() {
+    use std::path::PathBuf;
+    let mut config = sample_config();
+    config.add_recent_inputs(&[]);
+    assert!(config.recent_inputs.is_empty());
+    config.add_recent_inputs(&[PathBuf::from("a.txt"), PathBuf::from("b.txt")]);
+    config.add_recent_inputs(&[PathBuf::from("c.txt")]);
+    // picking the same files again moves them back to the front
+    config.add_recent_inputs(&[PathBuf::from("a.txt"), PathBuf::from("b.txt")]);
+    assert_eq!(config.recent_inputs, vec![vec!["a.txt".to_string(), "b.txt".to_string()], vec!["c.txt".to_string()]]);
+
+    for idx in 0..15 {config.add_recent_inputs(&[PathBuf::from(format!("{}.txt", idx))]);}
+    assert_eq!(config.recent_inputs.len(), crate::config_store::RECENT_INPUTS_MAX);
+    assert_eq!(config.recent_inputs[0], vec!["14.txt".to_string()]);
+
+    // extra entries from editing the file by hand are trimmed when saving
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-add_recent_inputs1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("config.json");
+    config.recent_inputs = (0..20).map(|idx| vec![format!("{}.txt", idx)]).collect();
+    crate::config_store::try_write_config(&config_path, &crate::config_store::ConfigProfiles::new(config)).unwrap();
+    let read_back = crate::config_store::try_read_config(&config_path).unwrap();
+    assert_eq!(read_back.get_active().recent_inputs.len(), crate::config_store::RECENT_INPUTS_MAX);
+}//end config_store_add_recent_inputs1()
+
+/// Test 1 for crate::data::find_duplicate_test_names() and crate::data::disambiguate_test_names()
+#[test]
+pub fn data_disambiguate_test_names1() {
+    let names = |data: &Vec<crate::data::Data>| data.iter().map(|d| d.test_name.clone()).collect::<Vec<String>>();
+    let mut data: Vec<crate::data::Data> = ["A", "B", "A", "C", "A", "B (2)", "B"].iter()
+        .map(|name| crate::data::Data::new1(name.to_string(), Vec::new()))
+        .collect();
+    assert_eq!(crate::data::find_duplicate_test_names(&data), vec![("A".to_string(), 3), ("B".to_string(), 2)]);
+    crate::data::disambiguate_test_names(&mut data);
+    // "B (2)" is already taken by another file, so the second "B" skips to 3
+    assert_eq!(names(&data), vec!["A", "B", "A (2)", "C", "A (3)", "B (2)", "B (3)"]);
+    assert!(crate::data::find_duplicate_test_names(&data).is_empty());
+
+    let mut unique = vec![crate::data::Data::new1("X".to_string(), Vec::new()), crate::data::Data::new1("Y".to_string(), Vec::new())];
+    crate::data::disambiguate_test_names(&mut unique);
+    assert_eq!(names(&unique), vec!["X", "Y"]);
+}//end data_disambiguate_test_names1()
+
+/// Test 1 for crate::process::order_columns() and crate::process::get_display_header()
+#[test]
+pub fn process_order_columns1() {
+    let data = vec![crate::data::Data::new1("Sample001".to_string(), vec![
+        crate::data::Row::new("P".to_string(), 1.),
+        crate::data::Row::new("L".to_string(), 2.),
+        crate::data::Row::new("G".to_string(), 3.),
+        crate::data::Row::new("W".to_string(), 4.),
+    ])];
+    let mut config = sample_config();
+    // nothing changes without any column names set
+    assert_eq!(crate::process::order_columns(&data, &config), data);
+    assert_eq!(crate::process::get_wide_table(&data, &config)[0], vec!["Test Name","P","L","G","W"]);
+
+    config.column_display = vec![("W".to_string(), "Work".to_string()), ("G".to_string(), "".to_string()), ("Ie".to_string(), "Elasticity".to_string())];
+    let headers: Vec<String> = crate::process::order_columns(&data, &config)[0].row_data.iter().map(|row| row.header.clone()).collect();
+    assert_eq!(headers, vec!["W","G","P","L"]);
+    assert_eq!(crate::process::get_display_header("W", &config), "Work");
+    assert_eq!(crate::process::get_display_header("G", &config), "G");
+    assert_eq!(crate::process::get_display_header("P", &config), "P");
+    let table = crate::process::get_wide_table(&data, &config);
+    assert_eq!(table[0], vec!["Test Name","Work","G","P","L"]);
+    assert_eq!(table[1], vec!["Sample001","4.00","3.00","1.00","2.00"]);
+    config.parse_row_flags = true;
+    assert_eq!(crate::process::get_wide_table(&data, &config)[0][1..3], ["Work".to_string(), "Work Flag".to_string()]);
+
+    // summary formulas still find columns by the header that was read
+    let mut wb = crate::process::get_workbook();
+    let sheet_range = crate::process::write_output_to_sheet(&mut wb, &data, "Sheet1", &config).unwrap().unwrap();
+    assert_eq!(sheet_range.get_header_ref("W").unwrap(), "'Sheet1'!B4");
+    assert!(config.validate().is_ok());
+
+    config.column_display = vec![("W".to_string(), "Work".to_string()), ("P".to_string(), "Work".to_string()), ("L".to_string(), "".to_string()), ("L".to_string(), "Length".to_string())];
+    let problems = config.validate().unwrap_err();
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].contains("\"Work\""));
+    assert!(problems[1].contains("\"L\""));
+}//end process_order_columns1()
+
+/// Test 1 for crate::process::get_unique_sheet_name()
+#[test]
+pub fn process_get_unique_sheet_name1() {
+    assert_eq!(crate::process::get_unique_sheet_name("Data", &["Summary"]), "Data");
+    assert_eq!(crate::process::get_unique_sheet_name("Data", &["data", "Data 2"]), "Data 3");
+    let long_name = "a".repeat(31);
+    let unique_name = crate::process::get_unique_sheet_name(&long_name, &[long_name.as_str()]);
+    assert_eq!(unique_name.chars().count(), 31);
+    assert!(unique_name.ends_with("a 2"));
+}//end process_get_unique_sheet_name1()
+
+/// Test 1 for crate::process::open_or_create_workbook(), appending to an earlier run
+#[test]
+pub fn process_open_or_create_workbook1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_open_or_create_workbook1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("good.txt");
+    std::fs::write(&input, sample_file_lines().join("\n")).unwrap();
+    let mut output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "test-sheet");
+    let _ = std::fs::remove_file(&output.path);
+    let mut config = sample_config();
+    config.include_summary_sheet = true;
+    let report = crate::process::run_with_progress(&vec![input.clone()], &output, &config, &mut |_,_| true);
+    assert!(report.succeeded());
+    let first_names: Vec<&str> = report.sheets.iter().map(|record| record.sheet_name.as_str()).collect();
+    assert_eq!(first_names, vec!["test-sheet", "Summary"]);
+    assert!(crate::process::open_or_create_workbook(&report.sheets).is_ok());
+    // appending keeps the earlier sheets, and gives the new ones free names
+    output.append_to = report.sheets.clone();
+    let report = crate::process::run_with_progress(&vec![input], &output, &config, &mut |_,_| true);
+    assert!(report.succeeded());
+    let names: Vec<&str> = report.sheets.iter().map(|record| record.sheet_name.as_str()).collect();
+    assert_eq!(names, vec!["test-sheet", "Summary", "test-sheet 2", "Summary 2"]);
+    assert!(output.path.exists());
+}//end process_open_or_create_workbook1()
+
+/// Test 1 for crate::data::Data::nonzero_rows()
+#[test]
+pub fn data_nonzero_rows1() {
+    let data = crate::data::Data::new1("A".to_string(), vec![
+        crate::data::Row::new("P".to_string(), 0.),
+        crate::data::Row::new("L".to_string(), 0.0004),
+        crate::data::Row::new("W".to_string(), -0.0004),
+        crate::data::Row::new("G".to_string(), -2.5),
+        crate::data::Row::new("Ie".to_string(), -0.),
+        crate::data::Row::new("P/L".to_string(), f64::NAN),
+        crate::data::Row::new("PPR".to_string(), 12.),
+    ]);
+    let headers = |rows: Vec<&crate::data::Row>| rows.iter().map(|row| row.header.clone()).collect::<Vec<String>>();
+    // only exact zeros are dropped without an epsilon
+    assert_eq!(headers(data.nonzero_rows(0.)), vec!["L", "W", "G", "P/L", "PPR"]);
+    assert_eq!(headers(data.nonzero_rows(0.001)), vec!["G", "P/L", "PPR"]);
+    assert_eq!(headers(data.nonzero_rows(3.)), vec!["P/L", "PPR"]);
+}//end data_nonzero_rows1()
+
+/// Test 1 for crate::process::get_summary_data(), with skip_zero_rows
+#[test]
+pub fn process_get_summary_data1() {
+    let mut config = sample_config();
+    let data = vec![
+        crate::data::Data::new1("A".to_string(), vec![crate::data::Row::new("P".to_string(), 0.), crate::data::Row::new("L".to_string(), 2.)]),
+        crate::data::Data::new1("B".to_string(), vec![crate::data::Row::new("P".to_string(), 4.), crate::data::Row::new("L".to_string(), 0.)]),
+    ];
+    assert_eq!(crate::process::get_summary_data(&data, &config), data);
+    config.skip_zero_rows = true;
+    let summaries = crate::data::summarize_data(&crate::process::get_summary_data(&data, &config));
+    let summary = |header: &str| summaries.iter().find(|summary| summary.header == header).unwrap().clone();
+    assert_eq!(summary("P").count, 1);
+    assert_eq!(summary("P").mean, Some(4.));
+    assert_eq!(summary("L").mean, Some(2.));
+    config.average_all_inputs = true;
+    let averaged = crate::process::combine_data(data.clone(), &config);
+    // sorted by row_order_preference, which puts L before P
+    assert_eq!(averaged[0].row_data.iter().map(|row| row.value).collect::<Vec<f64>>(), vec![2., 4.]);
+    // skipped values are left blank rather than shifting the columns over
+    config.average_all_inputs = false;
+    let table = crate::process::get_wide_table(&data, &config);
+    assert_eq!(table[1][1], "");
+    assert_eq!(table[1][2], "2.00");
+    config.zero_epsilon = -1.;
+    assert!(config.validate().is_err());
+}//end process_get_summary_data1()
+
+/// Test 1 for crate::process::run_export()
+#[test]
+pub fn process_run_export1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_run_export1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = dir.join("output");
+    let _ = std::fs::remove_file(dir.join("output.xlsx"));
+    let config = sample_config();
+    let contents = sample_file_lines().join("\n");
+    let inputs = vec![
+        ("first.txt".to_string(), contents.clone()),
+        ("second.txt".to_string(), contents),
+        ("bad.txt".to_string(), "nothing to see here".to_string()),
+    ];
+    let report = crate::process::run_export(&inputs, &output, &config).unwrap();
+    assert_eq!(report.output_path, dir.join("output.xlsx"));
+    assert!(report.output_path.exists());
+    assert_eq!(report.files_processed, 2);
+    assert_eq!(report.rows_written, 2);
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].contains("bad.txt"));
+    assert_eq!(report.duplicates.len(), 1);
+    assert_eq!(report.duplicates[0].1, 2);
+    // nothing is written when no input can be used
+    let err = crate::process::run_export(&inputs[2..], &output, &config).unwrap_err();
+    assert!(matches!(err, crate::process::ExportError::NoUsableInputs(ref msgs) if msgs.len() == 1));
+    let mut bad_config = config.clone();
+    bad_config.zero_epsilon = f64::NAN;
+    assert!(matches!(crate::process::run_export(&inputs, &output, &bad_config), Err(crate::process::ExportError::InvalidConfig(_))));
+}//end process_run_export1()
+
+/// Test 1 for crate::process::export_parsed()
+#[test]
+pub fn process_export_parsed1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_export_parsed1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut config = sample_config();
+    config.output_format = crate::config_store::OutputFormat::Csv;
+    let contents = sample_file_lines().join("\n");
+    let inputs = vec![("first.txt".to_string(), contents.clone()), ("second.txt".to_string(), contents)];
+    // without renaming, the output matches run_export()
+    let export_report = crate::process::run_export(&inputs, &dir.join("export.csv"), &config).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("kept.csv"), "Sheet 1");
+    let parsed = crate::process::parse_named_inputs(&inputs, &config);
+    let report = crate::process::export_parsed(parsed, &output, &config, &mut |_| false, &mut |_,_| true);
+    assert!(!report.duplicates_renamed);
+    assert_eq!(report.clone().into_export_report().duplicates, export_report.duplicates);
+    let kept = std::fs::read_to_string(dir.join("kept.csv")).unwrap();
+    assert_eq!(kept, std::fs::read_to_string(dir.join("export.csv")).unwrap());
+    // renaming numbers the second test
+    let output = crate::process::OutputSpec::new(dir.join("renamed.csv"), "Sheet 1");
+    let parsed = crate::process::parse_named_inputs(&inputs, &config);
+    let mut asked = Vec::new();
+    let report = crate::process::export_parsed(parsed, &output, &config, &mut |duplicates| {asked.extend_from_slice(duplicates); true}, &mut |_,_| true);
+    assert!(report.duplicates_renamed);
+    assert_eq!(asked, report.duplicates);
+    assert_eq!(asked[0].1, 2);
+    let renamed = std::fs::read_to_string(dir.join("renamed.csv")).unwrap();
+    assert!(renamed.contains(" (2)") && !kept.contains(" (2)"));
+    // rename_duplicates isn't called when every name is different
+    let parsed = crate::process::parse_named_inputs(&inputs[..1], &config);
+    let report = crate::process::export_parsed(parsed, &output, &config, &mut |_| panic!("no duplicates to rename"), &mut |_,_| true);
+    assert!(report.duplicates.is_empty() && !report.duplicates_renamed);
+}//end process_export_parsed1()
+
+/// Test 1 for crate::process::ExportReport::summary_message()
+#[test]
+pub fn process_summary_message1() {
+    let mut config = sample_config();
+    let report = crate::process::ExportReport {
+        output_path: std::path::PathBuf::from("reports").join("report.xlsx"),
+        files_processed: 12,
+        rows_written: 12,
+        duplicates: vec![("Sample001".to_string(), 3)],
+        out_of_range: vec!["Sample002: W is 450, outside 150 to 400".to_string()],
+        zero_values_skipped: 4,
+        ..crate::process::ExportReport::default()
+    };
+    assert_eq!(report.summary_message(true, &config), "Processed 12 file(s), wrote 12 row(s) to report.xlsx (2 duplicate(s) renamed).");
+    assert_eq!(report.summary_message(false, &config), "Processed 12 file(s), wrote 12 row(s) to report.xlsx (2 duplicate test name(s) kept).");
+    config.skip_zero_rows = true;
+    config.value_ranges.insert("W".to_string(), crate::config_store::ValueRange { min: Some(150.), max: Some(400.) });
+    assert_eq!(report.summary_message(true, &config), "Processed 12 file(s), wrote 12 row(s) to report.xlsx (2 duplicate(s) renamed, 4 zero value(s) skipped, 1 value(s) out of range).");
+    let plain = crate::process::ExportReport { duplicates: Vec::new(), ..report };
+    assert_eq!(plain.summary_message(false, &sample_config()), "Processed 12 file(s), wrote 12 row(s) to report.xlsx.");
+
+    // the counts come from the run
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_summary_message1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("input.txt");
+    std::fs::write(&input, sample_file_lines().join("\n").replace("L\t2", "L\t0")).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "Sheet 1");
+    let run_report = crate::process::run(&[input.clone(), input], &output, &config);
+    assert_eq!((run_report.rows_written, run_report.zero_values_skipped), (2, 2));
+}//end process_summary_message1()
+
+/// Test for crate::logging::init_file_logger() and crate::logging::set_min_level()
+#[test]
+pub fn logging_file_logger1() {
+    use crate::logging::LogLevel;
+    assert!(LogLevel::Debug < LogLevel::Info);
+    assert_eq!(LogLevel::from_str(&LogLevel::Debug.to_string()), Some(LogLevel::Debug));
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-logging_file_logger1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let log_path = dir.join("test.log");
+    let _ = std::fs::remove_file(&log_path);
+    crate::logging::init_file_logger(log_path.clone(), LogLevel::Debug);
+    assert_eq!(crate::logging::get_log_file_path(), Some(log_path.clone()));
+    crate::logging::log_debug("logging_file_logger1 debug");
+    crate::logging::log_warn("logging_file_logger1 warning");
+    let contents = std::fs::read_to_string(&log_path).unwrap();
+    assert!(contents.contains("[DEBUG] logging_file_logger1 debug"));
+    assert!(contents.contains("[WARN] logging_file_logger1 warning"));
+    // with a higher level, less serious messages are ignored
+    crate::logging::set_min_level(LogLevel::Warn);
+    crate::logging::log_info("logging_file_logger1 ignored");
+    crate::logging::set_min_level(LogLevel::Info);
+    assert!(!std::fs::read_to_string(&log_path).unwrap().contains("logging_file_logger1 ignored"));
+    assert!(!crate::logging::get_log_entries().iter().any(|e| e.message == "logging_file_logger1 ignored"));
+}//end logging_file_logger1()
+
+/// Test 1 for config unit_conversions in crate::data::read_data_from_file()
+#[test]
+pub fn data_unit_conversions1() {
+    let mut config = sample_config();
+    config.unit_conversions.insert("P".to_string(), crate::config_store::UnitConversion { factor: 0.980665, offset: 0. });
+    config.unit_conversions.insert("G".to_string(), crate::config_store::UnitConversion { factor: 2., offset: -1. });
+    let (data, errs) = crate::data::read_data_from_file("test.txt", &sample_file_lines().join("\n"), &config).unwrap();
+    assert!(errs.is_empty());
+    let row = |header: &str| data.row_data.iter().find(|row| row.header == header).unwrap().clone();
+    let converted = |header: &str, value: f64, raw_value: f64| crate::data::Row {raw_value: Some(raw_value), ..crate::data::Row::new(header.to_string(), value)};
+    assert!(row("P").approx_eq(&converted("P", 0.980665, 1.), TEST_EPSILON));
+    assert!(row("G").approx_eq(&converted("G", 5., 3.), TEST_EPSILON));
+    // headers without a conversion pass through unchanged
+    assert!(row("L").approx_eq(&crate::data::Row::new("L".to_string(), 2.), TEST_EPSILON));
+    // a conversion with just a factor keeps the default offset of 0
+    let parsed: crate::config_store::UnitConversion = serde_json::from_str("{\"factor\": 0.980665}").unwrap();
+    assert_eq!(parsed, crate::config_store::UnitConversion { factor: 0.980665, offset: 0. });
+    config.unit_conversions.insert("L".to_string(), crate::config_store::UnitConversion { factor: 0., offset: 1. });
+    assert_eq!(config.validate().unwrap_err().len(), 1);
+}//end data_unit_conversions1()
+
+/// Test for config normalize_headers together with unit_conversions and
+/// ReadRowMode::Header in crate::data::read_data_from_file()
+#[test]
+pub fn data_normalize_headers_unit_conversions1() {
+    let mut config = sample_config();
+    config.normalize_headers = true;
+    config.unit_conversions.insert("P".to_string(), crate::config_store::UnitConversion { factor: 2., offset: 0. });
+    config.unit_conversions.insert("Dmax 2".to_string(), crate::config_store::UnitConversion { factor: 10., offset: 0. });
+    config.read_row_headers = vec!["P","Dmax 2","G"].iter().map(|s| s.to_string()).collect();
+    config.row_order_preference = Vec::new();
+    let mut file_lines = sample_file_lines();
+    file_lines[8] = "P\u{A0}\t1".to_string();
+    file_lines[9] = "Dmax\u{A0}2 \t2".to_string();
+    let (data, errs) = crate::data::read_data_from_file("test.txt", &file_lines.join("\n"), &config).unwrap();
+    assert!(errs.is_empty());
+    let converted = |header: &str, value: f64, raw_value: f64| crate::data::Row {raw_value: Some(raw_value), ..crate::data::Row::new(header.to_string(), value)};
+    assert_eq!(data.row_data.len(), 3);
+    assert!(data.row_data[0].approx_eq(&converted("P", 2., 1.), TEST_EPSILON));
+    assert!(data.row_data[1].approx_eq(&converted("Dmax 2", 20., 2.), TEST_EPSILON));
+    assert!(data.row_data[2].approx_eq(&crate::data::Row::new("G".to_string(), 3.), TEST_EPSILON));
+}//end data_normalize_headers_unit_conversions1()
+
+/// Test 1 for crate::data::apply_derived_metrics(), including P/L when L is 0
+#[test]
+pub fn data_apply_derived_metrics1() {
+    use crate::config_store::{DerivedMetric, DerivedOperator};
+    let metric = |name: &str, expression: &str| DerivedMetric { name: name.to_string(), expression: expression.to_string() };
+    assert_eq!(metric("W/L", " W / L ").parse_expression(), Some(("W".to_string(), DerivedOperator::Divide, "L".to_string())));
+    assert_eq!(metric("Bad", "P").parse_expression(), None);
+    assert_eq!(metric("Bad", "-P").parse_expression(), None);
+    let mut config = sample_config();
+    config.derived_metrics = vec![metric("P/L", "P/L"), metric("P*G", "P * G"), metric("Ratio", "P/L"), metric("Sum", "Ratio+G"), metric("Missing", "P-W")];
+    let row = |header: &str, value: f64| crate::data::Row::new(header.to_string(), value);
+    let mut data = crate::data::Data::new1("Sample001-1234567".to_string(), vec![row("P", 3.), row("L", 2.), row("G", 4.)]);
+    crate::data::apply_derived_metrics(&mut data, &config);
+    assert!(data.approx_eq(&crate::data::Data::new1("Sample001-1234567".to_string(), vec![
+        row("P", 3.), row("L", 2.), row("G", 4.), row("P/L", 1.5), row("P*G", 12.), row("Ratio", 1.5), row("Sum", 5.5),
+    ]), TEST_EPSILON), "{:?}", data);
+
+    // dividing by 0 skips the metric, and anything that uses it, with a warning
+    let mut data = crate::data::Data::new1("Sample002-zero-L".to_string(), vec![row("P", 3.), row("L", 0.), row("G", 4.)]);
+    crate::data::apply_derived_metrics(&mut data, &config);
+    assert_eq!(data.row_data.iter().map(|row| row.header.as_str()).collect::<Vec<&str>>(), vec!["P", "L", "G", "P*G"]);
+    assert!(crate::logging::get_log_entries().iter().any(|e| e.level == crate::logging::LogLevel::Warn && e.message.contains("P/L for Sample002-zero-L")));
+
+    // derived metrics are added when reading files too
+    config.derived_metrics = vec![metric("P/L", "P/L")];
+    let (data, _) = crate::data::read_data_from_file("test.txt", &sample_file_lines().join("\n"), &config).unwrap();
+    assert!(data.row_data.last().unwrap().approx_eq(&row("P/L", 0.5), TEST_EPSILON));
+    config.derived_metrics.push(metric("", "P+"));
+    assert_eq!(config.validate().unwrap_err().len(), 2);
+}//end data_apply_derived_metrics1()
+
+/// Test 2 for crate::data::apply_derived_metrics(), where config include_headers
+/// leaves out a header the metric uses, and row_order_preference places the metric
+#[test]
+pub fn data_apply_derived_metrics2() {
+    let mut config = sample_config();
+    config.derived_metrics = vec![
+        crate::config_store::DerivedMetric { name: "P/L".to_string(), expression: "P/L".to_string() },
+        crate::config_store::DerivedMetric { name: "P*G".to_string(), expression: "P*G".to_string() },
+    ];
+    config.include_headers = vec!["P","P/L"].iter().map(|s| s.to_string()).collect();
+    config.row_order_preference = vec!["P/L","P"].iter().map(|s| s.to_string()).collect();
+    let (data, errs) = crate::data::read_data_from_file("test.txt", &sample_file_lines().join("\n"), &config).unwrap();
+    assert!(errs.is_empty());
+    // L is still used for P/L even though it isn't written, and P*G is left out like any other header
+    assert_eq!(data.row_data.iter().map(|row| row.header.as_str()).collect::<Vec<&str>>(), vec!["P/L", "P"]);
+    assert!(data.row_data[0].approx_eq(&crate::data::Row::new("P/L".to_string(), 0.5), TEST_EPSILON));
+}//end data_apply_derived_metrics2()
+
+/// Test 1 for config input_layout in crate::data::read_data_from_file(),
+/// with header and value lines split by read_row_split_char, the default
+#[test]
+pub fn data_input_layout_header_line_value_line() {
+    let config = sample_config();
+    assert_eq!(config.input_layout, crate::config_store::InputLayout::HeaderLineValueLine);
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &sample_file_lines().join("\n"), &config).unwrap();
+    assert!(errs.is_empty());
+    let row = |header: &str, value: f64| crate::data::Row::new(header.to_string(), value);
+    assert!(data.approx_eq(&crate::data::Data::new2("Sample001-1234567".to_string(), vec![row("G", 3.), row("L", 2.), row("P", 1.)], Some("sample-filename".to_string())), TEST_EPSILON), "{:?}", data);
+}//end data_input_layout_header_line_value_line()
+
+/// Test 2 for config input_layout in crate::data::read_data_from_file(),
+/// with several tab separated pairs of header and value on each line
+#[test]
+pub fn data_input_layout_tab_delimited() {
+    let mut config = sample_config();
+    config.input_layout = crate::config_store::InputLayout::TabDelimited;
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 2;
+    let file_lines = [
+        "Test name\t:\tSample003-1111111",
+        "Standard\t : \tAverage",
+        "P\t45\tL\t60\t",
+        "G\t17.2\tW\tbad\tIe",
+        "not\tread",
+    ];
+    let (data, errs) = crate::data::read_data_from_file("tabs.txt", &file_lines.join("\n"), &config).unwrap();
+    assert_eq!(data.row_data.iter().map(|row| (row.header.as_str(), row.value)).collect::<Vec<(&str,f64)>>(), vec![("G", 17.2), ("L", 60.), ("P", 45.)]);
+    assert_eq!(errs, vec![
+        crate::data::DataParseError::InvalidNumber{line: 4, raw: "bad".to_string()},
+        crate::data::DataParseError::MissingValue{line: 4, text: "G\t17.2\tW\tbad\tIe".to_string()},
+    ]);
+}//end data_input_layout_tab_delimited()
+
+/// Test 3 for config input_layout in crate::data::read_data_from_file(),
+/// with the header and value in fixed columns, read from a config file
+#[test]
+pub fn data_input_layout_fixed_width() {
+    let mut config: crate::config_store::ConfigStore = serde_json::from_str(
+        "{\"input_layout\": {\"FixedWidth\": {\"header_cols\": {\"start\": 0, \"end\": 6}, \"value_cols\": {\"start\": 6, \"end\": 14}}}}"
+    ).unwrap();
+    assert_eq!(config.input_layout, crate::config_store::InputLayout::FixedWidth { header_cols: 0..6, value_cols: 6..14 });
+    config.read_row_headers = vec!["P".to_string(), "L".to_string(), "G".to_string()];
+    config.parse_row_flags = true;
+    let file_lines = [
+        "Test name\t:\tSample004-2222222",
+        "Standard\t : \tAverage",
+        "P        45.0",
+        "L       112.5 LOW",
+        "G        23.6",
+        "W         ",
+    ];
+    let (data, errs) = crate::data::read_data_from_file("fixed.txt", &file_lines.join("\n"), &config).unwrap();
+    assert!(errs.is_empty(), "{:?}", errs);
+    let row = |header: &str, value: f64| crate::data::Row::new(header.to_string(), value);
+    assert!(data.approx_eq(&crate::data::Data::new2("Sample004-2222222".to_string(), vec![
+        row("P", 45.), crate::data::Row::new1("L".to_string(), 112.5, Some("LOW".to_string())), row("G", 23.6),
+    ], Some("fixed.txt".to_string())), TEST_EPSILON), "{:?}", data);
+    config.read_row_mode = crate::config_store::ReadRowMode::Max;
+    config.read_max_rows = 4;
+    let (_, errs) = crate::data::read_data_from_file("fixed.txt", &file_lines.join("\n"), &config).unwrap();
+    assert_eq!(errs, vec![crate::data::DataParseError::MissingValue{line: 6, text: "W         ".to_string()}]);
+    config.input_layout = crate::config_store::InputLayout::FixedWidth { header_cols: 0..8, value_cols: 6..14 };
+    assert_eq!(config.validate().unwrap_err().len(), 1);
+}//end data_input_layout_fixed_width()
+
+/// Test 1 for crate::data::Data::approx_eq() and crate::data::Row::approx_eq()
+#[test]
+pub fn data_approx_eq1() {
+    let row = |header: &str, value: f64| crate::data::Row::new(header.to_string(), value);
+    assert!(row("P", 0.1 + 0.2).approx_eq(&row("P", 0.3), TEST_EPSILON));
+    assert_ne!(row("P", 0.1 + 0.2), row("P", 0.3));
+    assert!(!row("P", 0.3).approx_eq(&row("L", 0.3), TEST_EPSILON));
+    assert!(!row("P", 0.3).approx_eq(&row("P", 0.31), TEST_EPSILON));
+    assert!(row("P", f64::NAN).approx_eq(&row("P", f64::NAN), TEST_EPSILON));
+    assert!(!row("P", f64::INFINITY).approx_eq(&row("P", f64::NEG_INFINITY), f64::INFINITY));
+    assert!(!row("P", 1.).approx_eq(&crate::data::Row::new1("P".to_string(), 1., Some("LOW".to_string())), TEST_EPSILON));
+    let data = crate::data::Data::new1("Sample".to_string(), vec![row("P", 0.1 + 0.2), row("L", 2.)]);
+    assert!(data.approx_eq(&crate::data::Data::new1("Sample".to_string(), vec![row("P", 0.3), row("L", 2.)]), TEST_EPSILON));
+    assert!(!data.approx_eq(&crate::data::Data::new1("Sample".to_string(), vec![row("P", 0.3)]), TEST_EPSILON));
+    assert!(!data.approx_eq(&crate::data::Data::new1("Other".to_string(), vec![row("P", 0.3), row("L", 2.)]), TEST_EPSILON));
+}//end data_approx_eq1()
+
+#[test]
+/// Test 3 for crate::cli::parse_args(), for --dry-run
+pub fn cli_parse_args3() {
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+    let parsed = crate::cli::parse_args(&args(&["--input", "a.txt", "--dry-run"])).unwrap().unwrap();
+    assert!(parsed.dry_run);
+    assert_eq!(parsed.output, std::path::PathBuf::new());
+    assert!(crate::cli::parse_args(&args(&["-n", "-i", "a.txt", "-o", "out.xlsx"])).unwrap().unwrap().dry_run);
+    assert!(crate::cli::parse_args(&args(&["--dry-run"])).is_err());
+}//end cli_parse_args3()
+
+/// Test 1 for crate::process::validate_parsed()
+#[test]
+pub fn process_validate_parsed1() {
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_validate_parsed1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let good = dir.join("good.txt");
+    std::fs::write(&good, sample_file_lines().join("\n")).unwrap();
+    let bad = dir.join("bad.txt");
+    std::fs::write(&bad, "nothing to see here").unwrap();
+    let config = sample_config();
+    let parsed = crate::process::parse_inputs_with_progress(&[good.clone()], &config, &mut |_,_| true);
+    let summary = crate::process::validate_parsed(&parsed, &config);
+    assert!(summary.passed);
+    assert_eq!(summary.lines, vec![
+        format!("PASS {}: 3 row(s), 0 issue(s)", good.to_string_lossy()),
+        "1 of 1 file(s) passed, with 3 row(s) parsed.".to_string(),
+    ]);
+    let parsed = crate::process::parse_inputs_with_progress(&[good.clone(), bad.clone(), good.clone()], &config, &mut |_,_| true);
+    let summary = crate::process::validate_parsed(&parsed, &config);
+    assert!(!summary.passed);
+    assert!(summary.lines[1].starts_with(&format!("FAIL {}: ", bad.to_string_lossy())));
+    assert_eq!(summary.lines[3], "Repeated test name \"Sample001-1234567\" is used by 2 files");
+    assert_eq!(summary.lines[4], "2 of 3 file(s) passed, with 6 row(s) parsed.");
+    // nothing is written while checking
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+}//end process_validate_parsed1()
+
+/// Test 1 for crate::process::get_metadata() and crate::process::write_metadata_sheet()
+#[test]
+pub fn process_get_metadata1() {
+    let mut config = sample_config();
+    config.parse_row_flags = true;
+    let now = time::macros::datetime!(2024-05-01 13:04:09 UTC);
+    let sample_dates = vec![("Sample001".to_string(), "2024-04-30".to_string())];
+    let metadata = crate::process::get_metadata(&config, &["a.txt".to_string(), "b.txt".to_string()], &sample_dates, Some("Mixolab"), now);
+    let value = |name: &str| metadata.iter().find(|(key,_)| key == name).map(|(_,value)| value.clone());
+    assert_eq!(value("Exported"), Some("2024-05-01 13:04:09 UTC".to_string()));
+    assert_eq!(value("Program Version"), Some(env!("CARGO_PKG_VERSION").to_string()));
+    assert_eq!(value("Config Profile"), Some("Mixolab".to_string()));
+    assert_eq!(metadata.iter().filter(|(key,_)| key == "Input File").map(|(_,value)| value.as_str()).collect::<Vec<&str>>(), vec!["a.txt", "b.txt"]);
+    assert_eq!(value("Sample Date"), Some("Sample001: 2024-04-30".to_string()));
+    assert_eq!(value("read_start_header"), Some("Standard\t : \tAverage".to_string()));
+    assert_eq!(value("read_row_headers"), Some("P, L, G".to_string()));
+    assert_eq!(value("parse_row_flags"), Some("true".to_string()));
+    assert!(crate::process::get_metadata(&config, &[], &[], None, now).iter().all(|(key,_)| key != "Config Profile"));
+    let mut wb = crate::process::get_workbook();
+    crate::process::write_metadata_sheet(&mut wb, &metadata, "Metadata").unwrap();
+    assert!(wb.save_to_buffer().is_ok());
+
+    // the metadata sheet comes after the data, and is kept when appending
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-process_get_metadata1");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("good.txt");
+    std::fs::write(&input, sample_file_lines().join("\n")).unwrap();
+    let output = crate::process::OutputSpec::new(dir.join("output.xlsx"), "Metadata");
+    config.include_metadata_sheet = true;
+    let report = crate::process::run(&[input], &output, &config);
+    assert!(report.succeeded());
+    let names: Vec<&str> = report.sheets.iter().map(|record| record.sheet_name.as_str()).collect();
+    assert_eq!(names, vec!["Metadata", "Metadata 2"]);
+    assert!(matches!(&report.sheets[1].contents, crate::process::SheetContents::Metadata(metadata) if metadata.iter().any(|(key, value)| key == "Input File" && value == "good.txt")));
+}//end process_get_metadata1()
+
+/// Test 1 for crate::data::get_metadata_from_lines()
+#[test]
+pub fn data_get_metadata_from_lines1() {
+    let config = sample_config();
+    let mut file_lines = sample_file_lines();
+    file_lines.splice(2..2, [
+        "Operator\t:\tJane".to_string(),
+        "Date : 2024-04-30".to_string(),
+        "no colon on this line".to_string(),
+        " : no key".to_string(),
+        "Hydration\t:\t50.0 %".to_string(),
+        "Operator\t:\tSam".to_string(),
+    ]);
+    // lines after the start header aren't metadata
+    file_lines.push("Late\t:\tignored".to_string());
+    let (data, errs) = crate::data::read_data_from_file("sample-filename", &file_lines.join("\n"), &config).unwrap();
+    assert!(errs.is_empty());
+    assert_eq!(data.metadata, vec![
+        ("Operator".to_string(), "Sam".to_string()),
+        ("Date".to_string(), "2024-04-30".to_string()),
+        ("Hydration".to_string(), "50.0 %".to_string()),
+    ]);
+    assert_eq!(data.get_sample_date(), Some("2024-04-30"));
+    assert_eq!(data.get_metadata_value("Hydration"), Some("50.0 %"));
+    assert_eq!(data.get_metadata_value("Late"), None);
+}//end data_get_metadata_from_lines1()
+
+/// Test 1 for crate::process::get_metadata_columns(), and how they're written in the wide layout
+#[test]
+pub fn process_get_metadata_columns1() {
+    let mut config = sample_config();
+    let data = |test_name: &str, metadata: &[(&str, &str)]| crate::data::Data {
+        metadata: metadata.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect(),
+        ..crate::data::Data::new1(test_name.to_string(), vec![crate::data::Row::new("P".to_string(), 1.)])
+    };
+    let data = vec![data("A", &[("Operator", "Jane")]), data("B", &[("Date", "2024-04-30"), ("Operator", "Sam")])];
+    assert!(crate::process::get_metadata_columns(&data, &config).is_empty());
+    assert_eq!(crate::process::get_wide_table(&data, &config)[0], vec!["Test Name", "P"]);
+    config.include_file_metadata_columns = true;
+    assert_eq!(crate::process::get_metadata_columns(&data, &config), vec!["Operator", "Date"]);
+    assert_eq!(crate::process::get_wide_table(&data, &config), vec![
+        vec!["Test Name", "Operator", "Date", "P"],
+        vec!["A", "Jane", "", "1.00"],
+        vec!["B", "Sam", "2024-04-30", "1.00"],
+    ]);
+    assert_eq!(crate::process::get_autofilter_range(&data, &config), Some((2, 0, 4, 3)));
+    let mut wb = crate::process::get_workbook();
+    let range = crate::process::write_output_to_sheet(&mut wb, &data, "Sheet1", &config).unwrap().unwrap();
+    assert_eq!(range.header_cols, vec![("P".to_string(), 3)]);
+}//end process_get_metadata_columns1()
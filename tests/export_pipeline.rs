@@ -0,0 +1,62 @@
+//! Integration tests that drive a sample file through the
+//! whole parse and export pipeline, using only the public api.
+
+use std::path::PathBuf;
+
+use alveograph_exporter::{config_store::{ConfigStore, ReadRowMode, ReadStartMode}, data, process};
+
+/// Gets the path of a file in the tests/fixtures directory.
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(name)
+}//end fixture_path(name)
+
+/// Gets a config that reads P, L, and G from the Average section
+/// of the sample fixture.
+fn fixture_config() -> ConfigStore {
+    ConfigStore {
+        read_start_header: "Standard\t : \tAverage".to_string(),
+        read_row_headers: vec!["P","L","G"].iter().map(|s| s.to_string()).collect(),
+        read_max_rows: 3,
+        read_start_mode: ReadStartMode::Header,
+        read_row_mode: ReadRowMode::Header,
+        row_order_preference: vec!["P","L","G"].iter().map(|s| s.to_string()).collect(),
+        read_row_split_char: "\t".to_string(),
+        read_test_name_prefix: "Test name\t:\t".to_string(),
+        ..ConfigStore::default()
+    }//end struct construction
+}//end fixture_config()
+
+/// Reads the sample fixture, writes it to an xlsx file, and
+/// checks that both the parsed values and the saved file look right.
+#[test]
+pub fn parse_and_export_sample_fixture() {
+    let config = fixture_config();
+    let contents = std::fs::read_to_string(fixture_path("sample_alveograph.txt")).unwrap();
+    let (parsed, errs) = data::read_data_from_file("sample_alveograph", &contents, &config).unwrap();
+    assert!(errs.is_empty(), "{:?}", errs);
+    assert_eq!(parsed.test_name, "Sample001-1234567");
+    let headers: Vec<&str> = parsed.row_data.iter().map(|row| row.header.as_str()).collect();
+    assert_eq!(headers, vec!["P","L","G"]);
+    let values: Vec<f64> = parsed.row_data.iter().map(|row| row.value).collect();
+    assert_eq!(values, vec![1.,2.,3.]);
+
+    let combined = process::combine_data(vec![parsed], &config);
+    let dir = std::env::temp_dir().join("alveograph-exporter-test-parse_and_export_sample_fixture");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let xlsx_path = dir.join("sample.xlsx");
+    let mut workbook = process::get_workbook();
+    let range = process::write_output_to_sheet(&mut workbook, &combined, "Sample", &config).unwrap();
+    assert!(range.is_some());
+    workbook.save(&xlsx_path).unwrap();
+    assert!(xlsx_path.exists());
+    assert!(std::fs::metadata(&xlsx_path).unwrap().len() > 0);
+
+    // there's no xlsx reader dependency, so check the written values through the csv output instead
+    let csv_path = dir.join("sample.csv");
+    process::write_csv_for_layout(&combined, &csv_path, &config).unwrap();
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("Test Name,P,L,G"));
+    assert_eq!(lines.next(), Some("Sample001-1234567,1.00,2.00,3.00"));
+}//end parse_and_export_sample_fixture()